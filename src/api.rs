@@ -51,8 +51,10 @@ pub mod input_device;
 pub mod playwright;
 
 pub mod accessibility;
+pub mod binding_call;
 pub mod browser;
 pub mod browser_context;
+pub mod browser_server;
 pub mod browser_type;
 pub mod console_message;
 pub mod dialog;
@@ -61,11 +63,13 @@ pub mod element_handle;
 pub mod file_chooser;
 pub mod frame;
 pub mod js_handle;
+pub mod locator;
 pub mod page;
 pub mod request;
 pub mod response;
 pub mod route;
 pub mod selectors;
+pub mod tracing;
 pub mod video;
 pub mod websocket;
 pub mod worker;
@@ -74,8 +78,10 @@ pub use crate::imp::{core::DateTime, utils::*};
 
 pub use self::playwright::Playwright;
 pub use accessibility::Accessibility;
+pub use binding_call::BindingCall;
 pub use browser::Browser;
 pub use browser_context::BrowserContext;
+pub use browser_server::BrowserServer;
 pub use browser_type::BrowserType;
 pub use console_message::ConsoleMessage;
 pub use dialog::Dialog;
@@ -85,17 +91,18 @@ pub use file_chooser::FileChooser;
 pub use frame::Frame;
 pub use input_device::{Keyboard, Mouse, TouchScreen};
 pub use js_handle::JsHandle;
+pub use locator::{GetByRoleOptions, Locator};
 pub use page::Page;
 pub use request::Request;
 pub use response::Response;
 pub use route::Route;
 pub use selectors::Selectors;
+pub use tracing::Tracing;
 pub use video::Video;
 pub use websocket::WebSocket;
 pub use worker::Worker;
 
 // Artifact
-// BindingCall
 // Stream
 
 // Android
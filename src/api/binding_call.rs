@@ -0,0 +1,79 @@
+use crate::{
+    api::{ElementHandle, Frame, JsHandle, Page},
+    imp::{
+        binding_call::{BindingCall as Impl, BindingCallback, Handle},
+        core::*,
+        prelude::*
+    },
+    Error
+};
+use std::future::Future;
+
+/// A single call from the page into a function exposed with [`method: Page.exposeBinding`].
+pub struct BindingCall {
+    inner: Weak<Impl>
+}
+
+impl BindingCall {
+    pub(crate) fn new(inner: Weak<Impl>) -> Self { Self { inner } }
+
+    /// Name of the exposed binding that was called.
+    pub fn name(&self) -> Result<String, Error> { Ok(upgrade(&self.inner)?.name().to_owned()) }
+
+    /// The frame from which the binding was called.
+    pub fn frame(&self) -> Frame { Frame::new(weak_and_then(&self.inner, |rc| rc.frame())) }
+
+    /// The page from which the binding was called.
+    pub fn page(&self) -> Result<Option<Page>, Error> { self.frame().page() }
+
+    /// Arguments passed by the page, deserialized as `T`. Calls with several arguments deserialize into a tuple or a
+    /// `Vec`, such as `(String, i32)`. There are no arguments when the binding was exposed with `handle` enabled.
+    pub fn args<T: DeserializeOwned>(&self) -> Result<T, Error> {
+        Ok(de::from_value(upgrade(&self.inner)?.args())?)
+    }
+
+    /// The argument passed by the page, when the binding was exposed with `handle` enabled.
+    /// Returns `None` when there is no handle or when it refers to a DOM element; see [`BindingCall::element_handle`].
+    pub fn handle(&self) -> Result<Option<JsHandle>, Error> {
+        Ok(match upgrade(&self.inner)?.handle() {
+            Some(Handle::Js(x)) => Some(JsHandle::new(x)),
+            _ => None
+        })
+    }
+
+    /// Same as [`BindingCall::handle`] for an argument that is a DOM element.
+    pub fn element_handle(&self) -> Result<Option<ElementHandle>, Error> {
+        Ok(match upgrade(&self.inner)?.handle() {
+            Some(Handle::Element(x)) => Some(ElementHandle::new(x)),
+            _ => None
+        })
+    }
+}
+
+pub(crate) fn callback<F, Fut, T>(f: F) -> BindingCallback
+where
+    F: Fn(BindingCall) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<T, String>> + Send + 'static,
+    T: Serialize
+{
+    Arc::new(move |call| {
+        let fut = f(BindingCall::new(call));
+        Box::pin(async move {
+            let x = fut.await?;
+            ser::to_value(&x).map_err(|e| e.to_string())
+        })
+    })
+}
+
+pub(crate) fn function_callback<F, Fut, A, T>(f: F) -> BindingCallback
+where
+    F: Fn(A) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<T, String>> + Send + 'static,
+    A: DeserializeOwned,
+    T: Serialize
+{
+    callback(move |call: BindingCall| {
+        let fut = call.args().map(&f).map_err(|e| e.to_string());
+        async move { fut?.await }
+    })
+}
@@ -1,9 +1,9 @@
 pub use crate::imp::browser_type::{RecordHar, RecordVideo};
 use crate::{
-    api::BrowserContext,
+    api::{BrowserContext, Page},
     imp::{
         self,
-        browser::NewContextArgs,
+        browser::{ContextHandler, NewContextArgs},
         core::*,
         playwright::DeviceDescriptor,
         prelude::*,
@@ -11,10 +11,15 @@ use crate::{
     },
     Error
 };
+use std::future::Future;
 
 #[derive(Debug)]
 pub struct Browser {
-    inner: Weak<imp::browser::Browser>
+    inner: Weak<imp::browser::Browser>,
+    /// The connection to the Playwright server this browser was attached to with
+    /// [`BrowserType::connect_builder`](crate::api::BrowserType::connect_builder). The browser is owned by the
+    /// connection, so it is kept here rather than in the browser.
+    connection: Mutex<Option<Connection>>
 }
 
 impl PartialEq for Browser {
@@ -28,7 +33,19 @@ impl PartialEq for Browser {
 }
 
 impl Browser {
-    pub(crate) fn new(inner: Weak<imp::browser::Browser>) -> Self { Self { inner } }
+    pub(crate) fn new(inner: Weak<imp::browser::Browser>) -> Self {
+        Self {
+            inner,
+            connection: Mutex::default()
+        }
+    }
+
+    pub(crate) fn connected(inner: Weak<imp::browser::Browser>, connection: Connection) -> Self {
+        Self {
+            inner,
+            connection: Mutex::new(Some(connection))
+        }
+    }
 
     /// Returns an array of all open browser contexts. In a newly created browser, this will return zero browser contexts.
     ///
@@ -55,15 +72,46 @@ impl Browser {
 
     pub fn exists(&self) -> bool { self.inner.upgrade().is_some() }
 
+    /// Calls `handler` with every [`BrowserContext`] created in this browser from now on, each on a task of its own. This
+    /// includes the contexts created by [`Browser::context_builder`] as well as the ones the driver creates by itself.
+    pub fn on_context<F, Fut>(&self, handler: F) -> Result<(), Error>
+    where
+        F: Fn(BrowserContext) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static
+    {
+        let handler: ContextHandler = Arc::new(move |c| Box::pin(handler(BrowserContext::new(c))));
+        upgrade(&self.inner)?.add_context_handler(handler);
+        Ok(())
+    }
+
     /// new_context [`BrowserContext`]
     /// Creates a new browser context. It won't share cookies/cache with other browser contexts.
     pub fn context_builder(&self) -> ContextBuilder<'_, '_, '_, '_, '_, '_, '_> {
         ContextBuilder::new(self.inner.clone())
     }
 
+    /// Creates a new page in a new browser context. Closing this page will close the context as well.
+    ///
+    /// This is a convenience API that should only be used for the single-page scenarios and short snippets. Production code
+    /// and testing frameworks should explicitly create [`Browser::context_builder`] followed by
+    /// [`BrowserContext::new_page`] to control their exact life times.
+    ///
+    /// The page is the same as any other: its [`keyboard`](Page::keyboard) and [`mouse`](Page::mouse) drive it directly.
+    pub async fn new_page(&self) -> Result<Page, Arc<Error>> {
+        let p = upgrade(&self.inner)?
+            .new_page(NewContextArgs::default())
+            .await?;
+        Ok(Page::new(p))
+    }
+
     /// All temporary browsers will be closed when the connection is terminated, but
     /// it needs to be called explicitly to close it at any given time.
     pub async fn close(&self) -> Result<(), Arc<Error>> {
+        // Disconnecting from the server leaves its browser running for other clients
+        if let Some(connection) = self.connection.lock().unwrap().take() {
+            drop(connection);
+            return Ok(());
+        }
         let inner = match self.inner.upgrade() {
             None => return Ok(()),
             Some(inner) => inner
@@ -98,6 +146,9 @@ impl<'e, 'f, 'g, 'h, 'i, 'j, 'k> ContextBuilder<'e, 'f, 'g, 'h, 'i, 'j, 'k> {
         }
     }
 
+    /// Emulates `device`, as given by [`Playwright::device`](crate::api::Playwright::device): sets the user agent, the
+    /// viewport, the screen, the device scale factor, `is_mobile` and `has_touch` at once. Setters called afterwards
+    /// override the descriptor's values.
     pub fn set_device(self, device: &'e DeviceDescriptor) -> Self {
         DeviceDescriptor::set_context(device, self)
     }
@@ -134,12 +185,24 @@ impl<'e, 'f, 'g, 'h, 'i, 'j, 'k> ContextBuilder<'e, 'f, 'g, 'h, 'i, 'j, 'k> {
         offline: Option<bool>,
         /// A list of permissions to grant to all pages in this context. See [BrowserContext::grant_permissions] for more details.
         permissions: Option<&'h [String]>,
-        /// Network proxy settings to use with this context. Note that browser needs to be launched with the global proxy for this
-        /// option to work. If all contexts override the proxy, global proxy will be never used and can be any string, for example
-        /// `launch({ proxy: { server: 'per-context' } })`.
+        /// Network proxy settings to use with this context, overriding the ones the browser was launched with.
+        ///
+        /// > NOTE: For Chromium on Windows the browser needs to be launched with the global proxy for this option to work. If all
+        /// contexts override the proxy, global proxy will be never used and can be any string, for example
+        /// `Launcher::proxy(ProxySettings::new("http://per-context"))`.
         proxy: Option<ProxySettings>,
         /// Enables [HAR](http://www.softwareishard.com/blog/har-12-spec) recording for all pages into `recordHar.path` file. If not
-        /// specified, the HAR is not recorded. Make sure to await [`method: BrowserContext.close`] for the HAR to be saved.
+        /// specified, the HAR is not recorded. Make sure to await [`BrowserContext::close`] for the HAR to be saved.
+        ///
+        /// ```
+        /// # use playwright::api::{browser::RecordHar, HarMode};
+        /// # use std::path::Path;
+        /// let har = RecordHar {
+        ///     mode: Some(HarMode::Minimal),
+        ///     url_filter: Some("**/api/**"),
+        ///     ..RecordHar::new(Path::new("network.har"))
+        /// };
+        /// ```
         record_har: Option<RecordHar<'k>>,
         /// Enables video recording for all pages into `recordVideo.dir` directory. If not specified videos are not recorded. Make
         /// sure to await [`method: BrowserContext.close`] for videos to be saved.
@@ -151,6 +214,9 @@ impl<'e, 'f, 'g, 'h, 'i, 'j, 'k> ContextBuilder<'e, 'f, 'g, 'h, 'i, 'j, 'k> {
         /// obtained via [`method: BrowserContext.storageState`]. Either a path to the file with saved storage, or an object with
         /// the following fields:
         storage_state: Option<StorageState>,
+        /// Path to a file saved by [`BrowserContext::storage_state`] to populate the context with. Takes precedence over
+        /// `storage_state`.
+        storage_state_path: Option<PathBuf>,
         /// Changes the timezone of the context. See
         /// [ICU's metaZones.txt](https://cs.chromium.org/chromium/src/third_party/icu/source/data/misc/metaZones.txt?rcl=faee8bc70570192d82d2978a71e2a615788597d1)
         /// for a list of supported timezone IDs.
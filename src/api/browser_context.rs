@@ -1,14 +1,17 @@
 pub use crate::imp::browser_context::EventType;
 use crate::{
-    api::{Browser, Page},
+    api::{binding_call, page, route, BindingCall, Browser, Page, Request, Route, Tracing, Worker},
     imp::{
+        binding_call::BindingCallback,
         browser_context::{BrowserContext as Impl, Evt},
         core::*,
         prelude::*,
-        utils::{Cookie, Geolocation, StorageState}
+        utils::{Cookie, Geolocation, StorageState, UrlMatcher}
     },
     Error
 };
+use futures::stream::Stream;
+use std::future::Future;
 
 /// BrowserContexts provide a way to operate multiple independent browser sessions.
 ///
@@ -45,6 +48,54 @@ impl BrowserContext {
             .collect())
     }
 
+    /// All existing background pages in the context.
+    ///
+    /// > NOTE: Only Manifest V2 extensions have background pages, which current Chromium no longer loads. Manifest V3
+    /// extensions run in service workers instead, see [`BrowserContext::service_workers`].
+    pub fn background_pages(&self) -> Result<Vec<Page>, Error> {
+        Ok(upgrade(&self.inner)?
+            .background_pages()
+            .into_iter()
+            .map(Page::new)
+            .collect())
+    }
+
+    /// Calls `handler` with every background page created in this context, each on a task of its own. The handler stays
+    /// registered as long as the context is alive.
+    ///
+    /// > NOTE: Only works with Chromium browser's persistent context, see [`BrowserContext::background_pages`].
+    pub fn on_background_page<F, Fut>(&self, handler: F) -> Result<(), Error>
+    where
+        F: Fn(Page) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static
+    {
+        use futures::stream::StreamExt;
+        let mut events = self.subscribe_event()?;
+        spawn(async move {
+            while let Some(e) = events.next().await {
+                if let Ok(Event::BackgroundPage(p)) = e {
+                    spawn(handler(p));
+                }
+            }
+        });
+        Ok(())
+    }
+
+    /// > NOTE: Service workers are only supported on Chromium-based browsers.
+    ///
+    /// All existing service workers in the context, such as the background workers of Manifest V3 extensions. Extensions
+    /// only load in a persistent context launched with `--load-extension=<path>` and
+    /// `--disable-extensions-except=<path>`, and only in headless mode on the [`BrowserChannel::Chromium`] channel.
+    ///
+    /// [`BrowserChannel::Chromium`]: crate::api::BrowserChannel::Chromium
+    pub fn service_workers(&self) -> Result<Vec<Worker>, Error> {
+        Ok(upgrade(&self.inner)?
+            .service_workers()
+            .into_iter()
+            .map(Worker::new)
+            .collect())
+    }
+
     /// Returns the browser instance of the context. If it was launched as a persistent context None gets returned.
     pub fn browser(&self) -> Result<Option<Browser>, Error> {
         Ok(upgrade(&self.inner)?.browser().map(Browser::new))
@@ -66,6 +117,18 @@ impl BrowserContext {
         upgrade(&self.inner)?.set_default_timeout(timeout).await
     }
 
+    /// Maximum navigation time in milliseconds currently applied to pages of this context.
+    /// Defaults to 30 seconds until changed with [`BrowserContext::set_default_navigation_timeout`].
+    pub fn default_navigation_timeout(&self) -> Result<u32, Error> {
+        Ok(upgrade(&self.inner)?.default_navigation_timeout())
+    }
+
+    /// Maximum time in milliseconds currently applied to operations of this context that accept a `timeout`.
+    /// Defaults to 30 seconds until changed with [`BrowserContext::set_default_timeout`].
+    pub fn default_timeout(&self) -> Result<u32, Error> {
+        Ok(upgrade(&self.inner)?.default_timeout())
+    }
+
     /// If no URLs are specified, this method returns all cookies. If URLs are specified, only cookies that affect those URLs
     /// are returned.
     pub async fn cookies(&self, urls: &[String]) -> ArcResult<Vec<Cookie>> {
@@ -73,15 +136,31 @@ impl BrowserContext {
     }
 
     /// Adds cookies into this browser context. All pages within this context will have these cookies installed.
+    ///
+    /// Every cookie needs either a `url`, or both a `domain` and a `path`; otherwise nothing is added and
+    /// [`Error::InvalidCookie`](crate::Error::InvalidCookie) is returned.
     pub async fn add_cookies(&self, cookies: &[Cookie]) -> ArcResult<()> {
         upgrade(&self.inner)?.add_cookies(cookies).await
     }
 
+    /// Returns the cookie named `name`, among those that affect `url` when it is given. When several cookies share the
+    /// name, such as ones set for different domains, the first one returned by [`BrowserContext::cookies`] is used.
+    pub async fn cookie(&self, name: &str, url: Option<&str>) -> ArcResult<Option<Cookie>> {
+        let urls: Vec<String> = url.into_iter().map(ToOwned::to_owned).collect();
+        let cookies = upgrade(&self.inner)?.cookies(&urls).await?;
+        Ok(cookies.into_iter().find(|c| c.name == name))
+    }
+
     /// Clears context cookies.
     pub async fn clear_cookies(&self) -> ArcResult<()> {
         upgrade(&self.inner)?.clear_cookies().await
     }
 
+    /// Removes the cookies named `name`, whatever their domain and path. Other cookies are kept.
+    pub async fn delete_cookie(&self, name: &str) -> ArcResult<()> {
+        upgrade(&self.inner)?.delete_cookies(name).await
+    }
+
     /// Grants specified permissions to the browser context. Only grants corresponding permissions to the given origin if
     /// specified.
     ///
@@ -133,11 +212,28 @@ impl BrowserContext {
     /// ```
     /// > NOTE: Consider using [`method: BrowserContext.grantPermissions`] to grant permissions for the browser context pages to
     /// read its geolocation.
+    ///
+    /// Passing `None` removes the emulated location instead of keeping the last one, so pages get a "position unavailable"
+    /// error from `getCurrentPosition` until a new location is set.
     pub async fn set_geolocation(&self, geolocation: Option<&Geolocation>) -> ArcResult<()> {
         upgrade(&self.inner)?.set_geolocation(geolocation).await
     }
 
+    /// Saves every download started from now on into `dir` as well, under its [`Download::suggested_filename`], so that
+    /// different phases of a test can collect their downloads in different directories. Downloads already in progress
+    /// are saved where they were going to be; `None` stops saving them.
+    ///
+    /// > NOTE: The context must be created with `accept_downloads(true)`.
+    ///
+    /// [`Download::suggested_filename`]: crate::api::Download::suggested_filename
+    pub fn set_download_path(&self, dir: Option<PathBuf>) -> Result<(), Error> {
+        upgrade(&self.inner)?.set_download_path(dir);
+        Ok(())
+    }
+
     /// Sets whether to emulate network being offline for the browser context.
+    ///
+    /// While offline, navigations fail with [`Error::Network`](crate::Error::Network).
     pub async fn set_offline(&self, offline: bool) -> ArcResult<()> {
         upgrade(&self.inner)?.set_offline(offline).await
     }
@@ -174,6 +270,8 @@ impl BrowserContext {
     /// header, page-specific header value will be used instead of the browser context header value.
     ///
     /// > NOTE: [`method: BrowserContext.setExtraHTTPHeaders`] does not guarantee the order of headers in the outgoing requests.
+    ///
+    /// Header names are lowercased before they are sent.
     pub async fn set_extra_http_headers<T>(&self, headers: T) -> ArcResult<()>
     where
         T: IntoIterator<Item = (String, String)>
@@ -181,13 +279,97 @@ impl BrowserContext {
         upgrade(&self.inner)?.set_extra_http_headers(headers).await
     }
 
-    // async fn expose_binding(&mut self) -> Result<(), Error> { unimplemented!() }
+    /// The method adds a function called `name` on the `window` object of every frame in every page in the context. When
+    /// called, the function executes `callback` and returns a [Promise] which resolves to the return value of `callback`.
+    /// If the `callback` returns an `Err`, the promise is rejected with its message.
+    ///
+    /// The first argument of the `callback` function contains information about the caller.
+    ///
+    /// See [`method: Page.exposeBinding`] for page-only version.
+    ///
+    /// ```js
+    /// await context.exposeBinding('pageURL', ({ page }) => page.url());
+    /// const page = await context.newPage();
+    /// await page.setContent(`
+    ///  <script>
+    ///    async function onClick() {
+    ///      document.querySelector('div').textContent = await window.pageURL();
+    ///    }
+    ///  </script>
+    ///  <button onclick="onClick()">Click me</button>
+    ///  <div></div>
+    /// `);
+    /// ```
+    pub fn expose_binding_builder<'a, F, Fut, T>(
+        &self,
+        name: &'a str,
+        callback: F
+    ) -> ExposeBindingBuilder<'a>
+    where
+        F: Fn(BindingCall) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, String>> + Send + 'static,
+        T: Serialize
+    {
+        ExposeBindingBuilder::new(self.inner.clone(), name, binding_call::callback(callback))
+    }
 
-    // async fn expose_function(&mut self) -> Result<(), Error> { unimplemented!() }
+    /// The method adds a function called `name` on the `window` object of every frame in every page in the context. When
+    /// called, the function executes `callback` with its arguments deserialized as `A`, and returns a [Promise] which
+    /// resolves to the return value of `callback`.
+    ///
+    /// See [`method: Page.exposeFunction`] for page-only version.
+    pub async fn expose_function<F, Fut, A, T>(&self, name: &str, callback: F) -> ArcResult<()>
+    where
+        F: Fn(A) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, String>> + Send + 'static,
+        A: DeserializeOwned,
+        T: Serialize
+    {
+        upgrade(&self.inner)?
+            .expose_binding(name, false, binding_call::function_callback(callback))
+            .await
+    }
 
-    // async fn route(&mut self) -> Result<(), Error> { unimplemented!() }
+    /// Removes a function installed with [`method: BrowserContext.exposeFunction`] or
+    /// [`method: BrowserContext.exposeBinding`] from every page in the context. As with
+    /// [`Page::remove_exposed_function`], the function stays on the `window` object but calls to it are rejected until
+    /// the name is exposed again. Removing a name that was never exposed does nothing.
+    pub async fn remove_exposed_function(&self, name: &str) -> ArcResult<()> {
+        upgrade(&self.inner)?.remove_exposed_binding(name);
+        Ok(())
+    }
 
-    // async fn unroute(&mut self) -> Result<(), Error> { unimplemented!() }
+    /// Routing provides the capability to modify network requests that are made by any page in the browser context.
+    ///
+    /// Once a route is enabled, every request matching the url pattern will stall unless it's continued, fulfilled or
+    /// aborted. `url` is a glob such as `**/*.png` or a [`regex::Regex`], see [`UrlMatcher`]. Handlers registered with
+    /// [`Page::route`] take precedence over the browser context ones when both match a request.
+    ///
+    /// ```js
+    /// const context = await browser.newContext();
+    /// await context.route('**/*.{png,jpg,jpeg}', route => route.abort());
+    /// ```
+    ///
+    /// The handler runs on a task of its own, so it may await other requests before handling the route, including
+    /// requests that are themselves routed through this handler.
+    pub async fn route<M, F, Fut>(&self, url: M, handler: F) -> ArcResult<()>
+    where
+        M: Into<UrlMatcher>,
+        F: Fn(Route, Request) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static
+    {
+        upgrade(&self.inner)?
+            .route(url.into(), route::handler(handler))
+            .await
+    }
+
+    /// Removes the handlers registered with [`BrowserContext::route`] for `url`.
+    pub async fn unroute<M: Into<UrlMatcher>>(&self, url: M) -> ArcResult<()> {
+        upgrade(&self.inner)?.unroute(&url.into()).await
+    }
+
+    /// Tracing of this context; see [`Tracing`].
+    pub fn tracing(&self) -> Tracing { Tracing::new(weak_and_then(&self.inner, |rc| rc.tracing())) }
 
     pub async fn expect_event(&self, evt: EventType) -> Result<Event, Error> {
         let stream = upgrade(&self.inner)?.subscribe_event();
@@ -196,9 +378,12 @@ impl BrowserContext {
     }
 
     /// Returns storage state for this browser context, contains current cookies and local storage snapshot.
-    pub async fn storage_state(&self) -> ArcResult<StorageState> {
-        // path no supported
-        upgrade(&self.inner)?.storage_state().await
+    ///
+    /// When `path` is given the state is also saved there as JSON, ready to be passed to
+    /// [`ContextBuilder::storage_state_path`](crate::api::browser::ContextBuilder::storage_state_path) to start a new
+    /// context already logged in.
+    pub async fn storage_state(&self, path: Option<&Path>) -> ArcResult<StorageState> {
+        upgrade(&self.inner)?.storage_state(path).await
     }
 
     /// All temporary browsers will be closed when the connection is terminated, but
@@ -214,15 +399,62 @@ impl BrowserContext {
 
     subscribe_event! {}
 
-    // background_page for chromium
+    /// Yields the lifecycle of every page opened in this context from now on, popups included:
+    /// [`PageLifecycle::Created`] when it opens, then [`PageLifecycle::Closed`] once it is closed. The stream ends when
+    /// the context is closed and all of its pages have been reported closed.
+    pub fn pages_stream(&self) -> Result<impl Stream<Item = PageLifecycle>, Error> {
+        use futures::{channel::mpsc, future, stream::StreamExt};
+        let inner = self.inner.clone();
+        let mut events = self.subscribe_event()?;
+        let (tx, rx) = mpsc::unbounded();
+        spawn(async move {
+            while let Some(e) = events.next().await {
+                match e {
+                    Ok(Event::Page(p)) => {
+                        // Subscribed before checking that the page is still open, so that its close can't be missed
+                        let page_events = p.subscribe_event();
+                        if tx
+                            .unbounded_send(PageLifecycle::Created(p.clone()))
+                            .is_err()
+                        {
+                            break;
+                        }
+                        let is_open = BrowserContext::new(inner.clone())
+                            .pages()
+                            .map(|pages| pages.contains(&p))
+                            .unwrap_or_default();
+                        let tx = tx.clone();
+                        spawn(async move {
+                            if let (true, Ok(page_events)) = (is_open, page_events) {
+                                let mut closes = page_events
+                                    .filter(|e| future::ready(matches!(e, Ok(page::Event::Close))));
+                                closes.next().await;
+                            }
+                            tx.unbounded_send(PageLifecycle::Closed(p)).ok();
+                        });
+                    }
+                    Ok(Event::Close) => break,
+                    _ => {}
+                }
+            }
+        });
+        Ok(rx)
+    }
+
     // new_cdp_session
-    // service_workers
 }
 
 #[derive(Debug, PartialEq)]
 pub enum Event {
-    // BackgroundPage for chromium persistent
-    // ServiceWorker
+    /// > NOTE: Only works with Chromium browser's persistent context.
+    ///
+    /// Emitted when new background page is created in the context. Chromium no longer runs background pages, see
+    /// [`Event::ServiceWorker`].
+    ///
+    /// ```js
+    /// const backgroundPage = await context.waitForEvent('backgroundpage');
+    /// ```
+    BackgroundPage(Page),
     /// Emitted when Browser context gets closed. This might happen because of one of the following:
     /// - Browser context is closed.
     /// - Browser application is closed or crashed.
@@ -242,14 +474,68 @@ pub enum Event {
     /// ]);
     /// console.log(await newPage.evaluate('location.href'));
     /// ```
-    Page(Page)
+    Page(Page),
+    /// > NOTE: Service workers are only supported on Chromium-based browsers.
+    ///
+    /// Emitted when new service worker is created in the context.
+    ServiceWorker(Worker)
+}
+
+/// Items of [`BrowserContext::pages_stream`].
+#[derive(Debug, PartialEq)]
+pub enum PageLifecycle {
+    /// The page was opened, as with [`Event::Page`].
+    Created(Page),
+    /// The page was closed.
+    Closed(Page)
 }
 
 impl From<Evt> for Event {
     fn from(e: Evt) -> Event {
         match e {
+            Evt::BackgroundPage(w) => Event::BackgroundPage(Page::new(w)),
             Evt::Close => Event::Close,
-            Evt::Page(w) => Event::Page(Page::new(w))
+            Evt::Page(w) => Event::Page(Page::new(w)),
+            Evt::ServiceWorker(w) => Event::ServiceWorker(Worker::new(w))
         }
     }
 }
+
+/// [`BrowserContext::expose_binding_builder`]
+pub struct ExposeBindingBuilder<'a> {
+    inner: Weak<Impl>,
+    name: &'a str,
+    callback: BindingCallback,
+    handle: bool
+}
+
+impl<'a> ExposeBindingBuilder<'a> {
+    pub(crate) fn new(inner: Weak<Impl>, name: &'a str, callback: BindingCallback) -> Self {
+        Self {
+            inner,
+            name,
+            callback,
+            handle: false
+        }
+    }
+
+    pub async fn expose_binding(self) -> ArcResult<()> {
+        let Self {
+            inner,
+            name,
+            callback,
+            handle
+        } = self;
+        upgrade(&inner)?
+            .expose_binding(name, handle, callback)
+            .await
+    }
+
+    /// Whether to pass the argument as a handle, instead of passing by value. When passing a handle, only one argument is
+    /// supported. Defaults to `false`. A name exposed again must keep the value it was first exposed with, or
+    /// [`Error::BindingNeedsHandle`] is returned.
+    pub fn handle(mut self, x: bool) -> Self {
+        self.handle = x;
+        self
+    }
+}
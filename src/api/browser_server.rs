@@ -0,0 +1,25 @@
+use crate::{imp::browser_server::BrowserServer as Impl, Error};
+
+/// A browser launched by [`BrowserType::launch_server_builder`] that other processes can connect to
+/// through its websocket endpoint.
+///
+/// The server is closed when this value is dropped.
+///
+/// [`BrowserType::launch_server_builder`]: crate::api::BrowserType::launch_server_builder
+#[derive(Debug)]
+pub struct BrowserServer {
+    inner: Impl
+}
+
+impl BrowserServer {
+    pub(crate) fn new(inner: Impl) -> Self { Self { inner } }
+
+    /// Browser websocket url.
+    ///
+    /// Browser websocket endpoint which can be used as an argument to [`method: BrowserType.connect`] to establish connection
+    /// to the browser.
+    pub fn ws_endpoint(&self) -> &str { self.inner.ws_endpoint() }
+
+    /// Closes the browser gracefully and makes sure the process is terminated.
+    pub async fn close(self) -> Result<(), Error> { self.inner.close().await }
+}
@@ -1,9 +1,13 @@
 pub use crate::imp::browser_type::{RecordHar, RecordVideo};
 use crate::{
-    api::{browser::Browser, browser_context::BrowserContext, playwright::DeviceDescriptor},
+    api::{
+        browser::Browser, browser_context::BrowserContext, browser_server::BrowserServer,
+        playwright::DeviceDescriptor
+    },
     imp::{
         browser_type::{
-            BrowserType as Impl, ConnectOverCdpArgs, LaunchArgs, LaunchPersistentContextArgs
+            BrowserType as Impl, ConnectArgs, ConnectOverCdpArgs, LaunchArgs,
+            LaunchPersistentContextArgs, LaunchServerArgs
         },
         core::*,
         prelude::*,
@@ -84,12 +88,30 @@ impl BrowserType {
     /// > NOTE: Connecting over the Chrome DevTools Protocol is only supported for Chromium-based browsers.
     /// A CDP websocket endpoint or http url to connect to. For example `http://localhost:9222/` or
     /// `ws://127.0.0.1:9222/devtools/browser/387adf4c-243f-4051-a181-46798f4a46f4`.
+    ///
+    /// Fails with [`Error::ConnectOverCdp`] if the endpoint cannot be reached within the timeout.
     pub fn connect_over_cdp_builder<'a>(&self, endpoint_url: &'a str) -> ConnectOverCdpBuilder<'a> {
         ConnectOverCdpBuilder::new(self.inner.clone(), endpoint_url)
     }
 
-    // connect
-    // launch_server
+    /// Launches a browser server that other processes can connect to through
+    /// [`BrowserServer::ws_endpoint`].
+    ///
+    /// The server runs in its own driver process, which is terminated when the returned [`BrowserServer`] is closed or
+    /// dropped.
+    pub fn launch_server_builder(&self) -> LaunchServerBuilder<'_, '_> {
+        LaunchServerBuilder::new(self.inner.clone())
+    }
+
+    /// Attaches Playwright to a browser server started by [`BrowserType::launch_server_builder`] or by the driver's
+    /// `launch-server` command, such as `ws://127.0.0.1:46221/c45a2e1d2b4f`.
+    ///
+    /// Unlike [`BrowserType::connect_over_cdp_builder`] this speaks the Playwright protocol, relayed over the websocket,
+    /// so any browser type is supported and every object obtained through the returned [`Browser`] lives on the server.
+    /// [`Browser::close`] disconnects from the server and leaves its browser running for other clients.
+    pub fn connect_builder<'a>(&self, ws_endpoint: &'a str) -> ConnectBuilder<'a> {
+        ConnectBuilder::new(self.inner.clone(), ws_endpoint)
+    }
 }
 
 /// [`BrowserType::launcher`]
@@ -139,7 +161,8 @@ impl<'a, 'b, 'c> Launcher<'a, 'b, 'c> {
         /// **Chromium-only** Whether to auto-open a Developer Tools panel for each tab. If this option is `true`, the `headless`
         /// option will be set `false`.
         devtools: Option<bool>,
-        /// Network proxy settings.
+        /// Network proxy settings, used by the contexts that don't set their own with
+        /// [`ContextBuilder::proxy`](crate::api::browser::ContextBuilder::proxy).
         proxy: Option<ProxySettings>,
         /// If specified, accepted downloads are downloaded into this directory. Otherwise, temporary directory is created and is
         /// deleted when browser is closed.
@@ -190,6 +213,7 @@ impl<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i, 'j, 'k>
         }
     }
 
+    /// Emulates `device` like [`ContextBuilder::set_device`](crate::api::browser::ContextBuilder::set_device).
     pub fn set_device(self, device: &'e DeviceDescriptor) -> Self {
         DeviceDescriptor::set_persistent_context(device, self)
     }
@@ -302,6 +326,50 @@ impl<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i, 'j, 'k>
     //#[doc = "**DEPRECATED** Use `recordVideo` instead."] videos_path: Option<path>,
 }
 
+/// [`BrowserType::launch_server_builder`]
+pub struct LaunchServerBuilder<'a, 'b> {
+    inner: Weak<Impl>,
+    args: LaunchServerArgs<'a, 'b>
+}
+
+impl<'a, 'b> LaunchServerBuilder<'a, 'b> {
+    /// Fails with [`Error::Timeout`] if the server hasn't reported its endpoint within `timeout`, or with
+    /// [`Error::BrowserServerExited`] if it exited first. Either becomes [`Error::DriverStartup`] carrying what the
+    /// server wrote to stderr, if anything.
+    pub async fn launch_server(self) -> Result<BrowserServer, Arc<Error>> {
+        let Self { inner, args } = self;
+        let r = upgrade(&inner)?.launch_server(args).await?;
+        Ok(BrowserServer::new(r))
+    }
+
+    fn new(inner: Weak<Impl>) -> Self {
+        Self {
+            inner,
+            args: LaunchServerArgs::default()
+        }
+    }
+
+    setter! {
+        /// Path to a browser executable to run instead of the bundled one.
+        executable: Option<&'a Path>,
+        /// Additional arguments to pass to the browser instance.
+        args: Option<&'b [String]>,
+        /// Maximum time in milliseconds to wait for the browser instance to start. Defaults to `30000` (30 seconds). Pass `0` to
+        /// disable timeout.
+        timeout: Option<f64>,
+        /// Whether to run browser in headless mode. Defaults to `true` unless the `devtools` option is `true`.
+        headless: Option<bool>,
+        /// Enable Chromium sandboxing. Defaults to `false`.
+        chromium_sandbox: Option<bool>,
+        /// Browser distribution channel.
+        channel: Option<BrowserChannel>,
+        /// Port to use for the web socket. Defaults to 0 that picks any available port.
+        port: Option<u16>,
+        /// Path at which to serve the Browser Server. For security, this defaults to an unguessable string.
+        ws_path: Option<String>
+    }
+}
+
 pub struct ConnectOverCdpBuilder<'a> {
     inner: Weak<Impl>,
     args: ConnectOverCdpArgs<'a>
@@ -332,3 +400,35 @@ impl<'a> ConnectOverCdpBuilder<'a> {
         slowmo: Option<f64>
     }
 }
+
+/// [`BrowserType::connect_builder`]
+pub struct ConnectBuilder<'a> {
+    inner: Weak<Impl>,
+    args: ConnectArgs<'a>
+}
+
+impl<'a> ConnectBuilder<'a> {
+    pub async fn connect(self) -> ArcResult<Browser> {
+        let Self { inner, args } = self;
+        let (r, connection) = upgrade(&inner)?.connect(args).await?;
+        Ok(Browser::connected(r, connection))
+    }
+
+    fn new(inner: Weak<Impl>, ws_endpoint: &'a str) -> Self {
+        Self {
+            inner,
+            args: ConnectArgs::new(ws_endpoint)
+        }
+    }
+
+    setter! {
+        /// Additional HTTP headers to be sent with web socket connect request.
+        headers: Option<HashMap<String, String>>,
+        /// Maximum time in milliseconds to wait for the connection to be established. Defaults to `30000` (30 seconds).
+        /// Pass `0` to disable timeout.
+        timeout: Option<f64>,
+        /// Slows down Playwright operations by the specified amount of milliseconds. Useful so that you can see what is going on.
+        /// Defaults to 0.
+        slowmo: Option<f64>
+    }
+}
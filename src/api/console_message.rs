@@ -1,36 +1,38 @@
 use crate::{
-    api::JsHandle,
+    api::{JsHandle, Page},
     imp::{console_message::ConsoleMessage as Impl, core::*, prelude::*, utils::SourceLocation}
 };
 
 /// `ConsoleMessage` objects are dispatched by page via the [page::Event::Console](crate::api::page::Event::Console) event.
 #[derive(Clone)]
 pub struct ConsoleMessage {
-    inner: Weak<Impl>
+    inner: Arc<Impl>
 }
 
 impl ConsoleMessage {
-    pub(crate) fn new(inner: Weak<Impl>) -> Self { Self { inner } }
+    pub(crate) fn new(inner: Arc<Impl>) -> Self { Self { inner } }
 
     /// One of the following values: `'log'`, `'debug'`, `'info'`, `'error'`, `'warning'`, `'dir'`, `'dirxml'`, `'table'`,
     /// `'trace'`, `'clear'`, `'startGroup'`, `'startGroupCollapsed'`, `'endGroup'`, `'assert'`, `'profile'`, `'profileEnd'`,
     /// `'count'`, `'timeEnd'`.
-    pub fn r#type(&self) -> Result<String, Error> { Ok(upgrade(&self.inner)?.r#type().into()) }
+    pub fn r#type(&self) -> Result<String, Error> { Ok(self.inner.r#type().into()) }
 
     /// The text of the console message.
-    pub fn text(&self) -> Result<String, Error> { Ok(upgrade(&self.inner)?.text().into()) }
+    pub fn text(&self) -> Result<String, Error> { Ok(self.inner.text().into()) }
 
     /// URL of the resource followed by 0-based line and column numbers in the resource formatted as `URL:line:column`.
-    pub fn location(&self) -> Result<SourceLocation, Error> {
-        Ok(upgrade(&self.inner)?.location().to_owned())
-    }
+    pub fn location(&self) -> Result<SourceLocation, Error> { Ok(self.inner.location().to_owned()) }
 
     /// List of arguments passed to a `console` function call.
+    ///
+    /// The handles are resolved when this is called and can be inspected with [`JsHandle::get_property`] or
+    /// [`JsHandle::json_value`]. They stay valid until the page navigates or they are disposed with [`JsHandle::dispose`].
     pub fn args(&self) -> Result<Vec<JsHandle>, Error> {
-        Ok(upgrade(&self.inner)?
-            .args()
-            .iter()
-            .map(|x| JsHandle::new(x.clone()))
-            .collect())
+        Ok(self.inner.args()?.into_iter().map(JsHandle::new).collect())
     }
+
+    pub(crate) async fn dispose_args(&self) { self.inner.dispose_args().await }
+
+    /// The page that produced this console message, if any.
+    pub fn page(&self) -> Option<Page> { self.inner.page().map(Page::new) }
 }
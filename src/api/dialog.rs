@@ -1,4 +1,12 @@
-use crate::imp::{core::*, dialog::Dialog as Impl, prelude::*};
+use crate::{
+    api::Page,
+    imp::{
+        core::*,
+        dialog::{Dialog as Impl, DialogHandler},
+        prelude::*
+    }
+};
+use std::future::Future;
 
 /// `Dialog` objects are dispatched by page via the [page::Event::Dialog](crate::api::page::Event::Dialog) event.
 ///
@@ -19,10 +27,11 @@ use crate::imp::{core::*, dialog::Dialog as Impl, prelude::*};
 /// })();
 /// ```
 ///
-/// > NOTE: Dialogs are dismissed automatically, unless there is a [`event: Page.dialog`] listener. When listener is
-/// present, it **must** either [`method: Dialog.accept`] or [`method: Dialog.dismiss`] the dialog - otherwise the page will
+/// > NOTE: Dialogs are dismissed automatically, unless a handler is set with [`Page::on_dialog`]. When a handler is
+/// set, it **must** either [`Dialog::accept`] or [`Dialog::dismiss`] the dialog - otherwise the page will
 /// [freeze](https://developer.mozilla.org/en-US/docs/Web/JavaScript/EventLoop#never_blocking) waiting for the dialog, and
 /// actions like click will never finish.
+#[derive(Clone)]
 pub struct Dialog {
     inner: Weak<Impl>
 }
@@ -30,15 +39,36 @@ pub struct Dialog {
 impl Dialog {
     pub(crate) fn new(inner: Weak<Impl>) -> Self { Self { inner } }
 
-    ///// Returns when the dialog has been accepted.
-    ///// A text to enter in prompt. Does not cause any effects if the dialog's `type` is not prompt. Optional.
-    // fn accept(&self, prompt_text: Option<String>) -> Result<(), Arc<Error>> { todo!() }
-    ///// If dialog is prompt, returns default prompt value. Otherwise, returns empty string.
-    // fn default_value(&self) -> Result<String, Error> { todo!() }
-    ///// Returns when the dialog has been dismissed.
-    // fn dismiss(&self) -> Result<(), Arc<Error>> { todo!() }
-    ///// A message displayed in the dialog.
-    // fn message(&self) -> Result<String, Error> { todo!() }
-    ///// Returns dialog's type, can be one of `alert`, `beforeunload`, `confirm` or `prompt`.
-    // fn r#type(&self) -> Result<String, Error> { todo!() }
+    /// Returns dialog's type, can be one of `alert`, `beforeunload`, `confirm` or `prompt`.
+    pub fn r#type(&self) -> Result<String, Error> { Ok(upgrade(&self.inner)?.r#type().into()) }
+
+    /// A message displayed in the dialog.
+    pub fn message(&self) -> Result<String, Error> { Ok(upgrade(&self.inner)?.message().into()) }
+
+    /// If dialog is prompt, returns default prompt value. Otherwise, returns empty string.
+    pub fn default_value(&self) -> Result<String, Error> {
+        Ok(upgrade(&self.inner)?.default_value().into())
+    }
+
+    /// The page that initiated this dialog, if available.
+    pub fn page(&self) -> Result<Option<Page>, Error> {
+        Ok(upgrade(&self.inner)?.page().map(Page::new))
+    }
+
+    /// Returns when the dialog has been accepted.
+    /// A text to enter in prompt. Does not cause any effects if the dialog's `type` is not prompt.
+    pub async fn accept(&self, prompt_text: Option<&str>) -> ArcResult<()> {
+        upgrade(&self.inner)?.accept(prompt_text).await
+    }
+
+    /// Returns when the dialog has been dismissed.
+    pub async fn dismiss(&self) -> ArcResult<()> { upgrade(&self.inner)?.dismiss().await }
+}
+
+pub(crate) fn handler<F, Fut>(f: F) -> DialogHandler
+where
+    F: Fn(Dialog) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static
+{
+    Arc::new(move |dialog| Box::pin(f(Dialog::new(dialog))))
 }
@@ -1,5 +1,5 @@
 use crate::{
-    api::Frame,
+    api::{locator, Frame, Locator},
     imp::{
         core::*,
         element_handle::{
@@ -9,8 +9,8 @@ use crate::{
         },
         prelude::*,
         utils::{
-            ElementState, File, FloatRect, KeyboardModifier, MouseButton, Position, ScreenshotType,
-            WaitForSelectorState
+            ElementState, FilePayload, FloatRect, InputFiles, KeyboardModifier, MouseButton,
+            Position, ScreenshotAnimations, ScreenshotType, WaitForSelectorState
         }
     }
 };
@@ -64,6 +64,9 @@ impl ElementHandle {
         Ok(upgrade(&self.inner)?.guid().to_owned())
     }
 
+    /// Releases the element so that it can be garbage collected. The handle can't be used afterwards.
+    pub async fn dispose(&mut self) -> ArcResult<()> { upgrade(&self.inner)?.dispose().await }
+
     /// The method finds an element matching the specified selector in the `ElementHandle`'s subtree.
     /// If no elements match the selector, returns `null`.
     pub async fn query_selector(&self, selector: &str) -> ArcResult<Option<ElementHandle>> {
@@ -286,7 +289,8 @@ impl ElementHandle {
     /// Returns the buffer with the captured screenshot.
     ///
     /// This method waits for the actionability checks, then scrolls element into view before taking a
-    /// screenshot. If the element is detached from DOM, the method throws an error.
+    /// screenshot. The image is clipped to the element's bounding box, so its size is the element's
+    /// size times the device scale factor. If the element is detached from DOM, the method throws an error.
     pub async fn screenshot_builder(&self) -> ScreenshotBuilder<'_> {
         ScreenshotBuilder::new(self.inner.clone())
     }
@@ -393,8 +397,10 @@ impl ElementHandle {
     ///
     /// Sets the value of the file input to these file paths or files. If some of the `filePaths` are relative paths, then they
     /// are resolved relative to the the current working directory. For empty array, clears the selected files.
-    pub fn set_input_files_builder(&self, file: File) -> SetInputFilesBuilder {
-        SetInputFilesBuilder::new(self.inner.clone(), file)
+    ///
+    /// Files are given either all as paths or all as [`FilePayload`]s.
+    pub fn set_input_files_builder(&self) -> SetInputFilesBuilder {
+        SetInputFilesBuilder::new(self.inner.clone())
     }
 
     // eval_on_selector
@@ -624,17 +630,29 @@ type_builder!(PressBuilder, PressArgs, key, press);
 
 pub struct ScreenshotBuilder<'a> {
     inner: Weak<Impl>,
-    args: ScreenshotArgs<'a>
+    args: ScreenshotArgs<'a>,
+    mask: Vec<Locator>
 }
 
 impl<'a> ScreenshotBuilder<'a> {
     pub(crate) fn new(inner: Weak<Impl>) -> Self {
         let args = ScreenshotArgs::default();
-        Self { inner, args }
+        Self {
+            inner,
+            args,
+            mask: Vec::new()
+        }
     }
 
     pub async fn screenshot(self) -> ArcResult<Vec<u8>> {
-        let Self { inner, args } = self;
+        let Self {
+            inner,
+            mut args,
+            mask
+        } = self;
+        if !mask.is_empty() {
+            args.mask = Some(locator::masked_elements(&mask)?);
+        }
         upgrade(&inner)?.screenshot(args).await
     }
 
@@ -644,7 +662,17 @@ impl<'a> ScreenshotBuilder<'a> {
         self
     }
 
+    /// Elements matched by these locators are covered with a pink box in the screenshot.
+    pub fn mask(mut self, x: Vec<Locator>) -> Self {
+        self.mask = x;
+        self
+    }
+
     setter! {
+        /// When set to `Disabled`, stops CSS animations, CSS transitions and Web Animations. Finite animations are
+        /// fast-forwarded to completion, so they'll fire `transitionend` event. Infinite animations are canceled to initial
+        /// state, and then played over after the screenshot.
+        animations: Option<ScreenshotAnimations>,
         /// Hides default white background and allows capturing screenshots with transparency. Not applicable to `jpeg` images.
         /// Defaults to `false`.
         omit_background: Option<bool>,
@@ -660,6 +688,11 @@ impl<'a> ScreenshotBuilder<'a> {
         self.args.r#type = None;
         self
     }
+
+    pub fn clear_mask(mut self) -> Self {
+        self.mask = Vec::new();
+        self
+    }
 }
 
 pub struct WaitForSelectorBuilder<'a> {
@@ -779,25 +812,44 @@ impl SelectOptionBuilder {
 
 pub struct SetInputFilesBuilder {
     inner: Weak<Impl>,
-    args: SetInputFilesArgs
+    args: SetInputFilesArgs,
+    files: Vec<FilePayload>,
+    paths: Vec<PathBuf>
 }
 
 impl SetInputFilesBuilder {
-    pub(crate) fn new(inner: Weak<Impl>, file: File) -> Self {
-        let args = SetInputFilesArgs {
-            files: vec![file],
-            ..SetInputFilesArgs::default()
-        };
-        Self { inner, args }
+    pub(crate) fn new(inner: Weak<Impl>) -> Self {
+        Self {
+            inner,
+            args: SetInputFilesArgs::default(),
+            files: Vec::new(),
+            paths: Vec::new()
+        }
     }
 
     pub async fn set_input_files(self) -> Result<(), Arc<Error>> {
-        let Self { inner, args } = self;
+        let Self {
+            inner,
+            mut args,
+            files,
+            paths
+        } = self;
+        args.files = InputFiles::new(files, paths)?;
         upgrade(&inner)?.set_input_files(args).await
     }
 
-    pub fn add_file(mut self, x: File) -> Self {
-        self.args.files.push(x);
+    pub fn add_file(mut self, x: FilePayload) -> Self {
+        self.files.push(x);
+        self
+    }
+
+    pub fn add_path<P: Into<PathBuf>>(mut self, x: P) -> Self {
+        self.paths.push(x.into());
+        self
+    }
+
+    pub fn paths(mut self, x: Vec<PathBuf>) -> Self {
+        self.paths = x;
         self
     }
 
@@ -810,7 +862,8 @@ impl SetInputFilesBuilder {
     }
 
     pub fn clear_files(mut self) -> Self {
-        self.args.files = vec![];
+        self.files = vec![];
+        self.paths = vec![];
         self
     }
 }
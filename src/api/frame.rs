@@ -1,15 +1,21 @@
 pub use crate::imp::frame::{FrameNavigatedEvent, FrameState, Polling};
 use crate::{
-    api::{ElementHandle, JsHandle, Page, Response},
+    api::{
+        locator::{self, GetByRoleOptions},
+        ElementHandle, JsHandle, Locator, Page, Response
+    },
     imp::{
         core::*,
         frame::{
-            AddScriptTagArgs, CheckArgs, ClickArgs, Evt, FillArgs, Frame as Impl, GotoArgs,
-            HoverArgs, Opt, PressArgs, SelectOptionArgs, SetContentArgs, SetInputFilesArgs,
-            TapArgs, TypeArgs, WaitForFunctionArgs, WaitForSelectorArgs
+            AddScriptTagArgs, CheckArgs, ClickArgs, DragAndDropArgs, Evt, FillArgs, Frame as Impl,
+            GotoArgs, HoverArgs, Opt, PressArgs, SelectOptionArgs, SetContentArgs,
+            SetInputFilesArgs, TapArgs, TypeArgs, WaitForFunctionArgs, WaitForSelectorArgs
         },
         prelude::*,
-        utils::{DocumentLoadState, File, KeyboardModifier, MouseButton, Position}
+        utils::{
+            DocumentLoadState, FilePayload, InputFiles, KeyboardModifier, MouseButton, Position,
+            UrlMatcher
+        }
     }
 };
 
@@ -61,7 +67,7 @@ impl PartialEq for Frame {
 macro_rules! is_checked {
     ($f: ident) => {
         pub async fn $f(&self, selector: &str, timeout: Option<f64>) -> ArcResult<bool> {
-            upgrade(&self.inner)?.$f(selector, timeout).await
+            upgrade(&self.inner)?.$f(selector, timeout, None).await
         }
     };
 }
@@ -113,6 +119,8 @@ impl Frame {
     /// `about:blank` or navigation to the same URL with a different hash, which would succeed and return `null`.
     /// > NOTE: Headless mode doesn't support navigation to a PDF document. See the
     /// [upstream issue](https://bugs.chromium.org/p/chromium/issues/detail?id=761295).
+    ///
+    /// An empty `url` navigates to `about:blank`.
     pub fn goto_builder<'a>(&self, url: &'a str) -> GotoBuilder<'a, '_> {
         GotoBuilder::new(self.inner.clone(), url)
     }
@@ -189,12 +197,16 @@ impl Frame {
         selector: &str,
         timeout: Option<f64>
     ) -> ArcResult<Option<String>> {
-        upgrade(&self.inner)?.text_content(selector, timeout).await
+        upgrade(&self.inner)?
+            .text_content(selector, timeout, None)
+            .await
     }
 
     /// Returns `element.innerText`.
     pub async fn inner_text(&self, selector: &str, timeout: Option<f64>) -> ArcResult<String> {
-        upgrade(&self.inner)?.inner_text(selector, timeout).await
+        upgrade(&self.inner)?
+            .inner_text(selector, timeout, None)
+            .await
     }
 
     /// Returns `element.innerHTML`.
@@ -214,6 +226,45 @@ impl Frame {
             .await
     }
 
+    /// Creates a [`Locator`] for `selector` scoped to this frame. The element is resolved each time the locator is used.
+    pub fn locator(&self, selector: &str) -> Locator {
+        Locator::new(self.inner.clone(), selector.into())
+    }
+
+    /// Locates elements by their [ARIA role](https://www.w3.org/TR/wai-aria-1.2/#roles), ARIA attributes and
+    /// [accessible name](https://w3c.github.io/accname/#dfn-accessible-name).
+    ///
+    /// ```js
+    /// page.getByRole('button', { name: 'Sign in' })
+    /// ```
+    pub fn get_by_role(&self, role: &str, options: GetByRoleOptions) -> Locator {
+        self.locator(&locator::get_by_role_selector(role, &options))
+    }
+
+    /// Locates elements that contain `text`. Without `exact` the match is case-insensitive and searches for a substring.
+    pub fn get_by_text(&self, text: &str, exact: bool) -> Locator {
+        self.locator(&locator::get_by_text_selector(text, exact))
+    }
+
+    /// Locates input elements by the text of the associated `<label>`, `aria-labelledby` element or `aria-label`.
+    pub fn get_by_label(&self, text: &str, exact: bool) -> Locator {
+        self.locator(&locator::get_by_label_selector(text, exact))
+    }
+
+    /// Locates input elements by their placeholder text.
+    pub fn get_by_placeholder(&self, text: &str, exact: bool) -> Locator {
+        self.locator(&locator::get_by_placeholder_selector(text, exact))
+    }
+
+    /// Locates elements by their `data-testid` attribute.
+    pub fn get_by_test_id(&self, test_id: &str) -> Locator {
+        self.locator(&locator::get_by_test_id_selector(test_id))
+    }
+
+    /// Returns the first element matching `selector`, or `None` if there is none.
+    ///
+    /// CSS and text selectors pierce open shadow roots, so elements rendered inside web components are found without any
+    /// special syntax. XPath selectors and closed shadow roots are the exceptions: they don't reach into shadow trees.
     pub async fn query_selector(&self, selector: &str) -> ArcResult<Option<ElementHandle>> {
         Ok(upgrade(&self.inner)?
             .query_selector(selector)
@@ -320,6 +371,19 @@ impl Frame {
         HoverBuilder::new(self.inner.clone(), selector)
     }
 
+    /// Drags the element matching `source` and drops it onto the element matching `target`. Both elements go through
+    /// [actionability](https://playwright.dev/docs/actionability/) checks, then the mouse is moved over the source,
+    /// pressed, moved over the target and released, so that the HTML5 drag and drop events are fired.
+    ///
+    /// When all steps combined have not finished during the specified `timeout`, this method throws a `TimeoutError`.
+    pub fn drag_and_drop_builder<'a, 'b>(
+        &self,
+        source: &'a str,
+        target: &'b str
+    ) -> DragAndDropBuilder<'a, 'b> {
+        DragAndDropBuilder::new(self.inner.clone(), source, target)
+    }
+
     is_checked! {is_checked}
     is_checked! {is_disabled}
     is_checked! {is_editable}
@@ -368,6 +432,16 @@ impl Frame {
         UncheckBuilder::new(self.inner.clone(), selector)
     }
 
+    /// Checks or unchecks an element matching `selector`, following the steps of [`method: Frame.check`] or
+    /// [`method: Frame.uncheck`]. Returns immediately when the element is already in the desired state.
+    pub fn set_checked_builder<'a>(
+        &self,
+        selector: &'a str,
+        checked: bool
+    ) -> SetCheckedBuilder<'a> {
+        SetCheckedBuilder::new(self.inner.clone(), selector, checked)
+    }
+
     // = |timeout| async { sleep(timeout).await }
     pub async fn wait_for_timeout(&self, timeout: f64) {
         sleep(std::time::Duration::from_millis(timeout as u64)).await
@@ -478,6 +552,9 @@ impl Frame {
     /// const html = await frame.evaluate(([body, suffix]) => body.innerHTML + suffix, [bodyHandle, 'hello']);
     /// await bodyHandle.dispose();
     /// ```
+    ///
+    /// `arg` is sent to the driver as a serialized value, apart from `expression`. It is never spliced into the source,
+    /// so strings holding quotes, backticks or `${}` reach the function as they are.
     pub async fn evaluate<T, U>(&self, expression: &str, arg: T) -> ArcResult<U>
     where
         T: Serialize,
@@ -615,12 +692,10 @@ impl Frame {
     ///
     /// Sets the value of the file input to these file paths or files. If some of the `filePaths` are relative paths, then they
     /// are resolved relative to the the current working directory. For empty array, clears the selected files.
-    pub fn set_input_files_builder<'a>(
-        &self,
-        selector: &'a str,
-        file: File
-    ) -> SetInputFilesBuilder<'a> {
-        SetInputFilesBuilder::new(self.inner.clone(), selector, file)
+    ///
+    /// Files are given either all as paths or all as [`FilePayload`]s.
+    pub fn set_input_files_builder<'a>(&self, selector: &'a str) -> SetInputFilesBuilder<'a> {
+        SetInputFilesBuilder::new(self.inner.clone(), selector)
     }
 
     /// Returns when the `expression` returns a truthy value, returns that value.
@@ -645,7 +720,67 @@ impl Frame {
 
     subscribe_event! {}
 
-    // wait_for_url
+    /// Waits for the frame to navigate to the given URL. Resolves immediately if the frame is already at a matching URL.
+    ///
+    /// Navigations made through the History API, such as `history.pushState` in single page applications, are detected
+    /// as well as document loads.
+    ///
+    /// ```js
+    /// await frame.click('a.delayed-navigation'); // Clicking the link will indirectly cause a navigation
+    /// await frame.waitForURL('https://example.com/target.html');
+    /// ```
+    /// # Args
+    /// ## url
+    /// A glob such as `**/target.html`, a [`regex::Regex`] or a [`UrlMatcher::predicate`], see [`UrlMatcher`].
+    /// ## timeout
+    /// Maximum time in milliseconds, defaults to the page's default timeout. Pass `0` to disable timeout.
+    pub async fn wait_for_url<M: Into<UrlMatcher>>(
+        &self,
+        url: M,
+        timeout: Option<f64>
+    ) -> ArcResult<()> {
+        upgrade(&self.inner)?
+            .wait_for_url(&url.into(), timeout)
+            .await
+    }
+
+    /// Waits for the next navigation of the frame and returns the main resource response. In case of multiple redirects,
+    /// the navigation resolves with the response of the last redirect. Navigations through the History API resolve
+    /// with `None`.
+    ///
+    /// Only navigations committed after the returned future is first polled are considered, so it is meant to be
+    /// joined with the action that navigates:
+    ///
+    /// ```js
+    /// const [response] = await Promise.all([
+    ///  frame.waitForNavigation(),
+    ///  frame.click('a.delayed-navigation'), // Clicking the link will indirectly cause a navigation
+    /// ]);
+    /// ```
+    pub fn expect_navigation_builder(&self) -> ExpectNavigationBuilder {
+        ExpectNavigationBuilder::new(self.inner.clone())
+    }
+
+    /// Waits for the frame to reach the given load state. Resolves immediately if it has already been reached, which is
+    /// the case of same-document navigations made through the History API as they don't load a new document.
+    ///
+    /// ```js
+    /// await frame.click('button'); // Click triggers navigation.
+    /// await frame.waitForLoadState(); // Waits for 'load' state by default.
+    /// ```
+    /// # Args
+    /// ## timeout
+    /// Maximum time in milliseconds, defaults to the page's default navigation timeout. Exceeding it fails with
+    /// [`Error::Timeout`](crate::Error::Timeout).
+    pub async fn wait_for_load_state(
+        &self,
+        state: DocumentLoadState,
+        timeout: Option<f64>
+    ) -> ArcResult<()> {
+        upgrade(&self.inner)?
+            .wait_for_load_state(state, timeout)
+            .await
+    }
 }
 
 #[derive(Debug)]
@@ -665,24 +800,42 @@ impl From<Evt> for Event {
 
 pub struct GotoBuilder<'a, 'b> {
     inner: Weak<Impl>,
-    args: GotoArgs<'a, 'b>
+    args: GotoArgs<'a, 'b>,
+    retries: u32
 }
 
 impl<'a, 'b> GotoBuilder<'a, 'b> {
     pub(crate) fn new(inner: Weak<Impl>, url: &'a str) -> Self {
         let args = GotoArgs::new(url);
-        Self { inner, args }
+        Self {
+            inner,
+            args,
+            retries: 0
+        }
     }
 
     pub async fn goto(self) -> Result<Option<Response>, Arc<Error>> {
-        let Self { inner, args } = self;
-        let r = upgrade(&inner)?.goto(args).await?;
+        let Self {
+            inner,
+            args,
+            retries
+        } = self;
+        let r = upgrade(&inner)?.goto_retrying(args, retries).await?;
         Ok(r.map(Response::new))
     }
 
+    /// How many times to navigate again when the browser can't connect to the server or resolve its name, waiting a
+    /// little longer before each attempt. Other failures, and responses with an error status, are not retried. Each
+    /// attempt has its own `timeout`. Defaults to `0`.
+    pub fn retries(mut self, x: u32) -> Self {
+        self.retries = x;
+        self
+    }
+
     setter! {
         /// Referer header value. If provided it will take preference over the referer header value set by
-        /// [`method: Page.setExtraHTTPHeaders`].
+        /// [`method: Page.setExtraHTTPHeaders`]. It is sent as is, the referrer policy of the current document only
+        /// applies to navigations started by the page itself, such as link clicks.
         referer: Option<&'b str>,
         timeout: Option<f64>,
         wait_until: Option<DocumentLoadState>
@@ -759,7 +912,14 @@ impl<'a> WaitForSelectorBuilder<'a> {
     setter! {
         /// Defaults to `'visible'`.
         state: Option<FrameState>,
-        timeout: Option<f64>
+        /// Maximum time in milliseconds. Exceeding it fails with [`Error::Timeout`](crate::Error::Timeout).
+        timeout: Option<f64>,
+        /// When true, the call requires selector to resolve to a single element. If given selector resolves to more than one
+        /// element, the call throws an exception.
+        strict: Option<bool>,
+        /// When true, the returned element is also guaranteed to be actionable: visible, stable, enabled and not obscured by
+        /// other elements. Resolution is delayed until all of these checks pass. Defaults to `false`.
+        check_actionability: Option<bool>
     }
 }
 
@@ -830,6 +990,38 @@ impl<'a> HoverBuilder<'a> {
     }
 }
 
+pub struct DragAndDropBuilder<'a, 'b> {
+    inner: Weak<Impl>,
+    args: DragAndDropArgs<'a, 'b>
+}
+
+impl<'a, 'b> DragAndDropBuilder<'a, 'b> {
+    pub(crate) fn new(inner: Weak<Impl>, source: &'a str, target: &'b str) -> Self {
+        let args = DragAndDropArgs::new(source, target);
+        Self { inner, args }
+    }
+
+    pub async fn drag_and_drop(self) -> Result<(), Arc<Error>> {
+        let Self { inner, args } = self;
+        upgrade(&inner)?.drag_and_drop(args).await
+    }
+
+    setter! {
+        /// A point to grab the source element at, relative to the top-left corner of its padding box. If not specified, uses
+        /// some visible point of the element.
+        source_position: Option<Position>,
+        /// A point to drop at, relative to the top-left corner of the target element's padding box. If not specified, uses
+        /// some visible point of the element.
+        target_position: Option<Position>,
+        /// Whether to bypass the actionability checks. Defaults to `false`.
+        force: Option<bool>,
+        timeout: Option<f64>,
+        /// When set, this method only performs the [actionability](https://playwright.dev/docs/actionability/) checks and skips the action. Defaults to
+        /// `false`. Useful to wait until the element is ready for the action without performing it.
+        trial: Option<bool>
+    }
+}
+
 pub struct SetContentBuilder<'a> {
     inner: Weak<Impl>,
     args: SetContentArgs<'a>
@@ -955,6 +1147,47 @@ macro_rules! check_builder {
 check_builder!(CheckBuilder, check);
 check_builder!(UncheckBuilder, uncheck);
 
+pub struct SetCheckedBuilder<'a> {
+    inner: Weak<Impl>,
+    checked: bool,
+    args: CheckArgs<'a>
+}
+
+impl<'a> SetCheckedBuilder<'a> {
+    pub(crate) fn new(inner: Weak<Impl>, selector: &'a str, checked: bool) -> Self {
+        let args = CheckArgs::new(selector);
+        Self {
+            inner,
+            checked,
+            args
+        }
+    }
+
+    pub async fn set_checked(self) -> Result<(), Arc<Error>> {
+        let Self {
+            inner,
+            checked,
+            args
+        } = self;
+        upgrade(&inner)?.set_checked(checked, args).await
+    }
+
+    setter! {
+        /// A point to use relative to the top-left corner of element padding box. If not specified, uses some visible point of the element.
+        position: Option<Position>,
+        /// Whether to bypass the actionability checks. Defaults to `false`.
+        force: Option<bool>,
+        /// Actions that initiate navigations are waiting for these navigations to happen and for pages to start loading. You can
+        /// opt out of waiting via setting this flag. You would only need this option in the exceptional cases such as navigating to
+        /// inaccessible pages. Defaults to `false`.
+        no_wait_after: Option<bool>,
+        timeout: Option<f64>,
+        /// When set, this method only performs the [actionability](https://playwright.dev/docs/actionability/) checks and skips the action. Defaults to
+        /// `false`. Useful to wait until the element is ready for the action without performing it.
+        trial: Option<bool>
+    }
+}
+
 pub struct AddScriptTagBuilder<'a, 'b, 'c> {
     inner: Weak<Impl>,
     args: AddScriptTagArgs<'a, 'b, 'c>
@@ -1086,23 +1319,45 @@ impl<'a> SelectOptionBuilder<'a> {
 
 pub struct SetInputFilesBuilder<'a> {
     inner: Weak<Impl>,
-    args: SetInputFilesArgs<'a>
+    args: SetInputFilesArgs<'a>,
+    files: Vec<FilePayload>,
+    paths: Vec<PathBuf>
 }
 
 impl<'a> SetInputFilesBuilder<'a> {
-    pub(crate) fn new(inner: Weak<Impl>, selector: &'a str, file: File) -> Self {
-        let mut args = SetInputFilesArgs::new(selector);
-        args.files = vec![file];
-        Self { inner, args }
+    pub(crate) fn new(inner: Weak<Impl>, selector: &'a str) -> Self {
+        let args = SetInputFilesArgs::new(selector);
+        Self {
+            inner,
+            args,
+            files: Vec::new(),
+            paths: Vec::new()
+        }
     }
 
     pub async fn set_input_files(self) -> Result<(), Arc<Error>> {
-        let Self { inner, args } = self;
+        let Self {
+            inner,
+            mut args,
+            files,
+            paths
+        } = self;
+        args.files = InputFiles::new(files, paths)?;
         upgrade(&inner)?.set_input_files(args).await
     }
 
-    pub fn add_file(mut self, x: File) -> Self {
-        self.args.files.push(x);
+    pub fn add_file(mut self, x: FilePayload) -> Self {
+        self.files.push(x);
+        self
+    }
+
+    pub fn add_path<P: Into<PathBuf>>(mut self, x: P) -> Self {
+        self.paths.push(x.into());
+        self
+    }
+
+    pub fn paths(mut self, x: Vec<PathBuf>) -> Self {
+        self.paths = x;
         self
     }
 
@@ -1115,7 +1370,8 @@ impl<'a> SetInputFilesBuilder<'a> {
     }
 
     pub fn clear_files(mut self) -> Self {
-        self.args.files = vec![];
+        self.files = vec![];
+        self.paths = vec![];
         self
     }
 }
@@ -1163,9 +1419,11 @@ impl<'a> WaitForFunctionBuilder<'a> {
     }
 
     setter! {
-        /// If `polling` is `'raf'`, then `expression` is constantly executed in `requestAnimationFrame` callback. If `polling` is a
-        /// number, then it is treated as an interval in milliseconds at which the function would be executed. Defaults to `raf`.
+        /// With [`Polling::RequestAnimationFrame`], `expression` is constantly executed in `requestAnimationFrame` callback.
+        /// With [`Polling::Millis`], it is executed at that interval in milliseconds. Defaults to
+        /// [`Polling::RequestAnimationFrame`].
         polling: Option<Polling>,
+        /// Maximum time in milliseconds. Exceeding it fails with [`Error::Timeout`](crate::Error::Timeout).
         timeout: Option<f64>
     }
 
@@ -1175,3 +1433,71 @@ impl<'a> WaitForFunctionBuilder<'a> {
         self
     }
 }
+
+/// [`Frame::expect_navigation_builder`]
+pub struct ExpectNavigationBuilder {
+    inner: Weak<Impl>,
+    url: Option<UrlMatcher>,
+    wait_until: Option<DocumentLoadState>,
+    timeout: Option<f64>
+}
+
+impl ExpectNavigationBuilder {
+    pub(crate) fn new(inner: Weak<Impl>) -> Self {
+        Self {
+            inner,
+            url: None,
+            wait_until: None,
+            timeout: None
+        }
+    }
+
+    /// Fails with [`Error::Timeout`](crate::Error::Timeout) if no matching navigation reaches `wait_until` in time,
+    /// and with [`Error::Navigation`](crate::Error::Navigation) if the navigation fails.
+    pub async fn expect_navigation(self) -> ArcResult<Option<Response>> {
+        let Self {
+            inner,
+            url,
+            wait_until,
+            timeout
+        } = self;
+        let wait_until = wait_until.unwrap_or(DocumentLoadState::Load);
+        let r = upgrade(&inner)?
+            .expect_navigation(url, wait_until, timeout)
+            .await?;
+        Ok(r.map(Response::new))
+    }
+
+    /// Only navigations to a URL matching this glob or [`regex::Regex`] are considered.
+    pub fn url<M: Into<UrlMatcher>>(mut self, x: M) -> Self {
+        self.url = Some(x.into());
+        self
+    }
+
+    /// When to consider the navigation done, defaults to [`DocumentLoadState::Load`].
+    pub fn wait_until(mut self, x: DocumentLoadState) -> Self {
+        self.wait_until = Some(x);
+        self
+    }
+
+    /// Maximum time in milliseconds, defaults to the page's default navigation timeout.
+    pub fn timeout(mut self, x: f64) -> Self {
+        self.timeout = Some(x);
+        self
+    }
+
+    pub fn clear_url(mut self) -> Self {
+        self.url = None;
+        self
+    }
+
+    pub fn clear_wait_until(mut self) -> Self {
+        self.wait_until = None;
+        self
+    }
+
+    pub fn clear_timeout(mut self) -> Self {
+        self.timeout = None;
+        self
+    }
+}
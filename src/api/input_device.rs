@@ -116,9 +116,14 @@ impl Keyboard {
     ///
     ///
     /// > NOTE: Modifier keys DO NOT effect `keyboard.insertText`. Holding down `Shift` will not type the text in upper case.
-    pub async fn input_text(&self, text: &str) -> Result<(), Arc<Error>> {
+    pub async fn insert_text(&self, text: &str) -> Result<(), Arc<Error>> {
         let inner = upgrade(&self.inner)?;
-        inner.key_input_text(text).await
+        inner.key_insert_text(text).await
+    }
+
+    #[deprecated(note = "renamed to `insert_text`")]
+    pub async fn input_text(&self, text: &str) -> Result<(), Arc<Error>> {
+        self.insert_text(text).await
     }
 
     /// Sends a `keydown`, `keypress`/`input`, and `keyup` event for each character in the text.
@@ -180,6 +185,13 @@ impl Mouse {
     pub fn dblclick_builder(&self, x: f64, y: f64) -> DblClickBuilder {
         DblClickBuilder::new(self.inner.clone(), x, y)
     }
+
+    /// Dispatches a `wheel` event at the current mouse position. Scrolling caused by the event happens asynchronously,
+    /// this method doesn't wait for it to finish.
+    pub async fn wheel(&self, delta_x: f64, delta_y: f64) -> Result<(), Arc<Error>> {
+        let inner = upgrade(&self.inner)?;
+        inner.mouse_wheel(delta_x, delta_y).await
+    }
 }
 
 impl TouchScreen {
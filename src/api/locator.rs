@@ -0,0 +1,363 @@
+use crate::imp::{
+    core::*,
+    element_handle::{MaskedElement, ScreenshotArgs},
+    frame::{ClickArgs, FillArgs, Frame as FrameImpl, FrameState, TypeArgs, WaitForSelectorArgs},
+    prelude::*,
+    utils::{remaining_timeout, ScreenshotAnimations, ScreenshotType}
+};
+use std::time::Instant;
+
+/// Options of [`Frame::get_by_role`](crate::api::Frame::get_by_role). Unset fields don't constrain the match.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GetByRoleOptions {
+    /// Accessible name. Matched case-insensitively as a substring unless `exact` is set. Like the accessible names it
+    /// is compared to, it is trimmed and its runs of whitespace are collapsed to single spaces.
+    pub name: Option<String>,
+    /// Match `name` case-sensitively and as a whole string.
+    pub exact: bool,
+    /// `aria-checked` or native checkbox state.
+    pub checked: Option<bool>,
+    /// `aria-disabled` or native `disabled` state.
+    pub disabled: Option<bool>,
+    /// `aria-pressed` state.
+    pub pressed: Option<bool>,
+    /// Heading level, `1` to `6` for `heading` roles.
+    pub level: Option<i32>
+}
+
+// Same escaping as Playwright's locator utilities, so the selectors match what the other bindings generate
+fn escape_for_text_selector(text: &str, exact: bool) -> String {
+    let quoted = serde_json::to_string(text).unwrap();
+    format!("{}{}", quoted, if exact { "s" } else { "i" })
+}
+
+fn escape_for_attribute_selector(value: &str, exact: bool) -> String {
+    let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("\"{}\"{}", escaped, if exact { "s" } else { "i" })
+}
+
+pub(crate) fn get_by_role_selector(role: &str, options: &GetByRoleOptions) -> String {
+    let mut s = format!("internal:role={}", role);
+    if let Some(x) = options.checked {
+        s.push_str(&format!("[checked={}]", x));
+    }
+    if let Some(x) = options.disabled {
+        s.push_str(&format!("[disabled={}]", x));
+    }
+    if let Some(x) = options.level {
+        s.push_str(&format!("[level={}]", x));
+    }
+    if let Some(x) = &options.name {
+        // Accessible names are computed with normalized whitespace
+        let name = x.split_whitespace().collect::<Vec<_>>().join(" ");
+        s.push_str(&format!(
+            "[name={}]",
+            escape_for_attribute_selector(&name, options.exact)
+        ));
+    }
+    if let Some(x) = options.pressed {
+        s.push_str(&format!("[pressed={}]", x));
+    }
+    s
+}
+
+pub(crate) fn get_by_text_selector(text: &str, exact: bool) -> String {
+    format!("internal:text={}", escape_for_text_selector(text, exact))
+}
+
+pub(crate) fn get_by_label_selector(text: &str, exact: bool) -> String {
+    format!("internal:label={}", escape_for_text_selector(text, exact))
+}
+
+pub(crate) fn get_by_placeholder_selector(text: &str, exact: bool) -> String {
+    format!(
+        "internal:attr=[placeholder={}]",
+        escape_for_attribute_selector(text, exact)
+    )
+}
+
+pub(crate) fn get_by_test_id_selector(test_id: &str) -> String {
+    format!(
+        "internal:testid=[data-testid={}]",
+        escape_for_attribute_selector(test_id, true)
+    )
+}
+
+pub(crate) fn masked_elements(mask: &[Locator]) -> Result<Vec<MaskedElement>, Error> {
+    mask.iter()
+        .map(|l| Ok(MaskedElement::new(&*upgrade(&l.frame)?, l.selector.clone())))
+        .collect()
+}
+
+/// Locators represent a way to find element(s) on the page at any moment. A locator only holds a selector and the frame
+/// it is scoped to; the element is resolved anew by every call, so it never goes stale. Locators are created with
+/// [`Page::locator`](crate::api::Page::locator) and [`Frame::locator`](crate::api::Frame::locator).
+///
+/// Actions auto-wait for the element to be actionable and are strict: they fail if the selector matches more than one
+/// element. Use [`Locator::nth`] to pick one of several matches.
+#[derive(Clone)]
+pub struct Locator {
+    frame: Weak<FrameImpl>,
+    selector: String
+}
+
+impl Locator {
+    pub(crate) fn new(frame: Weak<FrameImpl>, selector: String) -> Self { Self { frame, selector } }
+
+    /// The selector this locator resolves.
+    pub fn selector(&self) -> &str { &self.selector }
+
+    /// Returns a locator to the n-th matching element. It's zero based, `nth(0)` selects the first element.
+    pub fn nth(&self, index: i32) -> Locator {
+        Locator::new(
+            self.frame.clone(),
+            format!("{} >> nth={}", self.selector, index)
+        )
+    }
+
+    /// Returns the number of elements matching the locator.
+    pub async fn count(&self) -> ArcResult<usize> {
+        upgrade(&self.frame)?.query_count(&self.selector).await
+    }
+
+    /// Clicks the element once it is visible, stable, enabled and receives events.
+    pub async fn click(&self, timeout: Option<f64>) -> ArcResult<()> {
+        let mut args = ClickArgs::new(&self.selector);
+        args.timeout = timeout.or(args.timeout);
+        args.strict = Some(true);
+        upgrade(&self.frame)?.click(args).await
+    }
+
+    /// Waits for the element to be an editable `<input>`, `<textarea>` or `[contenteditable]` element, focuses it and fills
+    /// it with `value`.
+    pub async fn fill(&self, value: &str, timeout: Option<f64>) -> ArcResult<()> {
+        let mut args = FillArgs::new(&self.selector, value);
+        args.timeout = timeout.or(args.timeout);
+        args.strict = Some(true);
+        upgrade(&self.frame)?.fill(args).await
+    }
+
+    /// Focuses the element and presses a key for each character of `text`, firing `keydown`, `keypress`/`input` and
+    /// `keyup` like a user typing would. Works on `[contenteditable]` elements as well as inputs. `delay` is the time
+    /// to wait between key presses in milliseconds. Prefer [`Locator::fill`] unless the page relies on key events.
+    pub async fn press_sequentially(&self, text: &str, delay: Option<f64>) -> ArcResult<()> {
+        let mut args = TypeArgs::new(&self.selector, text);
+        args.delay = delay;
+        args.strict = Some(true);
+        upgrade(&self.frame)?.r#type(args).await
+    }
+
+    /// Returns `element.textContent`: the raw text of the element and its descendants, whitespace and hidden text
+    /// included. Use [`Locator::inner_text`] for the text as rendered.
+    pub async fn text_content(&self, timeout: Option<f64>) -> ArcResult<Option<String>> {
+        upgrade(&self.frame)?
+            .text_content(&self.selector, timeout, Some(true))
+            .await
+    }
+
+    /// Returns `element.innerText`: the rendered text, with whitespace collapsed according to CSS and hidden elements
+    /// left out.
+    pub async fn inner_text(&self, timeout: Option<f64>) -> ArcResult<String> {
+        upgrade(&self.frame)?
+            .inner_text(&self.selector, timeout, Some(true))
+            .await
+    }
+
+    /// Returns the `element.textContent` of every matching element, in document order. Does not wait: a locator that
+    /// matches nothing returns an empty list.
+    pub async fn all_text_contents(&self) -> ArcResult<Vec<String>> {
+        upgrade(&self.frame)?
+            .evaluate_on_selector_all::<(), _>(
+                &self.selector,
+                "ee => ee.map(e => e.textContent || '')",
+                None
+            )
+            .await
+    }
+
+    /// Returns the `element.innerText` of every matching element, in document order. Does not wait: a locator that
+    /// matches nothing returns an empty list.
+    pub async fn all_inner_texts(&self) -> ArcResult<Vec<String>> {
+        upgrade(&self.frame)?
+            .evaluate_on_selector_all::<(), _>(
+                &self.selector,
+                "ee => ee.map(e => e.innerText)",
+                None
+            )
+            .await
+    }
+
+    /// Returns whether the element is visible. Does not wait: a locator that matches nothing is considered not visible.
+    pub async fn is_visible(&self) -> ArcResult<bool> {
+        upgrade(&self.frame)?
+            .is_visible(&self.selector, None, Some(true))
+            .await
+    }
+
+    /// Waits for the element to be visible and captures it. Fails if the locator matches more than one element.
+    pub fn screenshot_builder<'a>(&self) -> ScreenshotBuilder<'a> {
+        ScreenshotBuilder::new(self.frame.clone(), self.selector.clone())
+    }
+
+    /// Captures the aria snapshot of the matching element, a YAML representation of its accessibility tree that can be
+    /// compared against an expected template.
+    ///
+    /// ```js
+    /// await page.getByRole('navigation').ariaSnapshot();
+    /// ```
+    ///
+    /// ```yaml
+    /// - navigation:
+    ///   - link "Home":
+    ///     - /url: /
+    ///   - link "About":
+    ///     - /url: /about
+    /// ```
+    ///
+    /// Each entry is the ARIA role of a node followed by its accessible name in quotes, with children nested below.
+    pub async fn aria_snapshot(&self, timeout: Option<f64>) -> ArcResult<String> {
+        upgrade(&self.frame)?
+            .aria_snapshot(&self.selector, timeout)
+            .await
+    }
+}
+
+/// [`Locator::screenshot_builder`]
+pub struct ScreenshotBuilder<'a> {
+    frame: Weak<FrameImpl>,
+    selector: String,
+    args: ScreenshotArgs<'a>,
+    mask: Vec<Locator>
+}
+
+impl<'a> ScreenshotBuilder<'a> {
+    fn new(frame: Weak<FrameImpl>, selector: String) -> Self {
+        Self {
+            frame,
+            selector,
+            args: ScreenshotArgs::default(),
+            mask: Vec::new()
+        }
+    }
+
+    pub async fn screenshot(self) -> ArcResult<Vec<u8>> {
+        let Self {
+            frame,
+            selector,
+            mut args,
+            mask
+        } = self;
+        if !mask.is_empty() {
+            args.mask = Some(masked_elements(&mask)?);
+        }
+        let mut wait = WaitForSelectorArgs::new(&selector);
+        wait.strict = Some(true);
+        wait.state = Some(FrameState::Visible);
+        wait.timeout = args.timeout.or(wait.timeout);
+        let timeout = wait.timeout;
+        let started = Instant::now();
+        let element = upgrade(&frame)?
+            .wait_for_selector(wait)
+            .await?
+            .ok_or(Error::ObjectNotFound)?;
+        let element = upgrade(&element)?;
+        // The screenshot only gets what the wait left of the timeout
+        args.timeout = remaining_timeout(timeout, started);
+        let bytes = element.screenshot(args).await;
+        element.dispose().await?;
+        bytes
+    }
+
+    /// Specify screenshot type, defaults to `png`.
+    pub fn r#type(mut self, x: ScreenshotType) -> Self {
+        self.args.r#type = Some(x);
+        self
+    }
+
+    /// Elements matched by these locators are covered with a pink box in the screenshot.
+    pub fn mask(mut self, x: Vec<Locator>) -> Self {
+        self.mask = x;
+        self
+    }
+
+    setter! {
+        /// When set to `Disabled`, stops CSS animations, CSS transitions and Web Animations. Finite animations are
+        /// fast-forwarded to completion, so they'll fire `transitionend` event. Infinite animations are canceled to initial
+        /// state, and then played over after the screenshot.
+        animations: Option<ScreenshotAnimations>,
+        /// Hides default white background and allows capturing screenshots with transparency. Not applicable to `jpeg` images.
+        /// Defaults to `false`.
+        omit_background: Option<bool>,
+        /// The file path to save the image to. The screenshot type will be inferred from file extension. If `path` is a relative
+        /// path, then it is resolved relative to the current working directory. If no path is provided, the image won't be saved to
+        /// the disk.
+        path: Option<&'a Path>,
+        quality: Option<i64>,
+        /// Maximum time in milliseconds to wait for the element and capture it.
+        timeout: Option<f64>
+    }
+
+    pub fn clear_type(mut self) -> Self {
+        self.args.r#type = None;
+        self
+    }
+
+    pub fn clear_mask(mut self) -> Self {
+        self.mask = Vec::new();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn role_selector() {
+        assert_eq!(
+            get_by_role_selector("button", &GetByRoleOptions::default()),
+            "internal:role=button"
+        );
+        let options = GetByRoleOptions {
+            name: Some("Say \"OK\"".into()),
+            exact: true,
+            checked: Some(false),
+            level: Some(2),
+            pressed: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(
+            get_by_role_selector("heading", &options),
+            r#"internal:role=heading[checked=false][level=2][name="Say \"OK\""s][pressed=true]"#
+        );
+        let options = GetByRoleOptions {
+            name: Some("\n  Submit \t order ".into()),
+            exact: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            get_by_role_selector("button", &options),
+            r#"internal:role=button[name="Submit order"s]"#
+        );
+    }
+
+    #[test]
+    fn text_selectors() {
+        assert_eq!(
+            get_by_text_selector("Hello", false),
+            r#"internal:text="Hello"i"#
+        );
+        assert_eq!(
+            get_by_label_selector("Name", true),
+            r#"internal:label="Name"s"#
+        );
+        assert_eq!(
+            get_by_placeholder_selector("a\\b", false),
+            r#"internal:attr=[placeholder="a\\b"i]"#
+        );
+        assert_eq!(
+            get_by_test_id_selector("submit"),
+            r#"internal:testid=[data-testid="submit"s]"#
+        );
+    }
+}
@@ -1,28 +1,32 @@
 pub use crate::{
     api::{
         frame::{
-            AddScriptTagBuilder, CheckBuilder, ClickBuilder, DblClickBuilder, FillBuilder,
-            GotoBuilder, HoverBuilder, PressBuilder, SelectOptionBuilder, SetContentBuilder,
-            SetInputFilesBuilder, TapBuilder, TypeBuilder, UncheckBuilder, WaitForFunctionBuilder,
+            AddScriptTagBuilder, CheckBuilder, ClickBuilder, DblClickBuilder, DragAndDropBuilder,
+            ExpectNavigationBuilder, FillBuilder, GotoBuilder, HoverBuilder, PressBuilder,
+            SelectOptionBuilder, SetCheckedBuilder, SetContentBuilder, SetInputFilesBuilder,
+            TapBuilder, TypeBuilder, UncheckBuilder, WaitForFunctionBuilder,
             WaitForSelectorBuilder
         },
+        locator::GetByRoleOptions,
         Download, JsHandle, Request
     },
     imp::page::{EventType, Media}
 };
 use crate::{
     api::{
-        input_device::*, Accessibility, BrowserContext, ConsoleMessage, ElementHandle, FileChooser,
-        Frame, Keyboard, Response, TouchScreen, Video, WebSocket, Worker
+        binding_call, browser::RecordVideo, dialog, input_device::*, route, Accessibility,
+        BindingCall, BrowserContext, ConsoleMessage, Dialog, ElementHandle, FileChooser, Frame,
+        Keyboard, Locator, Response, Route, TouchScreen, Video, WebSocket, Worker
     },
     imp::{
+        binding_call::BindingCallback,
         core::*,
         frame::Frame as FrameImpl,
         page::{EmulateMediaArgs, Evt, Page as Impl, PdfArgs, ReloadArgs, ScreenshotArgs},
         prelude::*,
         utils::{
-            ColorScheme, DocumentLoadState, File, FloatRect, Length, PdfMargins, ScreenshotType,
-            Viewport
+            ColorScheme, DocumentLoadState, FloatRect, Length, PdfMargins, ScreenshotType,
+            UrlMatcher, Viewport, VisionDeficiency, WindowBounds, WindowState
         }
     },
     Error
@@ -70,8 +74,27 @@ use crate::{
 #[derive(Debug, Clone)]
 pub struct Page {
     inner: Weak<Impl>,
+    /// Sends keyboard input to whichever element of the page is focused.
+    ///
+    /// ```
+    /// # async fn f(page: playwright::api::Page) -> Result<(), std::sync::Arc<playwright::Error>> {
+    /// page.focus("input", None).await?;
+    /// page.keyboard.r#type("Hello", None).await?;
+    /// page.keyboard.press("Enter", None).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
     pub keyboard: Keyboard,
     pub touch_screen: TouchScreen,
+    /// Moves and clicks the mouse in CSS pixels relative to the top-left corner of the viewport.
+    ///
+    /// ```
+    /// # async fn f(page: playwright::api::Page) -> Result<(), std::sync::Arc<playwright::Error>> {
+    /// page.mouse.r#move(0., 0., None).await?;
+    /// page.mouse.click_builder(100., 100.).click().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
     pub mouse: Mouse,
     pub accessibility: Accessibility
 }
@@ -101,11 +124,22 @@ impl Page {
         BrowserContext::new(weak_and_then(&self.inner, |rc| rc.browser_context()))
     }
 
+    /// A handle to the page's keyboard, the same as the `keyboard` field.
+    pub fn keyboard(&self) -> Keyboard { self.keyboard.clone() }
+
+    /// A handle to the page's mouse, the same as the `mouse` field.
+    pub fn mouse(&self) -> Mouse { self.mouse.clone() }
+
+    /// A handle to the page's touchscreen, the same as the `touch_screen` field. Tapping fails unless the browser context
+    /// was created with `has_touch` enabled.
+    pub fn touchscreen(&self) -> TouchScreen { self.touch_screen.clone() }
+
     fn main_frame_weak(&self) -> Weak<FrameImpl> {
         weak_and_then(&self.inner, |rc| rc.main_frame())
     }
 
-    /// The page's main frame. Page is guaranteed to have a main frame which persists during navigations.
+    /// The page's main frame. Page is guaranteed to have a main frame which persists during navigations, so a handle
+    /// obtained before navigating keeps working afterwards.
     pub fn main_frame(&self) -> Frame { Frame::new(self.main_frame_weak()) }
 
     /// An array of all frames attached to the page.
@@ -117,6 +151,26 @@ impl Page {
             .collect())
     }
 
+    /// The frames attached to the page for which `predicate` returns `true`, such as those whose url contains a given
+    /// string.
+    ///
+    /// ```
+    /// # async fn example(page: playwright::api::Page) -> Result<(), playwright::Error> {
+    /// let ads = page.frames_matching(|f| f.url().map(|u| u.contains("/ads/")).unwrap_or(false))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn frames_matching<F>(&self, predicate: F) -> Result<Vec<Frame>, Error>
+    where
+        F: Fn(&Frame) -> bool
+    {
+        Ok(self
+            .frames()?
+            .into_iter()
+            .filter(|f| predicate(f))
+            .collect())
+    }
+
     /// This method returns all of the dedicated [WebWorkers](https://developer.mozilla.org/en-US/docs/Web/API/Web_Workers_API)
     /// associated with the page.
     ///
@@ -169,10 +223,30 @@ impl Page {
     }
 
     /// Video object associated with this page.
+    ///
+    /// Recording is enabled for every page of a context with `record_video` on the context builder and lasts for the
+    /// lifetime of the page. Use [`Page::start_video`] to record only part of a session.
     pub fn video(&self) -> Result<Option<Video>, Error> {
         Ok(upgrade(&self.inner)?.video().map(Video::new))
     }
 
+    /// Starts recording a video segment into `record_video.dir`, independently of the context's `record_video`.
+    ///
+    /// The driver can only record whole pages, so the segment is recorded by a new page opened in a recording context of
+    /// its own, with the cookies, local storage, viewport and URL of this page. Perform the actions to record on the
+    /// returned page, then call [`Page::stop_video`] on it. This page is left untouched. Not available for pages of a
+    /// persistent context.
+    pub async fn start_video(&self, record_video: RecordVideo<'_>) -> ArcResult<Page> {
+        let p = upgrade(&self.inner)?.start_video(record_video).await?;
+        Ok(Page::new(p))
+    }
+
+    /// Stops the segment recorded by a page returned by [`Page::start_video`], closing that page, and returns the path of
+    /// the finished video. Fails with [`Error::NotRecording`] for any other page.
+    pub async fn stop_video(&self) -> ArcResult<PathBuf> {
+        upgrade(&self.inner)?.stop_video().await
+    }
+
     ///// Returns frame matching the specified criteria. Either `name` or `url` must be specified.
     // fn frame(&self) -> Option<Frame> { unimplemented!() }
 
@@ -207,9 +281,18 @@ impl Page {
         upgrade(&self.inner)?.add_init_script(source).await
     }
 
+    /// Same as [`Page::add_init_script`], with the script read from the file at `path` when this is called.
+    /// Fails without touching the page if the file can't be read.
+    pub async fn add_init_script_path(&self, path: &Path) -> ArcResult<()> {
+        let source = std::fs::read_to_string(path).map_err(Error::from)?;
+        let source = format!("{}\n//# sourceURL={}", source, path.display());
+        self.add_init_script(&source).await
+    }
+
     /// Returns the PDF buffer.
     ///
-    /// > NOTE: Generating a pdf is currently only supported in Chromium headless.
+    /// > NOTE: Generating a pdf is currently only supported in Chromium headless. Other browsers fail with
+    /// [`Error::PdfNotSupported`](crate::Error::PdfNotSupported) without contacting the driver.
     ///
     /// `page.pdf()` generates a pdf of the page with `print` css media. To generate a pdf with `screen` media, call
     /// [`method: Page.emulateMedia`] before calling `page.pdf()`:
@@ -309,6 +392,51 @@ impl Page {
         EmulateMediaBuilder::new(self.inner.clone())
     }
 
+    /// Renders the page as seen with a vision deficiency, through the Chrome DevTools Protocol. It stays in effect until
+    /// it is set again, [`VisionDeficiency::None`] restoring the unfiltered rendering.
+    ///
+    /// > NOTE: This is only supported in Chromium. Other browsers fail with
+    /// [`Error::CdpNotSupported`](crate::Error::CdpNotSupported) without contacting the driver.
+    pub async fn set_vision_deficiency(&self, x: VisionDeficiency) -> ArcResult<()> {
+        upgrade(&self.inner)?.set_vision_deficiency(x).await
+    }
+
+    /// Position, size and state of the browser window showing the page, through the Chrome DevTools Protocol.
+    ///
+    /// > NOTE: This is only supported in Chromium. Other browsers fail with
+    /// [`Error::CdpNotSupported`](crate::Error::CdpNotSupported).
+    pub async fn window_bounds(&self) -> ArcResult<WindowBounds> {
+        upgrade(&self.inner)?.window_bounds().await
+    }
+
+    /// Moves, resizes, maximizes or minimizes the browser window showing the page. A window that isn't in the
+    /// [`WindowState::Normal`] state must be restored to it before being moved or resized. The viewport of the page is
+    /// unchanged when it was set with [`Page::set_viewport_size`] or the context's viewport option.
+    ///
+    /// > NOTE: This is only supported in Chromium. Other browsers fail with
+    /// [`Error::CdpNotSupported`](crate::Error::CdpNotSupported).
+    pub async fn set_window_bounds(&self, bounds: WindowBounds) -> ArcResult<()> {
+        upgrade(&self.inner)?.set_window_bounds(bounds).await
+    }
+
+    /// Switches the browser window showing the page to fullscreen, or back to its normal state. This is the window
+    /// state, the page's `document.fullscreenElement` is unaffected.
+    ///
+    /// > NOTE: This is only supported in Chromium. Other browsers fail with
+    /// [`Error::CdpNotSupported`](crate::Error::CdpNotSupported).
+    pub async fn set_fullscreen(&self, fullscreen: bool) -> ArcResult<()> {
+        let window_state = if fullscreen {
+            WindowState::Fullscreen
+        } else {
+            WindowState::Normal
+        };
+        let bounds = WindowBounds {
+            window_state: Some(window_state),
+            ..WindowBounds::default()
+        };
+        self.set_window_bounds(bounds).await
+    }
+
     /// Returns the opener for popup pages and `null` for others. If the opener has been closed already the returns `null`.
     pub async fn opener(&self) -> ArcResult<Option<Page>> {
         Ok(upgrade(&self.inner)?.opener().await?.map(Page::new))
@@ -317,6 +445,8 @@ impl Page {
     /// The extra HTTP headers will be sent with every request the page initiates.
     ///
     /// > NOTE: [`method: Page.setExtraHTTPHeaders`] does not guarantee the order of headers in the outgoing requests.
+    ///
+    /// Header names are lowercased before they are sent.
     pub async fn set_extra_http_headers<T>(&self, headers: T) -> ArcResult<()>
     where
         T: IntoIterator<Item = (String, String)>
@@ -332,12 +462,326 @@ impl Page {
 
     subscribe_event! {}
 
+    /// Waits for a request for which `predicate` returns `true` to finish, that is until its response body has been
+    /// downloaded. This comes after the [`Event::Response`] of the request, which is emitted as soon as the headers are
+    /// received.
+    ///
+    /// Like [`Page::expect_event`], only requests finishing after the returned future is first polled are considered,
+    /// so it is meant to be joined with the action that sends the request.
+    pub fn expect_request_finished_builder<F>(
+        &self,
+        predicate: F
+    ) -> ExpectRequestFinishedBuilder<F>
+    where
+        F: Fn(&Request) -> bool + Send + Sync + 'static
+    {
+        ExpectRequestFinishedBuilder::new(self.inner.clone(), predicate)
+    }
+
+    /// Waits for a response whose url matches `url`, a glob such as `**/api/*` or a [`regex::Regex`].
+    ///
+    /// ```js
+    /// const [response] = await Promise.all([
+    ///  page.waitForResponse('https://example.com/resource'),
+    ///  page.click('input.foo'),
+    /// ]);
+    /// ```
+    ///
+    /// Like [`Page::expect_event`], only responses received after the returned future is first polled are considered,
+    /// so it is meant to be joined with the action that triggers the request.
+    pub fn wait_for_response_builder<M: Into<UrlMatcher>>(&self, url: M) -> WaitForResponseBuilder {
+        WaitForResponseBuilder::new(self.inner.clone(), url.into())
+    }
+
+    /// The method adds a function called `name` on the `window` object of every frame in this page. When called, the
+    /// function executes `callback` with its arguments deserialized as `A`, and returns a [Promise] which resolves to the
+    /// return value of `callback`. If the `callback` returns an `Err`, the promise is rejected with its message. A
+    /// function taking several arguments deserializes them into a tuple, see [`BindingCall::args`].
+    ///
+    /// Calls are served concurrently, so every call made by the page resolves on its own.
+    ///
+    /// Functions installed via [`method: Page.exposeFunction`] survive navigations.
+    ///
+    /// ```js
+    /// await page.exposeFunction('sha256', text => crypto.createHash('sha256').update(text).digest('hex'));
+    /// await page.setContent(`
+    ///  <script>
+    ///    async function onClick() {
+    ///      document.querySelector('div').textContent = await window.sha256('PLAYWRIGHT');
+    ///    }
+    ///  </script>
+    ///  <button onclick="onClick()">Click me</button>
+    ///  <div></div>
+    /// `);
+    /// ```
+    pub async fn expose_function<F, Fut, A, T>(&self, name: &str, callback: F) -> ArcResult<()>
+    where
+        F: Fn(A) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<T, String>> + Send + 'static,
+        A: DeserializeOwned,
+        T: Serialize
+    {
+        upgrade(&self.inner)?
+            .expose_binding(name, false, binding_call::function_callback(callback))
+            .await
+    }
+
+    /// The method adds a function called `name` on the `window` object of every frame in this page. When called, the
+    /// function executes `callback` and returns a [Promise] which resolves to the return value of `callback`. If the
+    /// `callback` returns an `Err`, the promise is rejected with its message.
+    ///
+    /// The first argument of the `callback` function contains information about the caller.
+    ///
+    /// Functions installed via [`method: Page.exposeBinding`] survive navigations.
+    ///
+    /// An example of passing an element handle:
+    ///
+    /// ```js
+    /// await page.exposeBinding('clicked', async (source, element) => {
+    ///  console.log(await element.textContent());
+    /// }, { handle: true });
+    /// await page.setContent(`
+    ///  <script>
+    ///    document.addEventListener('click', event => window.clicked(event.target));
+    ///  </script>
+    ///  <div>Click me</div>
+    ///  <div>Or click me</div>
+    /// `);
+    /// ```
+    pub fn expose_binding_builder<'a, F, Fut, T>(
+        &self,
+        name: &'a str,
+        callback: F
+    ) -> ExposeBindingBuilder<'a>
+    where
+        F: Fn(BindingCall) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<T, String>> + Send + 'static,
+        T: Serialize
+    {
+        ExposeBindingBuilder::new(self.inner.clone(), name, binding_call::callback(callback))
+    }
+
+    /// Removes a function installed with [`method: Page.exposeFunction`] or [`method: Page.exposeBinding`]. The driver
+    /// can't uninstall it, so the function stays on the `window` object of every frame but calls to it are rejected. The
+    /// name may be exposed again later, which makes the function work again. Removing a name that was never exposed does
+    /// nothing.
+    pub async fn remove_exposed_function(&self, name: &str) -> ArcResult<()> {
+        upgrade(&self.inner)?.remove_exposed_binding(name);
+        Ok(())
+    }
+
+    /// Routing provides the capability to modify network requests that are made by a page.
+    ///
+    /// Once routing is enabled, every request matching the url pattern will stall unless it's continued, fulfilled or
+    /// aborted. `url` is a glob such as `**/*.png` or a [`regex::Regex`], see [`UrlMatcher`]. When several handlers match,
+    /// the one registered last is used; page handlers take precedence over those of the browser context.
+    ///
+    /// An example of aborting all image requests:
+    ///
+    /// ```js
+    /// await page.route('**/*.{png,jpg,jpeg}', route => route.abort());
+    /// await page.goto('https://example.com');
+    /// ```
+    ///
+    /// The handler runs on a task of its own, so it may await other requests before handling the route.
+    pub async fn route<M, F, Fut>(&self, url: M, handler: F) -> ArcResult<()>
+    where
+        M: Into<UrlMatcher>,
+        F: Fn(Route, Request) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static
+    {
+        upgrade(&self.inner)?
+            .route(url.into(), route::handler(handler))
+            .await
+    }
+
+    /// Removes the handlers registered with [`Page::route`] for `url`.
+    pub async fn unroute<M: Into<UrlMatcher>>(&self, url: M) -> ArcResult<()> {
+        upgrade(&self.inner)?.unroute(&url.into()).await
+    }
+
+    /// Sets the handler of the JavaScript dialogs of this page, such as `alert`, `prompt`, `confirm` or `beforeunload`,
+    /// replacing any previous one. The handler **must** either [`Dialog::accept`] or [`Dialog::dismiss`] the dialog -
+    /// otherwise the page will freeze waiting for the dialog.
+    ///
+    /// Until a handler is set, dialogs are dismissed automatically (`beforeunload` ones are accepted) so that they never
+    /// block the page.
+    ///
+    /// ```js
+    /// page.on('dialog', dialog => dialog.accept());
+    /// ```
+    pub async fn on_dialog<F, Fut>(&self, handler: F) -> ArcResult<()>
+    where
+        F: Fn(Dialog) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static
+    {
+        upgrade(&self.inner)?
+            .set_dialog_handler(dialog::handler(handler))
+            .await
+    }
+
+    /// Calls `handler` with every [`ConsoleMessage`] logged by this page, each on a task of its own. The handler stays
+    /// registered as long as the page is alive.
+    ///
+    /// The handles of [`ConsoleMessage::args`] stay valid while the handler runs and are disposed once it returns, so
+    /// they must not be kept past it.
+    pub fn on_console<F, Fut>(&self, handler: F) -> Result<(), Error>
+    where
+        F: Fn(ConsoleMessage) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static
+    {
+        use futures::stream::StreamExt;
+        let mut events = self.subscribe_event()?;
+        spawn(async move {
+            while let Some(e) = events.next().await {
+                if let Ok(Event::Console(m)) = e {
+                    let handled = handler(m.clone());
+                    spawn(async move {
+                        handled.await;
+                        m.dispose_args().await;
+                    });
+                }
+            }
+        });
+        Ok(())
+    }
+
+    /// Calls `handler` with every [`Download`] started by this page, each on a task of its own. The handler stays
+    /// registered as long as the page is alive.
+    ///
+    /// > NOTE: The browser context must be created with `accept_downloads(true)` for the downloaded content to be
+    /// available.
+    pub fn on_download<F, Fut>(&self, handler: F) -> Result<(), Error>
+    where
+        F: Fn(Download) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static
+    {
+        use futures::stream::StreamExt;
+        let mut events = self.subscribe_event()?;
+        spawn(async move {
+            while let Some(e) = events.next().await {
+                if let Ok(Event::Download(d)) = e {
+                    spawn(handler(d));
+                }
+            }
+        });
+        Ok(())
+    }
+
+    /// Calls `handler` with every [`FileChooser`] opened by this page, each on a task of its own, instead of showing the
+    /// native dialog. The handler stays registered as long as the page is alive.
+    ///
+    /// ```js
+    /// page.on('filechooser', async (fileChooser) => {
+    ///  await fileChooser.setFiles('/tmp/myfile.pdf');
+    /// });
+    /// ```
+    pub async fn on_file_chooser<F, Fut>(&self, handler: F) -> ArcResult<()>
+    where
+        F: Fn(FileChooser) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static
+    {
+        use futures::stream::StreamExt;
+        let mut events = self.subscribe_event()?;
+        spawn(async move {
+            while let Some(e) = events.next().await {
+                if let Ok(Event::FileChooser(x)) = e {
+                    spawn(handler(x));
+                }
+            }
+        });
+        upgrade(&self.inner)?
+            .update_subscription("fileChooser", true)
+            .await
+    }
+
+    /// Waits for the next [`FileChooser`] opened by this page instead of the native dialog. Meant to be joined with the
+    /// action that opens it, so that setting the files reads linearly:
+    ///
+    /// ```
+    /// # async fn example(page: playwright::api::Page) -> Result<(), std::sync::Arc<playwright::Error>> {
+    /// let (chooser, _) = tokio::join!(
+    ///     page.expect_file_chooser_builder().expect_file_chooser(),
+    ///     page.click_builder("#upload").click()
+    /// );
+    /// chooser?
+    ///     .set_input_files_builder()
+    ///     .add_path("/tmp/myfile.pdf")
+    ///     .set_input_files()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Like [`Page::on_file_chooser`], file choosers opened by this page keep being intercepted afterwards.
+    pub fn expect_file_chooser_builder(&self) -> ExpectFileChooserBuilder {
+        ExpectFileChooserBuilder::new(self.inner.clone())
+    }
+
+    /// Calls `handler` with every [`Request`] issued by this page, each on a task of its own. The handler stays
+    /// registered as long as the page is alive.
+    pub fn on_request<F, Fut>(&self, handler: F) -> Result<(), Error>
+    where
+        F: Fn(Request) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static
+    {
+        use futures::stream::StreamExt;
+        let mut events = self.subscribe_event()?;
+        spawn(async move {
+            while let Some(e) = events.next().await {
+                if let Ok(Event::Request(x)) = e {
+                    spawn(handler(x));
+                }
+            }
+        });
+        Ok(())
+    }
+
+    /// Calls `handler` with every [`Response`] received by this page, each on a task of its own, as soon as its status
+    /// and headers are received. The handler stays registered as long as the page is alive.
+    ///
+    /// The body is only fetched when the handler asks for it, by which time the browser may have discarded it; see
+    /// [`OnResponseBuilder::prefetch_body`] to read it reliably.
+    pub fn on_response<F, Fut>(&self, handler: F) -> Result<(), Error>
+    where
+        F: Fn(Response) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static
+    {
+        self.on_response_builder(handler).on_response()
+    }
+
+    /// [`Page::on_response`] with options.
+    pub fn on_response_builder<F, Fut>(&self, handler: F) -> OnResponseBuilder<F>
+    where
+        F: Fn(Response) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static
+    {
+        OnResponseBuilder::new(self.clone(), handler)
+    }
+
+    /// Calls `handler` with every [`Request`] of this page that fails, each on a task of its own. The reason is
+    /// available through [`Request::failure`]. The handler stays registered as long as the page is alive.
+    ///
+    /// > NOTE: HTTP error responses such as 404 or 503 are still successful responses from the HTTP standpoint, so the
+    /// request completes rather than fails.
+    pub fn on_request_failed<F, Fut>(&self, handler: F) -> Result<(), Error>
+    where
+        F: Fn(Request) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static
+    {
+        use futures::stream::StreamExt;
+        let mut events = self.subscribe_event()?;
+        spawn(async move {
+            while let Some(e) = events.next().await {
+                if let Ok(Event::RequestFailed(x)) = e {
+                    spawn(handler(x));
+                }
+            }
+        });
+        Ok(())
+    }
+
     // coverage
-    // expose_binding
-    // expose_function
-    // route
-    // unroute
-    // once_dialog
 
     pub async fn wait_for_timeout(&self, timeout: f64) {
         sleep(std::time::Duration::from_millis(timeout as u64)).await
@@ -368,8 +812,9 @@ pub enum Event {
     /// [freeze](https://developer.mozilla.org/en-US/docs/Web/JavaScript/EventLoop#never_blocking) waiting for the dialog, and
     /// actions like click will never finish.
     ///
-    /// > NOTE: When no [`event: Page.dialog`] listeners are present, all dialogs are automatically dismissed.
-    Dialog,
+    /// > NOTE: Dialogs are automatically dismissed unless a handler is set with [`Page::on_dialog`]; listening to this
+    /// event alone doesn't prevent that.
+    Dialog(Dialog),
     DomContentLoaded,
     /// Emitted when attachment download started. User can access basic file operations on downloaded content via the passed
     /// `Download` instance.
@@ -386,7 +831,9 @@ pub enum Event {
     ///  await fileChooser.setFiles('/tmp/myfile.pdf');
     /// });
     /// ```
-    // FileChooser(FileChooser),
+    ///
+    /// The driver only reports file choosers once [`Page::on_file_chooser`] has been called.
+    FileChooser(FileChooser),
     FrameAttached(Frame),
     FrameDetached(Frame),
     FrameNavigated(Frame),
@@ -435,9 +882,9 @@ impl From<Evt> for Event {
             Evt::Close => Event::Close,
             Evt::Crash => Event::Crash,
             Evt::Console(x) => Event::Console(ConsoleMessage::new(x)),
-            Evt::Dialog => Event::Dialog,
+            Evt::Dialog(x) => Event::Dialog(Dialog::new(x)),
             Evt::Download(x) => Event::Download(Download::new(x)),
-            // Evt::FileChooser(x) => Event::FileChooser(x),
+            Evt::FileChooser(x) => Event::FileChooser(x),
             Evt::DomContentLoaded => Event::DomContentLoaded,
             Evt::PageError => Event::PageError,
             Evt::Request(x) => Event::Request(Request::new(x)),
@@ -464,9 +911,9 @@ impl IsEvent for Event {
             Self::Close => EventType::Close,
             Self::Crash => EventType::Crash,
             Self::Console(_) => EventType::Console,
-            Self::Dialog => EventType::Dialog,
+            Self::Dialog(_) => EventType::Dialog,
             Self::Download(_) => EventType::Download,
-            // Self::FileChooser(_) => EventType::FileChooser,
+            Self::FileChooser(_) => EventType::FileChooser,
             Self::DomContentLoaded => EventType::DomContentLoaded,
             Self::PageError => EventType::PageError,
             Self::Request(_) => EventType::Request,
@@ -496,6 +943,35 @@ macro_rules! is_checked {
 
 /// Shorthand of main_frame
 impl Page {
+    /// Creates a [`Locator`] for `selector` in the main frame.
+    pub fn locator(&self, selector: &str) -> Locator { self.main_frame().locator(selector) }
+
+    /// See [`Frame::get_by_role`].
+    pub fn get_by_role(&self, role: &str, options: GetByRoleOptions) -> Locator {
+        self.main_frame().get_by_role(role, options)
+    }
+
+    /// See [`Frame::get_by_text`].
+    pub fn get_by_text(&self, text: &str, exact: bool) -> Locator {
+        self.main_frame().get_by_text(text, exact)
+    }
+
+    /// See [`Frame::get_by_label`].
+    pub fn get_by_label(&self, text: &str, exact: bool) -> Locator {
+        self.main_frame().get_by_label(text, exact)
+    }
+
+    /// See [`Frame::get_by_placeholder`].
+    pub fn get_by_placeholder(&self, text: &str, exact: bool) -> Locator {
+        self.main_frame().get_by_placeholder(text, exact)
+    }
+
+    /// See [`Frame::get_by_test_id`].
+    pub fn get_by_test_id(&self, test_id: &str) -> Locator {
+        self.main_frame().get_by_test_id(test_id)
+    }
+
+    /// See [`Frame::query_selector`], which pierces open shadow roots.
     pub async fn query_selector(&self, selector: &str) -> ArcResult<Option<ElementHandle>> {
         self.main_frame().query_selector(selector).await
     }
@@ -504,6 +980,32 @@ impl Page {
         self.main_frame().query_selector_all(selector).await
     }
 
+    /// Runs `f` with the element matching `selector`, then disposes of the element handle whatever `f` returned, so that
+    /// handles don't pile up in long running sessions. Returns `None` without calling `f` when no element matches.
+    ///
+    /// The handle must not be used once `f` has completed.
+    pub async fn with_element<F, Fut, T>(&self, selector: &str, f: F) -> ArcResult<Option<T>>
+    where
+        F: FnOnce(ElementHandle) -> Fut,
+        Fut: std::future::Future<Output = T>
+    {
+        let handle = match upgrade(&self.main_frame_weak())?
+            .query_selector(selector)
+            .await?
+        {
+            Some(h) => h,
+            None => return Ok(None)
+        };
+        let output = f(ElementHandle::new(handle.clone())).await;
+        // The handle is already gone if its frame navigated meanwhile
+        if let Some(h) = handle.upgrade() {
+            if let Err(e) = h.dispose().await {
+                log::trace!("Failed to dispose element handle: {:?}", e);
+            }
+        }
+        Ok(Some(output))
+    }
+
     pub fn wait_for_selector_builder<'a>(&self, selector: &'a str) -> WaitForSelectorBuilder<'a> {
         self.main_frame().wait_for_selector_builder(selector)
     }
@@ -569,6 +1071,34 @@ impl Page {
         self.main_frame().evaluate(expression, arg).await
     }
 
+    /// [`Page::evaluate`] with options, such as evaluating in an isolated world so that instrumentation doesn't collide
+    /// with the globals of the page.
+    ///
+    /// ```
+    /// # async fn example(page: playwright::api::Page) -> Result<(), std::sync::Arc<playwright::Error>> {
+    /// let clicks: i32 = page
+    ///     .evaluate_builder("() => (window.clicks = (window.clicks || 0) + 1)")
+    ///     .isolated_world(true)
+    ///     .evaluate()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn evaluate_builder<'a>(&self, expression: &'a str) -> EvaluateBuilder<'a> {
+        EvaluateBuilder::new(self.inner.clone(), expression)
+    }
+
+    /// Scrolls the main frame's window to the given document coordinates, clamped to the scrollable area.
+    pub async fn scroll_to(&self, x: f64, y: f64) -> ArcResult<()> {
+        self.evaluate("([x, y]) => window.scrollTo(x, y)", (x, y))
+            .await
+    }
+
+    /// Returns the main frame's window scroll offsets as `(scrollX, scrollY)`.
+    pub async fn scroll_position(&self) -> ArcResult<(f64, f64)> {
+        self.eval("() => [window.scrollX, window.scrollY]").await
+    }
+
     pub async fn evaluate_on_selector<T, U>(
         &self,
         selector: &str,
@@ -644,7 +1174,27 @@ impl Page {
         GotoBuilder::new(self.main_frame_weak(), url)
     }
 
-    // wait_for_load_state
+    /// Waits for the main frame to reach the given load state, resolving immediately if it has already been reached.
+    ///
+    /// Shortcut for main frame's [`method: Frame.waitForLoadState`]
+    pub async fn wait_for_load_state(
+        &self,
+        state: DocumentLoadState,
+        timeout: Option<f64>
+    ) -> ArcResult<()> {
+        self.main_frame().wait_for_load_state(state, timeout).await
+    }
+
+    /// Waits for the main frame to navigate to the given URL, including navigations made with `history.pushState`.
+    ///
+    /// Shortcut for main frame's [`method: Frame.waitForURL`]
+    pub async fn wait_for_url<M: Into<UrlMatcher>>(
+        &self,
+        url: M,
+        timeout: Option<f64>
+    ) -> ArcResult<()> {
+        self.main_frame().wait_for_url(url, timeout).await
+    }
 
     pub async fn title(&self) -> ArcResult<String> { self.main_frame().title().await }
 
@@ -656,6 +1206,7 @@ impl Page {
         self.main_frame().dblclick_builder(selector)
     }
 
+    /// See [`Frame::tap_builder`], which requires a browser context created with `has_touch` enabled.
     pub fn tap_builder<'a>(&self, selector: &'a str) -> TapBuilder<'a> {
         self.main_frame().tap_builder(selector)
     }
@@ -699,22 +1250,33 @@ impl Page {
         self.main_frame().hover_builder(selector)
     }
 
+    pub fn drag_and_drop_builder<'a, 'b>(
+        &self,
+        source: &'a str,
+        target: &'b str
+    ) -> DragAndDropBuilder<'a, 'b> {
+        self.main_frame().drag_and_drop_builder(source, target)
+    }
+
     pub fn select_option_builder<'a>(&self, selector: &'a str) -> SelectOptionBuilder<'a> {
         self.main_frame().select_option_builder(selector)
     }
 
-    pub fn set_input_files_builder<'a>(
-        &self,
-        selector: &'a str,
-        file: File
-    ) -> SetInputFilesBuilder<'a> {
-        self.main_frame().set_input_files_builder(selector, file)
+    pub fn set_input_files_builder<'a>(&self, selector: &'a str) -> SetInputFilesBuilder<'a> {
+        self.main_frame().set_input_files_builder(selector)
     }
 
-    pub fn type_builer<'a, 'b>(&self, selector: &'a str, text: &'b str) -> TypeBuilder<'a, 'b> {
+    /// See [`Frame::type_builder`]. The element is focused before typing.
+    pub fn type_builder<'a, 'b>(&self, selector: &'a str, text: &'b str) -> TypeBuilder<'a, 'b> {
         self.main_frame().type_builder(selector, text)
     }
 
+    #[deprecated(note = "renamed to `type_builder`")]
+    pub fn type_builer<'a, 'b>(&self, selector: &'a str, text: &'b str) -> TypeBuilder<'a, 'b> {
+        self.type_builder(selector, text)
+    }
+
+    /// See [`Frame::press_builder`]. The element is focused before pressing `key`.
     pub fn press_builder<'a, 'b>(&self, selector: &'a str, key: &'b str) -> PressBuilder<'a, 'b> {
         self.main_frame().press_builder(selector, key)
     }
@@ -727,10 +1289,24 @@ impl Page {
         self.main_frame().uncheck_builder(selector)
     }
 
+    pub fn set_checked_builder<'a>(
+        &self,
+        selector: &'a str,
+        checked: bool
+    ) -> SetCheckedBuilder<'a> {
+        self.main_frame().set_checked_builder(selector, checked)
+    }
+
     pub fn wait_for_function_builder<'a>(&self, expression: &'a str) -> WaitForFunctionBuilder<'a> {
         self.main_frame().wait_for_function_builder(expression)
     }
-    // expect_navigation
+
+    /// Waits for the next navigation of the main frame, see [`Frame::expect_navigation_builder`].
+    ///
+    /// Shortcut for main frame's [`method: Frame.waitForNavigation`]
+    pub fn expect_navigation_builder(&self) -> ExpectNavigationBuilder {
+        self.main_frame().expect_navigation_builder()
+    }
 }
 
 macro_rules! navigation {
@@ -768,6 +1344,14 @@ navigation!(ReloadBuilder, reload);
 navigation!(GoBackBuilder, go_back);
 navigation!(GoForwardBuilder, go_forward);
 
+impl ReloadBuilder {
+    setter! {
+        /// Performs a hard reload: every resource is fetched again instead of being served from the HTTP cache, like
+        /// `Ctrl+Shift+R`. Defaults to `false`.
+        bypass_cache: Option<bool>
+    }
+}
+
 pub struct PdfBuilder<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i, 'j> {
     inner: Weak<Impl>,
     args: PdfArgs<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i, 'j>
@@ -779,10 +1363,10 @@ impl<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i, 'j> PdfBuilder<'a, 'b, 'c, 'd, 'e, 'f,
         Self { inner, args }
     }
 
-    pub async fn pdf(self) -> Result<(), Arc<Error>> {
+    /// Returns the PDF bytes, also writing them to `path` when it is set.
+    pub async fn pdf(self) -> ArcResult<Vec<u8>> {
         let Self { inner, args } = self;
-        let _ = upgrade(&inner)?.pdf(args).await?;
-        Ok(())
+        upgrade(&inner)?.pdf(args).await
     }
 
     setter! {
@@ -834,6 +1418,7 @@ impl ScreenshotBuilder {
         Self { inner, args }
     }
 
+    /// Returns the captured image bytes, also writing them to `path` when it is set.
     pub async fn screenshot(self) -> ArcResult<Vec<u8>> {
         let Self { inner, args } = self;
         upgrade(&inner)?.screenshot(args).await
@@ -853,9 +1438,11 @@ impl ScreenshotBuilder {
         /// Hides default white background and allows capturing screenshots with transparency. Not applicable to `jpeg` images.
         /// Defaults to `false`.
         omit_background: Option<bool>,
+        /// The quality of the image, between 0-100. Not applicable to `png` images.
         quality: Option<i32>,
-        /// Maximum time in milliseconds, defaults to 30 seconds, pass `0` to disable timeout. The default value can be changed by
-        /// using the [`method: BrowserContext.setDefaultTimeout`] or [`method: Page.setDefaultTimeout`] methods.
+        /// Maximum time in milliseconds, defaults to the page's default timeout, pass `0` to disable timeout. The default value can
+        /// be changed by using the [`method: BrowserContext.setDefaultTimeout`] or [`method: Page.setDefaultTimeout`] methods.
+        /// Exceeding it fails with [`Error::Timeout`](crate::Error::Timeout).
         timeout: Option<f64>,
         /// The file path to save the image to. The screenshot type will be inferred from file extension. If `path` is a relative
         /// path, then it is resolved relative to the current working directory. If no path is provided, the image won't be saved to
@@ -867,6 +1454,356 @@ impl ScreenshotBuilder {
         self.args.r#type = None;
         self
     }
+
+    /// Resizes the page to `width` x `height` for this capture only. The viewport the page had before is restored once
+    /// the screenshot is taken. Fails with [`Error::NoViewport`] on pages without a viewport, such as those of a context
+    /// created with `no_viewport`, as they could not be restored.
+    pub fn viewport(mut self, width: i32, height: i32) -> Self {
+        self.args.viewport = Some(Viewport { width, height });
+        self
+    }
+
+    pub fn clear_viewport(mut self) -> Self {
+        self.args.viewport = None;
+        self
+    }
+}
+
+/// [`Page::expose_binding_builder`]
+pub struct ExposeBindingBuilder<'a> {
+    inner: Weak<Impl>,
+    name: &'a str,
+    callback: BindingCallback,
+    handle: bool
+}
+
+impl<'a> ExposeBindingBuilder<'a> {
+    pub(crate) fn new(inner: Weak<Impl>, name: &'a str, callback: BindingCallback) -> Self {
+        Self {
+            inner,
+            name,
+            callback,
+            handle: false
+        }
+    }
+
+    pub async fn expose_binding(self) -> ArcResult<()> {
+        let Self {
+            inner,
+            name,
+            callback,
+            handle
+        } = self;
+        upgrade(&inner)?
+            .expose_binding(name, handle, callback)
+            .await
+    }
+
+    /// Whether to pass the argument as a handle, instead of passing by value. When passing a handle, only one argument is
+    /// supported, and it is available through [`BindingCall::handle`] or [`BindingCall::element_handle`]. Defaults to
+    /// `false`. A name exposed again must keep the value it was first exposed with, or
+    /// [`Error::BindingNeedsHandle`] is returned.
+    pub fn handle(mut self, x: bool) -> Self {
+        self.handle = x;
+        self
+    }
+}
+
+pub struct ExpectRequestFinishedBuilder<F> {
+    inner: Weak<Impl>,
+    predicate: F,
+    timeout: Option<f64>
+}
+
+impl<F> ExpectRequestFinishedBuilder<F>
+where
+    F: Fn(&Request) -> bool + Send + Sync + 'static
+{
+    pub(crate) fn new(inner: Weak<Impl>, predicate: F) -> Self {
+        Self {
+            inner,
+            predicate,
+            timeout: None
+        }
+    }
+
+    /// Fails with [`Error::Timeout`] if no matching request finishes in time.
+    pub async fn expect_request_finished(self) -> Result<Request, Error> {
+        let Self {
+            inner,
+            predicate,
+            timeout
+        } = self;
+        let (stream, timeout) = {
+            let this = upgrade(&inner)?;
+            let timeout = timeout
+                .map(|t| t as u32)
+                .unwrap_or_else(|| this.default_timeout());
+            (this.subscribe_event(), timeout)
+        };
+        let matches = move |e: &Evt| match e {
+            Evt::RequestFinished(r) => predicate(&Request::new(r.clone())),
+            _ => false
+        };
+        match expect_event_matching(stream, matches, timeout).await? {
+            Evt::RequestFinished(r) => Ok(Request::new(r)),
+            _ => unreachable!()
+        }
+    }
+
+    /// Maximum time to wait for in milliseconds. Defaults to the page's default timeout.
+    pub fn timeout(mut self, x: f64) -> Self {
+        self.timeout = Some(x);
+        self
+    }
+
+    pub fn clear_timeout(mut self) -> Self {
+        self.timeout = None;
+        self
+    }
+}
+
+/// [`Page::evaluate_builder`]
+pub struct EvaluateBuilder<'a> {
+    inner: Weak<Impl>,
+    expression: &'a str,
+    arg: Option<Value>,
+    isolated_world: bool,
+    err: Option<Error>
+}
+
+impl<'a> EvaluateBuilder<'a> {
+    pub(crate) fn new(inner: Weak<Impl>, expression: &'a str) -> Self {
+        Self {
+            inner,
+            expression,
+            arg: None,
+            isolated_world: false,
+            err: None
+        }
+    }
+
+    pub async fn evaluate<U>(self) -> ArcResult<U>
+    where
+        U: DeserializeOwned
+    {
+        let Self {
+            inner,
+            expression,
+            arg,
+            isolated_world,
+            err
+        } = self;
+        if let Some(e) = err {
+            return Err(e.into());
+        }
+        let inner = upgrade(&inner)?;
+        if isolated_world {
+            inner.evaluate_in_isolated_world(expression, arg).await
+        } else {
+            upgrade(&inner.main_frame())?
+                .evaluate(expression, arg)
+                .await
+        }
+    }
+
+    pub fn arg<T>(mut self, x: &T) -> Self
+    where
+        T: Serialize
+    {
+        match serde_json::to_value(x).map_err(Error::Serde) {
+            Ok(arg) => self.arg = Some(arg),
+            Err(e) => self.err = Some(e)
+        }
+        self
+    }
+
+    pub fn clear_arg(mut self) -> Self {
+        self.arg = None;
+        self.err = None;
+        self
+    }
+
+    /// Whether to evaluate `expression` in an isolated world of the main frame, which shares the DOM with the page but
+    /// not its JavaScript globals, so that neither sees the variables the other defines. Defaults to `false`.
+    ///
+    /// The world persists across evaluations until the page navigates. The argument and the result are passed as
+    /// plain JSON, so handles and values such as dates are not supported there.
+    ///
+    /// > NOTE: Isolated worlds are created through the Chrome DevTools Protocol, so other browsers fail with
+    /// [`Error::CdpNotSupported`](crate::Error::CdpNotSupported).
+    pub fn isolated_world(mut self, x: bool) -> Self {
+        self.isolated_world = x;
+        self
+    }
+}
+
+pub struct OnResponseBuilder<F> {
+    page: Page,
+    handler: F,
+    prefetch_body: Option<UrlMatcher>
+}
+
+impl<F, Fut> OnResponseBuilder<F>
+where
+    F: Fn(Response) -> Fut + Send + Sync + 'static,
+    Fut: std::future::Future<Output = ()> + Send + 'static
+{
+    pub(crate) fn new(page: Page, handler: F) -> Self {
+        Self {
+            page,
+            handler,
+            prefetch_body: None
+        }
+    }
+
+    pub fn on_response(self) -> Result<(), Error> {
+        use futures::stream::StreamExt;
+        let Self {
+            page,
+            handler,
+            prefetch_body
+        } = self;
+        let mut events = page.subscribe_event()?;
+        let handler = Arc::new(handler);
+        spawn(async move {
+            while let Some(e) = events.next().await {
+                let x = match e {
+                    Ok(Event::Response(x)) => x,
+                    _ => continue
+                };
+                let prefetch = match (&prefetch_body, x.url()) {
+                    (Some(m), Ok(url)) => m.is_match(&url),
+                    _ => false
+                };
+                if !prefetch {
+                    spawn(handler(x));
+                    continue;
+                }
+                let handler = handler.clone();
+                spawn(async move {
+                    let x = match x.body().await {
+                        Ok(body) => x.with_body(body),
+                        Err(_) => x
+                    };
+                    handler(x).await
+                });
+            }
+        });
+        Ok(())
+    }
+
+    /// Fetches the body of the responses whose url matches, a glob such as `**/api/*` or a [`regex::Regex`], before the
+    /// handler is called, so that [`Response::body`] succeeds even if the browser has discarded it or the page has been
+    /// closed since. Bodies of other responses are still fetched lazily.
+    pub fn prefetch_body<M: Into<UrlMatcher>>(mut self, url: M) -> Self {
+        self.prefetch_body = Some(url.into());
+        self
+    }
+
+    pub fn clear_prefetch_body(mut self) -> Self {
+        self.prefetch_body = None;
+        self
+    }
+}
+
+pub struct WaitForResponseBuilder {
+    inner: Weak<Impl>,
+    url: UrlMatcher,
+    timeout: Option<f64>
+}
+
+impl WaitForResponseBuilder {
+    pub(crate) fn new(inner: Weak<Impl>, url: UrlMatcher) -> Self {
+        Self {
+            inner,
+            url,
+            timeout: None
+        }
+    }
+
+    /// Fails with [`Error::Timeout`] if no matching response is received in time.
+    pub async fn wait_for_response(self) -> Result<Response, Error> {
+        let Self {
+            inner,
+            url,
+            timeout
+        } = self;
+        let (stream, timeout) = {
+            let this = upgrade(&inner)?;
+            let timeout = timeout
+                .map(|t| t as u32)
+                .unwrap_or_else(|| this.default_timeout());
+            (this.subscribe_event(), timeout)
+        };
+        let matches = move |e: &Evt| match e {
+            Evt::Response(r) => r
+                .upgrade()
+                .map(|r| url.is_match(r.url()))
+                .unwrap_or_default(),
+            _ => false
+        };
+        match expect_event_matching(stream, matches, timeout).await? {
+            Evt::Response(r) => Ok(Response::new(r)),
+            _ => unreachable!()
+        }
+    }
+
+    /// Maximum time to wait for in milliseconds. Defaults to the page's default timeout.
+    pub fn timeout(mut self, x: f64) -> Self {
+        self.timeout = Some(x);
+        self
+    }
+
+    pub fn clear_timeout(mut self) -> Self {
+        self.timeout = None;
+        self
+    }
+}
+
+pub struct ExpectFileChooserBuilder {
+    inner: Weak<Impl>,
+    timeout: Option<f64>
+}
+
+impl ExpectFileChooserBuilder {
+    pub(crate) fn new(inner: Weak<Impl>) -> Self {
+        Self {
+            inner,
+            timeout: None
+        }
+    }
+
+    /// Fails with [`Error::Timeout`] if no file chooser is opened in time.
+    pub async fn expect_file_chooser(self) -> ArcResult<FileChooser> {
+        let Self { inner, timeout } = self;
+        let this = upgrade(&inner)?;
+        let timeout = timeout
+            .map(|t| t as u32)
+            .unwrap_or_else(|| this.default_timeout());
+        let matches = |e: &Evt| matches!(e, Evt::FileChooser(_));
+        // Polled together so that the subscription is requested before the action opening the chooser
+        let (subscribed, chooser) = futures::join!(
+            this.update_subscription("fileChooser", true),
+            expect_event_matching(this.subscribe_event(), matches, timeout)
+        );
+        subscribed?;
+        match chooser? {
+            Evt::FileChooser(x) => Ok(x),
+            _ => unreachable!()
+        }
+    }
+
+    /// Maximum time to wait for in milliseconds. Defaults to the page's default timeout.
+    pub fn timeout(mut self, x: f64) -> Self {
+        self.timeout = Some(x);
+        self
+    }
+
+    pub fn clear_timeout(mut self) -> Self {
+        self.timeout = None;
+        self
+    }
 }
 
 pub struct EmulateMediaBuilder {
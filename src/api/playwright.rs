@@ -9,7 +9,7 @@ use std::{io, process::Command};
 /// Entry point
 pub struct Playwright {
     driver: Driver,
-    _conn: Connection,
+    conn: Connection,
     inner: Weak<Impl>,
 }
 
@@ -84,6 +84,8 @@ impl Playwright {
     /// # Errors
     ///
     /// This function will return an error if the connection to the driver process fails.
+    /// When the driver exits before initializing, the error is [`Error::DriverStartup`]
+    /// and carries what the driver wrote to stderr.
     ///
     /// # Examples
     ///
@@ -97,19 +99,45 @@ impl Playwright {
     /// # }
     /// ```
     pub async fn with_driver(driver: Driver) -> Result<Playwright, Error> {
-        let conn = Connection::run(&driver)?;
-        let p = Impl::wait_initial_object(&conn).await?;
+        let mut conn = Connection::run(&driver)?;
+        let p = match Impl::wait_initial_object(&conn).await {
+            Ok(p) => p,
+            Err(e) => return Err(conn.startup_error(e))
+        };
         Ok(Self {
             driver,
-            _conn: conn,
+            conn,
             inner: p,
         })
     }
 
+    /// Returns whether the browsers installed by [`prepare`](Self::prepare) are already present.
+    ///
+    /// This only inspects the browsers directory (`PLAYWRIGHT_BROWSERS_PATH`, or
+    /// `$CACHE_DIR/ms-playwright` by default) for the revisions pinned by the driver;
+    /// it neither spawns the CLI nor touches the network.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use playwright::Playwright;
+    /// # let playwright = Playwright::initialize().await?;
+    /// if !playwright.browsers_installed() {
+    ///     playwright.prepare()?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn browsers_installed(&self) -> bool { self.driver.browsers_installed() }
+
     /// Installs all Playwright browsers (Chromium, Firefox, and WebKit).
     ///
     /// This runs the equivalent of `playwright install` and downloads all supported
     /// browser engines. It may take several minutes depending on your internet connection.
+    /// When [`browsers_installed`](Self::browsers_installed) is already true this returns
+    /// immediately without spawning the CLI, so it is cheap to call on every run and works
+    /// offline with a pre-populated cache.
     /// If you only need specific browsers, consider using [`install_chromium`](Self::install_chromium),
     /// [`install_firefox`](Self::install_firefox), or [`install_webkit`](Self::install_webkit) instead.
     ///
@@ -128,6 +156,9 @@ impl Playwright {
     /// # }
     /// ```
     pub fn prepare(&self) -> io::Result<()> {
+        if self.browsers_installed() {
+            return Ok(());
+        }
         run(&self.driver, &["install"])
     }
 
@@ -321,6 +352,24 @@ impl Playwright {
         Some(Selectors::new(selectors_weak))
     }
 
+    /// Returns what the driver process has written to stderr, up to its last 1000 lines.
+    ///
+    /// The driver only writes diagnostics there, such as Node.js warnings or crash reports,
+    /// which are also logged at the `debug` level as they are read.
+    pub fn driver_stderr(&self) -> String { self.conn.stderr().text() }
+
+    /// Returns how many protocol objects, such as pages, frames or element handles, the driver has
+    /// created on this connection and not disposed yet.
+    ///
+    /// This is a diagnostic for spotting handles that are never disposed in long running sessions.
+    pub fn object_count(&self) -> usize {
+        self.conn
+            .context()
+            .upgrade()
+            .map(|ctx| ctx.lock().unwrap().object_count())
+            .unwrap_or_default()
+    }
+
     /// Returns a dictionary of all available device descriptors.
     ///
     /// Device descriptors contain pre-configured settings for various devices
@@ -395,4 +444,81 @@ mod tests {
             assert_eq!(e.kind(), io::ErrorKind::Other);
         }
     });
+
+    crate::runtime_test!(bad_driver_path_error_source, {
+        use std::error::Error as _;
+        let driver = Driver::new(std::env::temp_dir().join("playwright-rust-missing-driver"));
+        let err = match Playwright::with_driver(driver).await {
+            Ok(_) => unreachable!(),
+            Err(e) => e
+        };
+        assert!(matches!(err, Error::Io(_)));
+        let source = err.source().unwrap().downcast_ref::<io::Error>().unwrap();
+        assert_eq!(source.kind(), io::ErrorKind::NotFound);
+    });
+
+    #[cfg(unix)]
+    crate::runtime_test!(failing_driver_stderr, {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = std::env::temp_dir().join(format!(
+            "playwright-rust-failing-driver-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let node = dir.join("node");
+        std::fs::write(
+            &node,
+            "#!/bin/sh\necho 'Error: driver exploded' >&2\nexit 1\n"
+        )
+        .unwrap();
+        std::fs::set_permissions(&node, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let driver = Driver::new(&dir).on_stderr({
+            let lines = lines.clone();
+            move |line| lines.lock().unwrap().push(line.to_owned())
+        });
+        let err = match Playwright::with_driver(driver).await {
+            Ok(_) => unreachable!(),
+            Err(e) => e
+        };
+        std::fs::remove_dir_all(&dir).ok();
+        match err {
+            Error::DriverStartup { stderr, .. } => {
+                assert!(stderr.contains("driver exploded"), "{}", stderr)
+            }
+            e => panic!("{:?}", e)
+        }
+        assert_eq!(*lines.lock().unwrap(), vec!["Error: driver exploded"]);
+    });
+
+    #[cfg(unix)]
+    crate::runtime_test!(prepare_when_installed, {
+        use std::os::unix::fs::PermissionsExt;
+        let mut p = Playwright::initialize().await.unwrap();
+        p.prepare().unwrap();
+        assert!(p.browsers_installed());
+        // A driver pinning the same browsers, whose CLI leaves a mark if it runs
+        let dir = std::env::temp_dir().join(format!(
+            "playwright-rust-marking-driver-{}",
+            std::process::id()
+        ));
+        let package = dir.join("package");
+        std::fs::create_dir_all(&package).unwrap();
+        let installed = p.driver().cli_script().with_file_name("browsers.json");
+        std::fs::copy(installed, package.join("browsers.json")).unwrap();
+        let node = dir.join("node");
+        let mark = dir.join("ran");
+        let script = format!("#!/bin/sh\ntouch '{}'\nexit 1\n", mark.display());
+        std::fs::write(&node, script).unwrap();
+        std::fs::set_permissions(&node, std::fs::Permissions::from_mode(0o755)).unwrap();
+        *p.driver() = Driver::new(&dir);
+        let prepared = p.prepare();
+        let ran = mark.exists();
+        std::fs::remove_dir_all(&dir).ok();
+        prepared.unwrap();
+        assert!(
+            !ran,
+            "prepare ran the CLI although the browsers are installed"
+        );
+    });
 }
@@ -1,5 +1,5 @@
 use crate::{
-    api::{Frame, Response},
+    api::{Frame, Response, Worker},
     imp::{core::*, prelude::*, request::Request as Impl, utils::ResponseTiming}
 };
 
@@ -51,12 +51,20 @@ impl Request {
         Ok(upgrade(&self.inner)?.is_navigation_request())
     }
 
-    /// Returns the `Frame` that initiated this request.
+    /// Returns the `Frame` that initiated this request. Requests issued by a service worker have no frame, so the
+    /// methods of the returned one fail for them; see [`Request::service_worker`].
     pub fn frame(&self) -> Frame {
         let inner = weak_and_then(&self.inner, |rc| rc.frame());
         Frame::new(inner)
     }
 
+    /// The service worker that issued this request, if it was issued by one.
+    ///
+    /// > NOTE: This is only supported by Chromium.
+    pub fn service_worker(&self) -> Result<Option<Worker>, Error> {
+        Ok(upgrade(&self.inner)?.service_worker().map(Worker::new))
+    }
+
     pub fn post_data(&self) -> Result<Option<Vec<u8>>, Error> {
         Ok(upgrade(&self.inner)?.post_data())
     }
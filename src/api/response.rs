@@ -5,7 +5,8 @@ use crate::{
 
 #[derive(Debug, Clone)]
 pub struct Response {
-    inner: Weak<Impl>
+    inner: Weak<Impl>,
+    body: Option<Arc<Vec<u8>>>
 }
 
 impl PartialEq for Response {
@@ -19,7 +20,15 @@ impl PartialEq for Response {
 }
 
 impl Response {
-    pub(crate) fn new(inner: Weak<Impl>) -> Self { Self { inner } }
+    pub(crate) fn new(inner: Weak<Impl>) -> Self { Self { inner, body: None } }
+
+    /// Keeps a body fetched ahead of time, which stays readable after the response is disposed with its page.
+    pub(crate) fn with_body(self, body: Vec<u8>) -> Self {
+        Self {
+            body: Some(Arc::new(body)),
+            ..self
+        }
+    }
 
     pub fn url(&self) -> Result<String, Error> { Ok(upgrade(&self.inner)?.url().into()) }
     /// Contains the status code of the response (e.g., 200 for a success).
@@ -32,6 +41,22 @@ impl Response {
     /// Contains a boolean stating whether the response was successful (status in the range 200-299) or not.
     pub fn ok(&self) -> Result<bool, Error> { Ok(upgrade(&self.inner)?.ok()) }
 
+    /// Whether this response was fulfilled by a service worker, through its `FetchEvent.respondWith`, rather than
+    /// received from the network.
+    ///
+    /// > NOTE: This is only supported by Chromium.
+    pub fn from_service_worker(&self) -> Result<bool, Error> {
+        Ok(upgrade(&self.inner)?.is_from_service_worker())
+    }
+
+    /// Whether the response was served from the browser cache, either directly or after being revalidated by the server
+    /// with a `304 Not Modified`.
+    ///
+    /// The response is looked up in the [Resource Timing](https://developer.mozilla.org/en-US/docs/Web/API/PerformanceResourceTiming)
+    /// entries of the frame that made the request, so this returns `false` once the frame has navigated away, or for
+    /// cross-origin resources that don't allow timing information.
+    pub async fn from_cache(&self) -> ArcResult<bool> { upgrade(&self.inner)?.is_from_cache().await }
+
     pub fn request(&self) -> Request {
         let inner = weak_and_then(&self.inner, |rc| rc.request());
         Request::new(inner)
@@ -42,13 +67,29 @@ impl Response {
         upgrade(&self.inner)?.finished().await
     }
 
-    pub async fn body(&self) -> ArcResult<Vec<u8>> { upgrade(&self.inner)?.body().await }
+    /// Returns the buffer with response body. The body is cached after the first read, so this and [`Response::text`] or
+    /// [`Response::json`] can be called repeatedly on the same response.
+    pub async fn body(&self) -> ArcResult<Vec<u8>> {
+        match &self.body {
+            Some(body) => Ok(body.to_vec()),
+            None => upgrade(&self.inner)?.body().await
+        }
+    }
 
     /// Returns the text representation of response body.
-    pub async fn text(&self) -> ArcResult<String> { upgrade(&self.inner)?.text().await }
+    pub async fn text(&self) -> ArcResult<String> {
+        Ok(String::from_utf8(self.body().await?).map_err(Error::InvalidUtf8)?)
+    }
+
+    /// Returns the JSON representation of response body.
+    ///
+    /// This method will throw if the response body is not parsable via `JSON.parse`.
+    pub async fn json<T: DeserializeOwned>(&self) -> ArcResult<T> {
+        Ok(serde_json::from_slice(&self.body().await?).map_err(Error::Serde)?)
+    }
 
     /// Returns the object with HTTP headers associated with the response. All header names are lower-case.
-    pub async fn headers(&self) -> ArcResult<Vec<Header>> { upgrade(&self.inner)?.headers().await }
+    pub async fn headers(&self) -> ArcResult<Vec<Header>> { Ok(upgrade(&self.inner)?.headers()) }
 
     /// Shortcut for [`Response::request`]'s  [`Request::frame`]
     pub fn frame(&self) -> Frame { self.request().frame() }
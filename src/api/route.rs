@@ -1,11 +1,12 @@
 use crate::{
-    api::{Header, Request},
+    api::{AbortErrorCode, Header, Request},
     imp::{
         core::*,
         prelude::*,
-        route::{ContinueArgs, FulfillArgs, Route as Impl},
-    },
+        route::{ContinueArgs, FulfillArgs, Route as Impl, RouteHandler}
+    }
 };
+use std::future::Future;
 
 /// Whenever a network route is set up with [`method: Page.route`] or [`method: BrowserContext.route`], the `Route` object
 /// allows to handle the route.
@@ -24,9 +25,7 @@ impl PartialEq for Route {
 }
 
 impl Route {
-    fn new(inner: Weak<Impl>) -> Self {
-        Self { inner }
-    }
+    pub(crate) fn new(inner: Weak<Impl>) -> Self { Self { inner } }
 
     /// A request to be routed.
     pub fn request(&self) -> Request {
@@ -35,24 +34,9 @@ impl Route {
     }
 
     /// Aborts the route's request.
-    /// Optional error code. Defaults to `failed`, could be one of the following:
-    /// - `'aborted'` - An operation was aborted (due to user action)
-    /// - `'accessdenied'` - Permission to access a resource, other than the network, was denied
-    /// - `'addressunreachable'` - The IP address is unreachable. This usually means that there is no route to the specified
-    ///  host or network.
-    /// - `'blockedbyclient'` - The client chose to block the request.
-    /// - `'blockedbyresponse'` - The request failed because the response was delivered along with requirements which are not
-    ///  met ('X-Frame-Options' and 'Content-Security-Policy' ancestor checks, for instance).
-    /// - `'connectionaborted'` - A connection timed out as a result of not receiving an ACK for data sent.
-    /// - `'connectionclosed'` - A connection was closed (corresponding to a TCP FIN).
-    /// - `'connectionfailed'` - A connection attempt failed.
-    /// - `'connectionrefused'` - A connection attempt was refused.
-    /// - `'connectionreset'` - A connection was reset (corresponding to a TCP RST).
-    /// - `'internetdisconnected'` - The Internet connection has been lost.
-    /// - `'namenotresolved'` - The host name could not be resolved.
-    /// - `'timedout'` - An operation timed out.
-    /// - `'failed'` - A generic failure occurred.
-    pub async fn abort(&self, err_code: Option<&str>) -> Result<(), Arc<Error>> {
+    /// Optional error code. Defaults to [`AbortErrorCode::Failed`]; see [`AbortErrorCode`] for the failures that can be
+    /// simulated.
+    pub async fn abort(&self, err_code: Option<AbortErrorCode>) -> Result<(), Arc<Error>> {
         let inner = upgrade(&self.inner)?;
         inner.abort(err_code).await
     }
@@ -69,12 +53,9 @@ impl Route {
     ///    body: 'Not Found!'
     ///  });
     /// });
-    pub async fn fulfill_builder<'a>(
-        &self,
-        body: &'a str,
-        is_base64: bool,
-    ) -> FulfillBuilder<'a, '_> {
-        FulfillBuilder::new(self.inner.clone(), body, is_base64)
+    /// ```
+    pub fn fulfill_builder<'a, 'b>(&self) -> FulfillBuilder<'a, 'b> {
+        FulfillBuilder::new(self.inner.clone())
     }
 
     /// Continues route's request with optional overrides.
@@ -90,19 +71,30 @@ impl Route {
     ///  route.continue({headers});
     /// });
     /// ```
-    pub async fn continue_builder(&self) -> ContinueBuilder<'_, '_, '_> {
+    pub fn continue_builder<'a, 'b, 'c>(&self) -> ContinueBuilder<'a, 'b, 'c> {
         ContinueBuilder::new(self.inner.clone())
     }
 }
 
+pub(crate) fn handler<F, Fut>(f: F) -> RouteHandler
+where
+    F: Fn(Route, Request) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static
+{
+    Arc::new(move |route| {
+        let request = Request::new(weak_and_then(&route, |r| r.request()));
+        Box::pin(f(Route::new(route), request))
+    })
+}
+
 pub struct FulfillBuilder<'a, 'b> {
     inner: Weak<Impl>,
     args: FulfillArgs<'a, 'b>,
 }
 
 impl<'a, 'b> FulfillBuilder<'a, 'b> {
-    pub(crate) fn new(inner: Weak<Impl>, body: &'a str, is_base64: bool) -> Self {
-        let args = FulfillArgs::new(body, is_base64);
+    pub(crate) fn new(inner: Weak<Impl>) -> Self {
+        let args = FulfillArgs::default();
         Self { inner, args }
     }
 
@@ -121,8 +113,15 @@ impl<'a, 'b> FulfillBuilder<'a, 'b> {
     }
 
     setter! {
+        /// Response body.
+        body: Option<&'a str>,
         /// If set, equals to setting `Content-Type` response header.
         content_type: Option<&'b str>,
+        /// Whether `body` is base64 encoded binary data. Defaults to `false`.
+        is_base64: Option<bool>,
+        /// File path to respond with. The content type will be inferred from file extension unless `content_type` is
+        /// set. Takes precedence over `body`.
+        path: Option<PathBuf>,
         /// Response status code, defaults to `200`.
         status: Option<i32>
     }
@@ -182,12 +181,14 @@ mod tests {
     #[test]
     fn test_fulfill_builder_headers_and_setters() {
         let inner: Weak<Impl> = Weak::new();
-        let b = FulfillBuilder::new(inner, "hello", false)
+        let b = FulfillBuilder::new(inner)
+            .body("hello")
             .headers(vec![("foo".to_string(), "bar".to_string())])
             .content_type("text/plain")
             .status(404);
 
         // Verify args captured by builder (fields with pub(crate) visibility)
+        assert_eq!(b.args.body, Some("hello"));
         assert_eq!(b.args.content_type, Some("text/plain"));
         assert_eq!(b.args.status, Some(404));
         assert_eq!(
@@ -223,7 +224,7 @@ mod tests {
     #[tokio::test]
     async fn test_fulfill_and_continue_fail_on_no_inner() {
         let inner: Weak<Impl> = Weak::new();
-        let b = FulfillBuilder::new(inner.clone(), "body", false);
+        let b = FulfillBuilder::new(inner.clone()).body("body");
         // fulfill should error because there's no inner implementation (upgrade fails)
         assert!(b.fulfill().await.is_err());
 
@@ -0,0 +1,104 @@
+use crate::imp::{
+    core::*,
+    prelude::*,
+    tracing::{StartArgs, Tracing as Impl}
+};
+
+/// API for collecting and saving Playwright traces. Playwright traces can be opened in
+/// [Trace Viewer](https://playwright.dev/docs/trace-viewer) after Playwright script runs.
+///
+/// Start recording a trace before performing actions. At the end, stop tracing and save it to a file.
+///
+/// ```js
+/// const browser = await chromium.launch();
+/// const context = await browser.newContext();
+/// await context.tracing.start({ screenshots: true, snapshots: true });
+/// const page = await context.newPage();
+/// await page.goto('https://playwright.dev');
+/// await context.tracing.stop({ path: 'trace.zip' });
+/// ```
+#[derive(Debug, Clone)]
+pub struct Tracing {
+    inner: Weak<Impl>
+}
+
+impl Tracing {
+    pub(crate) fn new(inner: Weak<Impl>) -> Self { Self { inner } }
+
+    /// Start tracing.
+    #[track_caller]
+    pub fn start_builder(&self) -> StartBuilder {
+        let caller = std::panic::Location::caller().file();
+        StartBuilder::new(self.inner.clone(), caller.into())
+    }
+
+    /// Start a new trace chunk. If you'd like to record multiple traces on the same [`BrowserContext`], use
+    /// [`Tracing::start_builder`] once, and then create multiple trace chunks with [`Tracing::start_chunk`] and
+    /// [`Tracing::stop_chunk`].
+    ///
+    /// ```js
+    /// await context.tracing.start({ screenshots: true, snapshots: true });
+    /// const page = await context.newPage();
+    /// await page.goto('https://playwright.dev');
+    ///
+    /// await context.tracing.startChunk();
+    /// await page.click('text=Get Started');
+    /// // Everything between startChunk and stopChunk will be recorded in the trace.
+    /// await context.tracing.stopChunk({ path: 'trace1.zip' });
+    /// ```
+    ///
+    /// `name` is the prefix of the intermediate trace files kept by the browser, and defaults to the name given to
+    /// [`StartBuilder::name`].
+    ///
+    /// [`BrowserContext`]: crate::api::BrowserContext
+    pub async fn start_chunk(&self, name: Option<&str>) -> ArcResult<()> {
+        upgrade(&self.inner)?.start_chunk(name).await
+    }
+
+    /// Stop the trace chunk. The chunk is exported into the file at `path`, or discarded when `path` is `None`.
+    /// Tracing goes on until [`Tracing::stop`], so another chunk can be started.
+    pub async fn stop_chunk(&self, path: Option<&Path>) -> ArcResult<()> {
+        upgrade(&self.inner)?.stop_chunk(path).await
+    }
+
+    /// Stop tracing. The trace is exported into the file at `path`, or discarded when `path` is `None`.
+    pub async fn stop(&self, path: Option<&Path>) -> ArcResult<()> {
+        upgrade(&self.inner)?.stop(path).await
+    }
+}
+
+pub struct StartBuilder {
+    inner: Weak<Impl>,
+    args: StartArgs
+}
+
+impl StartBuilder {
+    fn new(inner: Weak<Impl>, caller: PathBuf) -> Self {
+        let args = StartArgs {
+            caller: Some(caller),
+            ..StartArgs::default()
+        };
+        Self { inner, args }
+    }
+
+    pub async fn start(self) -> ArcResult<()> {
+        let Self { inner, args } = self;
+        upgrade(&inner)?.start(args).await
+    }
+
+    setter! {
+        /// Prefix of the intermediate trace files kept by the browser while tracing. The exported archive is written
+        /// wherever [`Tracing::stop`] or [`Tracing::stop_chunk`] is told to.
+        name: Option<String>,
+        /// Whether to capture screenshots during tracing. Screenshots are used to build a timeline preview.
+        screenshots: Option<bool>,
+        /// If this option is true tracing will
+        /// - capture DOM snapshot on every action
+        /// - record network activity
+        snapshots: Option<bool>,
+        /// Whether to include source files in the trace. Rust call stacks can't be attached to actions, so this adds the
+        /// source file that called [`Tracing::start_builder`] under `sources/` in the archive, where it is readable as long
+        /// as the program runs from the directory it was compiled in.
+        sources: Option<bool>
+    }
+}
@@ -8,13 +8,16 @@ pub struct Video {
 impl Video {
     pub(crate) fn new(inner: Impl) -> Self { Self { inner } }
 
+    /// Path where the video is being recorded, inside the `record_video` directory of the context. The file is only
+    /// complete once the page or its context is closed.
     pub fn path(&self) -> Result<PathBuf, Error> { self.inner.path() }
 
-    // doesn't work with this version
-    async fn save_as<P: AsRef<Path>>(&self, path: P) -> ArcResult<()> {
+    /// Saves the video to a user-specified path. It is safe to call this method while the video is still in progress,
+    /// or after the page has closed; it waits for the recording to finish.
+    pub async fn save_as<P: AsRef<Path>>(&self, path: P) -> ArcResult<()> {
         self.inner.save_as(path).await
     }
 
-    // doesn't work with this version
-    async fn delete(&self) -> ArcResult<()> { self.inner.delete().await }
+    /// Deletes the video file. Waits for the recording to finish if necessary.
+    pub async fn delete(&self) -> ArcResult<()> { self.inner.delete().await }
 }
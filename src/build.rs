@@ -11,7 +11,13 @@ fn main() {
     let dest = out_dir.join("driver.zip");
     let platform = PlaywrightPlatform::default();
     fs::write(out_dir.join("platform"), platform.to_string()).unwrap();
-    download(&url(platform), &dest);
+    if env::var_os("CARGO_FEATURE_DRIVER_DOWNLOAD").is_some() {
+        // The driver is fetched at runtime by Driver::install instead of being embedded
+        fs::write(out_dir.join("driver_url"), url(platform)).unwrap();
+        let _ = fs::remove_file(&dest);
+    } else {
+        download(&url(platform), &dest);
+    }
     println!("cargo:rerun-if-changed=src/build.rs");
     println!("cargo:rustc-env=SEP={}", MAIN_SEPARATOR);
 }
@@ -100,6 +100,8 @@ pub(crate) mod artifact;
 pub(crate) mod binding_call;
 pub(crate) mod browser;
 pub(crate) mod browser_context;
+pub(crate) mod browser_server;
+pub(crate) mod cdp_session;
 pub(crate) mod console_message;
 pub(crate) mod dialog;
 pub(crate) mod download;
@@ -112,6 +114,7 @@ pub(crate) mod request;
 pub(crate) mod response;
 pub(crate) mod route;
 pub(crate) mod stream;
+pub(crate) mod tracing;
 pub(crate) mod video;
 pub(crate) mod websocket;
 pub(crate) mod worker;
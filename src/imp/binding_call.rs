@@ -1,15 +1,211 @@
-use crate::imp::core::*;
+use crate::imp::{
+    core::*, element_handle::ElementHandle, frame::Frame, js_handle::JsHandle, prelude::*
+};
+use futures::future::BoxFuture;
+use tokio::sync::mpsc;
+
+pub(crate) type BindingCallback =
+    Arc<dyn Fn(Weak<BindingCall>) -> BoxFuture<'static, Result<Value, String>> + Send + Sync>;
 
 #[derive(Debug)]
 pub(crate) struct BindingCall {
-    channel: ChannelOwner
+    channel: ChannelOwner,
+    name: String,
+    frame: Weak<Frame>,
+    args: Value,
+    handle: Option<Handle>
+}
+
+/// Functions exposed by a page or a browser context. The driver can't unregister a name, so removing a function only
+/// drops its callback, and exposing the name again installs a new callback without registering it anew.
+#[derive(Debug, Default)]
+pub(crate) struct Bindings {
+    callbacks: HashMap<String, mpsc::UnboundedSender<Weak<BindingCall>>>,
+    /// `needs_handle` of the names registered with the driver
+    registered: HashMap<String, bool>
+}
+
+impl Bindings {
+    pub(crate) fn set_registered(&mut self, name: &str, needs_handle: bool) {
+        self.registered.insert(name.to_owned(), needs_handle);
+    }
+
+    /// Installs `callback` and returns whether `name` is yet to be registered with the driver. As the driver keeps the
+    /// `needs_handle` it was registered with, exposing the name again with another one fails.
+    pub(crate) fn insert(
+        &mut self,
+        name: &str,
+        needs_handle: bool,
+        callback: BindingCallback
+    ) -> Result<bool, Error> {
+        let registered = self.registered.get(name).copied();
+        if matches!(registered, Some(x) if x != needs_handle) {
+            return Err(Error::BindingNeedsHandle(name.to_owned()));
+        }
+        self.callbacks
+            .insert(name.to_owned(), BindingCall::serve(callback));
+        Ok(registered.is_none())
+    }
+
+    pub(crate) fn remove(&mut self, name: &str) { self.callbacks.remove(name); }
+
+    pub(crate) fn get(&self, name: &str) -> Option<mpsc::UnboundedSender<Weak<BindingCall>>> {
+        self.callbacks.get(name).cloned()
+    }
+}
+
+/// The argument passed by the page when the binding was exposed with `needsHandle`.
+#[derive(Debug, Clone)]
+pub(crate) enum Handle {
+    Js(Weak<JsHandle>),
+    Element(Weak<ElementHandle>)
 }
 
 impl BindingCall {
-    pub(crate) fn new(channel: ChannelOwner) -> Self { Self { channel } }
+    pub(crate) fn try_new(ctx: &Context, channel: ChannelOwner) -> Result<Self, Error> {
+        let Initializer {
+            frame: OnlyGuid { guid },
+            name,
+            args,
+            handle
+        } = serde_json::from_value(channel.initializer.clone())?;
+        let frame = get_object!(ctx, &guid, Frame)?;
+        let handle = match handle {
+            None => None,
+            Some(OnlyGuid { guid }) => match ctx.find_object(&guid) {
+                Some(RemoteWeak::JsHandle(x)) => Some(Handle::Js(x)),
+                Some(RemoteWeak::ElementHandle(x)) => Some(Handle::Element(x)),
+                _ => return Err(Error::ObjectNotFound)
+            }
+        };
+        Ok(Self {
+            channel,
+            name,
+            frame,
+            args,
+            handle
+        })
+    }
+
+    pub(crate) fn name(&self) -> &str { &self.name }
+
+    pub(crate) fn frame(&self) -> Weak<Frame> { self.frame.clone() }
+
+    /// Serialized arguments of the call. Empty when the binding was exposed with `needsHandle`.
+    pub(crate) fn args(&self) -> &Value { &self.args }
+
+    pub(crate) fn handle(&self) -> Option<Handle> { self.handle.clone() }
+
+    pub(crate) async fn resolve(&self, result: Value) -> ArcResult<()> {
+        let mut args = Map::new();
+        args.insert("result".into(), result);
+        let _ = send_message!(self, "resolve", args);
+        Ok(())
+    }
+
+    pub(crate) async fn reject(&self, message: &str) -> ArcResult<()> {
+        #[derive(Serialize)]
+        struct Thrown<'a> {
+            message: &'a str,
+            name: &'a str,
+            stack: &'a str
+        }
+        #[derive(Serialize)]
+        struct SerializedError<'a> {
+            error: Thrown<'a>
+        }
+        #[derive(Serialize)]
+        struct Args<'a> {
+            error: SerializedError<'a>
+        }
+        let args = Args {
+            error: SerializedError {
+                error: Thrown {
+                    message,
+                    name: "Error",
+                    stack: ""
+                }
+            }
+        };
+        let _ = send_message!(self, "reject", args);
+        Ok(())
+    }
+
+    /// Rejects a call to a function whose callback was removed.
+    pub(crate) fn reject_removed(call: Weak<BindingCall>) {
+        spawn(async move {
+            let call = match call.upgrade() {
+                Some(c) => c,
+                None => return
+            };
+            let message = format!("Function \"{}\" has been removed", call.name());
+            if let Err(e) = call.reject(&message).await {
+                log::trace!("Failed to respond to binding call: {:?}", e);
+            }
+        });
+    }
+
+    /// Runs `callback` for every call sent through the returned channel.
+    /// Calls are handled concurrently so that a slow callback doesn't hold back the others.
+    pub(crate) fn serve(callback: BindingCallback) -> mpsc::UnboundedSender<Weak<BindingCall>> {
+        let (tx, mut rx) = mpsc::unbounded_channel::<Weak<BindingCall>>();
+        spawn(async move {
+            while let Some(call) = rx.recv().await {
+                let callback = callback.clone();
+                spawn(async move {
+                    let result = callback(call.clone()).await;
+                    let call = match call.upgrade() {
+                        Some(c) => c,
+                        None => return
+                    };
+                    let sent = match result {
+                        Ok(v) => call.resolve(v).await,
+                        Err(e) => call.reject(&e).await
+                    };
+                    if let Err(e) = sent {
+                        log::trace!("Failed to respond to binding call: {:?}", e);
+                    }
+                });
+            }
+        });
+        tx
+    }
 }
 
 impl RemoteObject for BindingCall {
     fn channel(&self) -> &ChannelOwner { &self.channel }
     fn channel_mut(&mut self) -> &mut ChannelOwner { &mut self.channel }
 }
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Initializer {
+    frame: OnlyGuid,
+    name: String,
+    #[serde(default = "empty_args")]
+    args: Value,
+    handle: Option<OnlyGuid>
+}
+
+fn empty_args() -> Value { Value::Array(Vec::new()) }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::runtime_test!(bindings_keep_needs_handle, {
+        let callback: BindingCallback = Arc::new(|_| Box::pin(async { Ok(Value::Null) }));
+        let mut bindings = Bindings::default();
+        assert!(bindings.insert("f", true, callback.clone()).unwrap());
+        bindings.set_registered("f", true);
+        assert!(!bindings.insert("f", true, callback.clone()).unwrap());
+        assert!(matches!(
+            bindings.insert("f", false, callback.clone()),
+            Err(Error::BindingNeedsHandle(name)) if name == "f"
+        ));
+        bindings.remove("f");
+        assert!(bindings.get("f").is_none());
+        assert!(!bindings.insert("f", true, callback).unwrap());
+        assert!(bindings.get("f").is_some());
+    });
+}
@@ -2,9 +2,18 @@ use crate::imp::{
     browser_context::BrowserContext,
     browser_type::{RecordHar, RecordVideo},
     core::*,
+    page::Page,
     prelude::*,
-    utils::{ColorScheme, Geolocation, HttpCredentials, ProxySettings, StorageState, Viewport},
+    utils::{
+        serialize_extra_http_headers, ColorScheme, Geolocation, HttpCredentials, ProxySettings,
+        StorageState, Viewport
+    }
 };
+use futures::future::BoxFuture;
+use tokio::sync::mpsc;
+
+pub(crate) type ContextHandler =
+    Arc<dyn Fn(Weak<BrowserContext>) -> BoxFuture<'static, ()> + Send + Sync>;
 
 #[derive(Debug)]
 pub(crate) struct Browser {
@@ -17,6 +26,7 @@ pub(crate) struct Browser {
 pub(crate) struct Variable {
     contexts: Vec<Weak<BrowserContext>>,
     is_remote: bool,
+    context_handlers: Vec<mpsc::UnboundedSender<Weak<BrowserContext>>>
 }
 
 impl Browser {
@@ -25,24 +35,25 @@ impl Browser {
         Ok(Self {
             channel,
             version,
-            var: Mutex::new(Variable {
-                contexts: Vec::new(),
-                is_remote: false,
-            }),
+            var: Mutex::default()
         })
     }
     pub(crate) fn version(&self) -> &str {
         &self.version
     }
 
+    /// Name of the browser type that launched this browser, e.g. `chromium`.
+    pub(crate) fn browser_name(&self) -> Option<String> {
+        match &self.channel.parent {
+            Some(RemoteWeak::BrowserType(t)) => Some(t.upgrade()?.name().to_owned()),
+            _ => None
+        }
+    }
+
     pub(crate) async fn close(&self) -> Result<(), Arc<Error>> {
         let _ = send_message!(self, "close", Map::new());
         Ok(())
     }
-
-    // Responds newtype `OwnerPage` of `SinglePageBrowserContext`.
-    // There are different behavior in BrowserContext::new_page
-    // async fn new_page(
 }
 
 // mutable
@@ -70,23 +81,62 @@ impl Browser {
 
     pub(crate) async fn new_context(
         &self,
-        args: NewContextArgs<'_, '_, '_, '_, '_, '_, '_>,
+        mut args: NewContextArgs<'_, '_, '_, '_, '_, '_, '_>
     ) -> Result<Weak<BrowserContext>, Arc<Error>> {
+        if let Some(g) = &args.geolocation {
+            g.validate()?;
+        }
+        if let Some(path) = args.storage_state_path.take() {
+            let json = std::fs::read(&path).map_err(Error::from)?;
+            args.storage_state = Some(serde_json::from_slice(&json).map_err(Error::Serde)?);
+        }
+        let har_path = args.record_har.as_ref().map(|h| h.path.to_owned());
         let res = send_message!(self, "newContext", args);
         let guid = only_guid(&res)?;
         let c = get_object!(self.context()?.lock().unwrap(), guid, BrowserContext)?;
+        upgrade(&c)?.set_har_path(har_path);
         self.register_new_context(c.clone())?;
+        upgrade(&c)?.update_subscription("console", true).await?;
         Ok(c)
     }
 
+    /// Creates a page in a context of its own, which is closed together with the page.
+    pub(crate) async fn new_page(
+        &self,
+        args: NewContextArgs<'_, '_, '_, '_, '_, '_, '_>
+    ) -> Result<Weak<Page>, Arc<Error>> {
+        let c = self.new_context(args).await?;
+        let p = upgrade(&c)?.new_page().await?;
+        upgrade(&p)?.set_owns_context();
+        Ok(p)
+    }
+
+    /// Contexts are announced both by the `context` event and by the response to `newContext`, so the second one is
+    /// ignored.
     fn register_new_context(&self, c: Weak<BrowserContext>) -> Result<(), Arc<Error>> {
-        self.push_context(c);
+        let var = &mut self.var.lock().unwrap();
+        if var.contexts.iter().any(|x| x.ptr_eq(&c)) {
+            return Ok(());
+        }
+        var.contexts.push(c.clone());
+        var.context_handlers.retain(|tx| tx.send(c.clone()).is_ok());
         // TODO: options
         // let this = get_object!(self.context()?.lock().unwrap(), &self.guid(), Browser)?;
         // let bc = upgrade(&c)?;
         // bc._options = params
         Ok(())
     }
+
+    /// Runs `handler` on a task of its own for every context created from now on.
+    pub(crate) fn add_context_handler(&self, handler: ContextHandler) {
+        let (tx, mut rx) = mpsc::unbounded_channel::<Weak<BrowserContext>>();
+        spawn(async move {
+            while let Some(c) = rx.recv().await {
+                spawn(handler(c));
+            }
+        });
+        self.var.lock().unwrap().context_handlers.push(tx);
+    }
 }
 
 impl RemoteObject for Browser {
@@ -96,6 +146,21 @@ impl RemoteObject for Browser {
     fn channel_mut(&mut self) -> &mut ChannelOwner {
         &mut self.channel
     }
+
+    fn handle_event(
+        &self,
+        ctx: &Context,
+        method: Str<Method>,
+        params: Map<String, Value>
+    ) -> Result<(), Error> {
+        if method.as_str() == "context" {
+            let first = first_object(&params).ok_or(Error::InvalidParams)?;
+            let OnlyGuid { guid } = serde_json::from_value((*first).clone())?;
+            let c = get_object!(ctx, &guid, BrowserContext)?;
+            self.register_new_context(c).map_err(Error::from)?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -127,7 +192,10 @@ pub(crate) struct NewContextArgs<'e, 'f, 'g, 'h, 'i, 'j, 'k> {
     pub(crate) timezone_id: Option<&'g str>,
     pub(crate) geolocation: Option<Geolocation>,
     pub(crate) permissions: Option<&'h [String]>,
-    #[serde(rename = "extraHTTPHeaders")]
+    #[serde(
+        rename = "extraHTTPHeaders",
+        serialize_with = "serialize_extra_http_headers"
+    )]
     pub(crate) extra_http_headers: Option<HashMap<String, String>>,
     pub(crate) offline: Option<bool>,
     pub(crate) http_credentials: Option<&'i HttpCredentials>,
@@ -141,6 +209,9 @@ pub(crate) struct NewContextArgs<'e, 'f, 'g, 'h, 'i, 'j, 'k> {
     pub(crate) record_har: Option<RecordHar<'k>>,
 
     pub(crate) storage_state: Option<StorageState>,
+    /// JSON file written by `storage_state`, read in place of `storage_state`
+    #[serde(skip)]
+    pub(crate) storage_state_path: Option<PathBuf>
 }
 
 impl<'e, 'f, 'g, 'h, 'i, 'j, 'k> Default for NewContextArgs<'e, 'f, 'g, 'h, 'i, 'j, 'k> {
@@ -172,6 +243,7 @@ impl<'e, 'f, 'g, 'h, 'i, 'j, 'k> Default for NewContextArgs<'e, 'f, 'g, 'h, 'i,
             record_video: None,
             record_har: None,
             storage_state: None,
+            storage_state_path: None
         }
     }
 }
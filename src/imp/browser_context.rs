@@ -1,14 +1,22 @@
 use crate::imp::{
+    binding_call::{BindingCall, BindingCallback, Bindings},
     browser::Browser,
+    cdp_session::CdpSession,
+    console_message::ConsoleMessage,
     core::*,
-    page::Page,
+    dialog::Dialog,
+    page::{self, Page},
     prelude::*,
-    utils::{Cookie, Geolocation, Header, StorageState},
+    route::{self, Route, RouteEntry, RouteHandler},
+    tracing::Tracing,
+    utils::{extra_http_headers, Cookie, Geolocation, Header, StorageState, UrlMatcher},
+    worker::Worker
 };
 
 #[derive(Debug)]
 pub(crate) struct BrowserContext {
     channel: ChannelOwner,
+    tracing: Weak<Tracing>,
     var: Mutex<Variable>,
     tx: Mutex<Option<broadcast::Sender<Evt>>>,
 }
@@ -17,15 +25,24 @@ pub(crate) struct BrowserContext {
 pub(crate) struct Variable {
     browser: Option<Weak<Browser>>,
     pages: Vec<Weak<Page>>,
+    background_pages: Vec<Weak<Page>>,
+    service_workers: Vec<Weak<Worker>>,
     timeout: Option<u32>,
     navigation_timeout: Option<u32>,
+    routes: Vec<RouteEntry>,
+    bindings: Bindings,
+    har_path: Option<PathBuf>,
+    download_path: Option<PathBuf>
 }
 
 impl BrowserContext {
     const DEFAULT_TIMEOUT: u32 = 30000;
 
-    pub(crate) fn try_new(channel: ChannelOwner) -> Result<Self, Error> {
-        let Initializer {} = serde_json::from_value(channel.initializer.clone())?;
+    pub(crate) fn try_new(ctx: &Context, channel: ChannelOwner) -> Result<Self, Error> {
+        let Initializer {
+            tracing: OnlyGuid { guid }
+        } = serde_json::from_value(channel.initializer.clone())?;
+        let tracing = get_object!(ctx, &guid, Tracing)?;
         let browser = match &channel.parent {
             Some(RemoteWeak::Browser(b)) => Some(b.clone()),
             _ => None,
@@ -36,6 +53,7 @@ impl BrowserContext {
         });
         Ok(Self {
             channel,
+            tracing,
             var,
             tx: Mutex::default(),
         })
@@ -48,14 +66,65 @@ impl BrowserContext {
         Ok(p)
     }
 
+    pub(crate) async fn new_cdp_session(&self, page: &Page) -> ArcResult<Weak<CdpSession>> {
+        #[derive(Serialize)]
+        struct Args {
+            page: OnlyGuid
+        }
+        let args = Args {
+            page: OnlyGuid {
+                guid: page.guid().to_owned()
+            }
+        };
+        let res = send_message!(self, "newCDPSession", args);
+        let guid = only_guid(&res)?;
+        let s = get_object!(self.context()?.lock().unwrap(), guid, CdpSession)?;
+        Ok(s)
+    }
+
     pub(crate) async fn close(&self) -> Result<(), Arc<Error>> {
+        let har_path = self.var.lock().unwrap().har_path.take();
+        if let Some(path) = har_path {
+            self.export_har(&path).await?;
+        }
         let _ = send_message!(self, "close", Map::new());
         Ok(())
     }
 
-    pub(crate) async fn storage_state(&self) -> ArcResult<StorageState> {
+    /// The HAR recorded with the `recordHar` option is only available as an artifact until the context is closed.
+    async fn export_har(&self, path: &Path) -> ArcResult<()> {
+        let res = send_message!(self, "harExport", Map::new());
+        let guid = only_guid(&res)?;
+        let artifact = get_object!(self.context()?.lock().unwrap(), guid, Artifact)?;
+        let artifact = upgrade(&artifact)?;
+        artifact.save_as(path).await?;
+        artifact.delete().await?;
+        Ok(())
+    }
+
+    pub(crate) fn download_path(&self) -> Option<PathBuf> {
+        self.var.lock().unwrap().download_path.clone()
+    }
+
+    pub(crate) fn set_download_path(&self, path: Option<PathBuf>) {
+        self.var.lock().unwrap().download_path = path;
+    }
+
+    pub(crate) fn set_har_path(&self, path: Option<PathBuf>) {
+        self.var.lock().unwrap().har_path = path;
+    }
+
+    /// Also writes the state as JSON to `path` when given, creating its parent directories as needed.
+    pub(crate) async fn storage_state(&self, path: Option<&Path>) -> ArcResult<StorageState> {
         let v = send_message!(self, "storageState", Map::new());
         let s = serde_json::from_value((*v).clone()).map_err(Error::Serde)?;
+        if let Some(path) = path {
+            let json = serde_json::to_vec_pretty(&s).map_err(Error::Serde)?;
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir).map_err(Error::from)?;
+            }
+            std::fs::write(path, json).map_err(Error::from)?;
+        }
         Ok(s)
     }
 
@@ -64,6 +133,15 @@ impl BrowserContext {
         Ok(())
     }
 
+    pub(crate) async fn delete_cookies(&self, name: &str) -> ArcResult<()> {
+        #[derive(Serialize)]
+        struct Args<'a> {
+            name: &'a str
+        }
+        let _ = send_message!(self, "clearCookies", Args { name });
+        Ok(())
+    }
+
     pub(crate) async fn cookies(&self, urls: &[String]) -> ArcResult<Vec<Cookie>> {
         #[derive(Serialize)]
         #[serde(rename_all = "camelCase")]
@@ -78,6 +156,9 @@ impl BrowserContext {
     }
 
     pub(crate) async fn add_cookies(&self, cookies: &[Cookie]) -> ArcResult<()> {
+        for c in cookies {
+            c.validate()?;
+        }
         #[derive(Serialize)]
         #[serde(rename_all = "camelCase")]
         struct Args<'a> {
@@ -120,6 +201,9 @@ impl BrowserContext {
         struct Args<'a> {
             geolocation: Option<&'a Geolocation>,
         }
+        if let Some(g) = geolocation {
+            g.validate()?;
+        }
         let args = Args { geolocation };
         let _ = send_message!(self, "setGeolocation", args);
         Ok(())
@@ -149,16 +233,79 @@ impl BrowserContext {
             headers: Vec<Header>,
         }
         let args = Args {
-            headers: headers.into_iter().map(Header::from).collect(),
+            headers: extra_http_headers(headers)
         };
         let _ = send_message!(self, "setExtraHTTPHeaders", args);
         Ok(())
     }
 
-    // async def expose_binding(
-    // async def expose_function(self, name: str, callback: Callable) -> None:
-    // async def route(self, url: URLMatch, handler: RouteHandler) -> None:
-    // async def unroute(
+    /// The driver only dispatches some events, such as `console`, once they are subscribed to.
+    pub(crate) async fn update_subscription(&self, event: &str, enabled: bool) -> ArcResult<()> {
+        let mut args = Map::new();
+        args.insert("event".into(), event.into());
+        args.insert("enabled".into(), enabled.into());
+        let _ = send_message!(self, "updateSubscription", args);
+        Ok(())
+    }
+
+    /// The handler is only kept once the driver has taken the new patterns.
+    pub(crate) async fn route(&self, matcher: UrlMatcher, handler: RouteHandler) -> ArcResult<()> {
+        let entry = RouteEntry { matcher, handler };
+        let patterns = {
+            let mut routes = self.var.lock().unwrap().routes.clone();
+            routes.push(entry.clone());
+            route::interception_patterns(&routes)
+        };
+        let _ = send_message!(self, "setNetworkInterceptionPatterns", patterns);
+        self.var.lock().unwrap().routes.push(entry);
+        Ok(())
+    }
+
+    /// Removes every handler registered for `matcher`.
+    pub(crate) async fn unroute(&self, matcher: &UrlMatcher) -> ArcResult<()> {
+        let patterns = {
+            let routes = &mut self.var.lock().unwrap().routes;
+            routes.retain(|r| &r.matcher != matcher);
+            route::interception_patterns(routes)
+        };
+        let _ = send_message!(self, "setNetworkInterceptionPatterns", patterns);
+        Ok(())
+    }
+
+    pub(crate) async fn expose_binding(
+        &self,
+        name: &str,
+        needs_handle: bool,
+        callback: BindingCallback
+    ) -> ArcResult<()> {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Args<'a> {
+            name: &'a str,
+            needs_handle: bool
+        }
+        let unregistered =
+            self.var
+                .lock()
+                .unwrap()
+                .bindings
+                .insert(name, needs_handle, callback)?;
+        if unregistered {
+            let args = Args { name, needs_handle };
+            let _ = send_message!(self, "exposeBinding", args);
+            self.var
+                .lock()
+                .unwrap()
+                .bindings
+                .set_registered(name, needs_handle);
+        }
+        Ok(())
+    }
+
+    /// See `Page::remove_exposed_binding`.
+    pub(crate) fn remove_exposed_binding(&self, name: &str) {
+        self.var.lock().unwrap().bindings.remove(name);
+    }
 
     // async fn pause(&self) -> ArcResult<()> {
     //    let _ = send_message!(self, "pause", Map::new());
@@ -176,6 +323,17 @@ impl BrowserContext {
         self.var.lock().unwrap().browser = Some(browser);
     }
 
+    /// Persistent contexts are children of their browser type rather than of a browser.
+    pub(crate) fn browser_name(&self) -> Option<String> {
+        match &self.channel.parent {
+            Some(RemoteWeak::Browser(b)) => b.upgrade()?.browser_name(),
+            Some(RemoteWeak::BrowserType(t)) => Some(t.upgrade()?.name().to_owned()),
+            _ => None
+        }
+    }
+
+    pub(crate) fn tracing(&self) -> Weak<Tracing> { self.tracing.clone() }
+
     pub(crate) fn pages(&self) -> Vec<Weak<Page>> {
         self.var.lock().unwrap().pages.clone()
     }
@@ -185,8 +343,22 @@ impl BrowserContext {
     }
 
     pub(super) fn remove_page(&self, page: &Weak<Page>) {
-        let pages = &mut self.var.lock().unwrap().pages;
-        pages.remove_one(|p| p.ptr_eq(page));
+        let var = &mut self.var.lock().unwrap();
+        var.pages.remove_one(|p| p.ptr_eq(page));
+        var.background_pages.remove_one(|p| p.ptr_eq(page));
+    }
+
+    pub(crate) fn background_pages(&self) -> Vec<Weak<Page>> {
+        self.var.lock().unwrap().background_pages.clone()
+    }
+
+    pub(crate) fn service_workers(&self) -> Vec<Weak<Worker>> {
+        self.var.lock().unwrap().service_workers.clone()
+    }
+
+    pub(crate) fn remove_service_worker(&self, worker: &Weak<Worker>) {
+        let workers = &mut self.var.lock().unwrap().service_workers;
+        workers.remove_one(|w| w.ptr_eq(worker));
     }
 
     pub(crate) fn default_timeout(&self) -> u32 {
@@ -197,12 +369,10 @@ impl BrowserContext {
             .unwrap_or(Self::DEFAULT_TIMEOUT)
     }
 
+    /// Falls back to the default timeout, as the driver does.
     pub(crate) fn default_navigation_timeout(&self) -> u32 {
-        self.var
-            .lock()
-            .unwrap()
-            .navigation_timeout
-            .unwrap_or(Self::DEFAULT_TIMEOUT)
+        let this = self.var.lock().unwrap().navigation_timeout;
+        this.unwrap_or_else(|| self.default_timeout())
     }
 
     pub(crate) async fn set_default_timeout(&self, timeout: u32) -> ArcResult<()> {
@@ -232,8 +402,57 @@ impl BrowserContext {
         Ok(())
     }
 
-    fn on_route(&self, _ctx: &Context, _parmas: Map<String, Value>) -> Result<(), Error> {
-        // TODO: noimplemented
+    fn on_console(&self, ctx: &Context, params: Map<String, Value>) -> Result<(), Error> {
+        let console = ConsoleMessage::try_new(ctx, self.channel().ctx.clone(), params)?;
+        if let Some(p) = console.page().and_then(|p| p.upgrade()) {
+            p.emit_event(page::Evt::Console(Arc::new(console)));
+        }
+        Ok(())
+    }
+
+    fn on_dialog(&self, ctx: &Context, params: Map<String, Value>) -> Result<(), Error> {
+        let first = first_object(&params).ok_or(Error::InvalidParams)?;
+        let OnlyGuid { guid } = serde_json::from_value((*first).clone())?;
+        let dialog = get_object!(ctx, &guid, Dialog)?;
+        match upgrade(&dialog)?.page().and_then(|p| p.upgrade()) {
+            Some(p) => p.on_dialog(dialog),
+            None => Dialog::close_unhandled(dialog)
+        }
+        Ok(())
+    }
+
+    fn on_binding(&self, ctx: &Context, params: Map<String, Value>) -> Result<(), Error> {
+        let first = first_object(&params).ok_or(Error::InvalidParams)?;
+        let OnlyGuid { guid } = serde_json::from_value((*first).clone())?;
+        let call = get_object!(ctx, &guid, BindingCall)?;
+        self.handle_binding(call)
+    }
+
+    /// Also reached from pages that expose no function of that name.
+    pub(crate) fn handle_binding(&self, call: Weak<BindingCall>) -> Result<(), Error> {
+        let name = upgrade(&call)?.name().to_owned();
+        let tx = self.var.lock().unwrap().bindings.get(&name);
+        match tx {
+            Some(tx) => {
+                let _ = tx.send(call);
+            }
+            None => BindingCall::reject_removed(call)
+        }
+        Ok(())
+    }
+
+    fn on_route(&self, ctx: &Context, params: Map<String, Value>) -> Result<(), Error> {
+        let first = first_object(&params).ok_or(Error::InvalidParams)?;
+        let OnlyGuid { guid } = serde_json::from_value((*first).clone())?;
+        let route = get_object!(ctx, &guid, Route)?;
+        self.handle_route(route)
+    }
+
+    /// Also reached from pages whose own handlers don't match the request.
+    pub(crate) fn handle_route(&self, route: Weak<Route>) -> Result<(), Error> {
+        let request = upgrade(&upgrade(&route)?.request())?;
+        let handler = route::find_handler(&self.var.lock().unwrap().routes, request.url());
+        Route::handle(route, handler);
         Ok(())
     }
 }
@@ -260,8 +479,26 @@ impl RemoteObject for BrowserContext {
                 self.push_page(p.clone());
                 self.emit_event(Evt::Page(p));
             }
+            "backgroundPage" => {
+                let first = first_object(&params).ok_or(Error::InvalidParams)?;
+                let OnlyGuid { guid } = serde_json::from_value((*first).clone())?;
+                let p = get_object!(ctx, &guid, Page)?;
+                self.var.lock().unwrap().background_pages.push(p.clone());
+                self.emit_event(Evt::BackgroundPage(p));
+            }
+            "serviceWorker" => {
+                let first = first_object(&params).ok_or(Error::InvalidParams)?;
+                let OnlyGuid { guid } = serde_json::from_value((*first).clone())?;
+                let w = get_object!(ctx, &guid, Worker)?;
+                let this = get_object!(ctx, self.guid(), BrowserContext)?;
+                upgrade(&w)?.set_browser_context(this);
+                self.var.lock().unwrap().service_workers.push(w.clone());
+                self.emit_event(Evt::ServiceWorker(w));
+            }
             "close" => self.on_close(ctx)?,
-            "bindingCall" => {}
+            "console" => self.on_console(ctx, params)?,
+            "dialog" => self.on_dialog(ctx, params)?,
+            "bindingCall" => self.on_binding(ctx, params)?,
             "route" => self.on_route(ctx, params)?,
             _ => {}
         }
@@ -271,8 +508,10 @@ impl RemoteObject for BrowserContext {
 
 #[derive(Debug, Clone)]
 pub(crate) enum Evt {
+    BackgroundPage(Weak<Page>),
     Close,
     Page(Weak<Page>),
+    ServiceWorker(Weak<Worker>)
 }
 
 impl EventEmitter for BrowserContext {
@@ -289,8 +528,10 @@ impl EventEmitter for BrowserContext {
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum EventType {
+    BackgroundPage,
     Close,
     Page,
+    ServiceWorker
 }
 
 impl IsEvent for Evt {
@@ -298,15 +539,19 @@ impl IsEvent for Evt {
 
     fn event_type(&self) -> Self::EventType {
         match self {
+            Self::BackgroundPage(_) => EventType::BackgroundPage,
             Self::Close => EventType::Close,
             Self::Page(_) => EventType::Page,
+            Self::ServiceWorker(_) => EventType::ServiceWorker
         }
     }
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct Initializer {}
+struct Initializer {
+    tracing: OnlyGuid
+}
 
 #[cfg(test)]
 mod tests {
@@ -323,7 +568,7 @@ mod tests {
         let b = b.upgrade().unwrap();
         let c = b.new_context(NewContextArgs::default()).await.unwrap();
         let c = c.upgrade().unwrap();
-        c.storage_state().await.unwrap();
+        c.storage_state(None).await.unwrap();
         c.cookies(&[]).await.unwrap();
         // Note: set_default_timeout is incompatible with Playwright 1.57.0 driver (setDefaultTimeoutNoReply not supported)
         // c.set_default_timeout(30000).await.unwrap();
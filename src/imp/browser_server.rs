@@ -0,0 +1,207 @@
+use crate::imp::{browser_type::LaunchServerArgs, core::*, prelude::*};
+use futures::{channel::oneshot, future};
+use std::{
+    fs, io,
+    io::{BufRead, BufReader},
+    process::{self, Child, Command, Stdio},
+    sync::atomic::{AtomicUsize, Ordering},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant}
+};
+
+/// How long the driver gets to close its browsers before it is killed.
+const GRACE_PERIOD: Duration = Duration::from_secs(30);
+
+/// A browser hosted by its own driver process, listening on a websocket endpoint.
+#[derive(Debug)]
+pub(crate) struct BrowserServer {
+    child: Mutex<Option<Child>>,
+    ws_endpoint: String
+}
+
+impl BrowserServer {
+    pub(crate) async fn launch(
+        driver: &Driver,
+        browser: &str,
+        args: LaunchServerArgs<'_, '_>
+    ) -> ArcResult<Self> {
+        let timeout = args.timeout.unwrap_or_default() as u64;
+        static CONFIGS: AtomicUsize = AtomicUsize::new(0);
+        let config = std::env::temp_dir().join(format!(
+            "playwright-rust-server-{}-{}.json",
+            process::id(),
+            CONFIGS.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::write(&config, serde_json::to_vec(&args).map_err(Error::Serde)?).map_err(Error::Io)?;
+        let spawned = Command::new(driver.executable())
+            .arg(driver.cli_script())
+            .args(["launch-server", "--browser", browser, "--config"])
+            .arg(&config)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn();
+        let mut child = match spawned {
+            Ok(x) => x,
+            Err(e) => {
+                fs::remove_file(&config).ok();
+                return Err(Error::Io(e).into());
+            }
+        };
+        let stderr = Arc::new(DriverStderr::default());
+        let stderr_reader = stderr.read(child.stderr.take().unwrap(), driver.stderr_handler());
+        let stdout = child.stdout.take().unwrap();
+        let (tx, rx) = oneshot::channel();
+        std::thread::spawn(move || {
+            let mut lines = BufReader::new(stdout).lines().map_while(Result::ok);
+            let endpoint = lines.by_ref().find(|l| l.starts_with("ws://"));
+            tx.send(endpoint).ok();
+            // Keep reading so that the server never blocks on a full pipe
+            lines.for_each(drop);
+        });
+        // The driver gives up launching the browser after `timeout` too, but it may hang before even trying
+        let endpoint = match timeout {
+            0 => rx.await.ok().flatten().ok_or(Error::BrowserServerExited),
+            _ => match future::select(rx, Box::pin(sleep(Duration::from_millis(timeout)))).await {
+                future::Either::Left((endpoint, _)) => {
+                    endpoint.ok().flatten().ok_or(Error::BrowserServerExited)
+                }
+                future::Either::Right(_) => Err(Error::Timeout)
+            }
+        };
+        fs::remove_file(&config).ok();
+        let ws_endpoint = match endpoint {
+            Ok(x) => x,
+            Err(e) => return Err(startup_error(child, &stderr, stderr_reader, e).into())
+        };
+        Ok(Self {
+            child: Mutex::new(Some(child)),
+            ws_endpoint
+        })
+    }
+
+    pub(crate) fn ws_endpoint(&self) -> &str { &self.ws_endpoint }
+
+    /// Asks the driver to close its browsers and waits for it to exit, killing it only if that takes longer than
+    /// [`GRACE_PERIOD`].
+    pub(crate) async fn close(&self) -> Result<(), Error> {
+        let child = match self.child.lock().unwrap().take() {
+            Some(x) => x,
+            None => return Ok(())
+        };
+        let (tx, rx) = oneshot::channel();
+        thread::spawn(move || {
+            tx.send(shut_down(child)).ok();
+        });
+        rx.await.map_err(|_| Error::BrowserServerExited)??;
+        Ok(())
+    }
+}
+
+impl Drop for BrowserServer {
+    fn drop(&mut self) {
+        if let Some(child) = self.child.lock().unwrap().take() {
+            thread::spawn(move || shut_down(child).ok());
+        }
+    }
+}
+
+/// Stops the server and turns `e` into [`Error::DriverStartup`] once all of its stderr has been read, like
+/// `Connection::startup_error` does for the driver. Errors are kept as they are when the server wrote nothing.
+fn startup_error(
+    mut child: Child,
+    stderr: &DriverStderr,
+    stderr_reader: JoinHandle<()>,
+    e: Error
+) -> Error {
+    child.kill().ok();
+    child.wait().ok();
+    stderr_reader.join().ok();
+    let stderr = stderr.text();
+    if stderr.is_empty() {
+        return e;
+    }
+    Error::DriverStartup {
+        message: e.to_string(),
+        stderr
+    }
+}
+
+fn shut_down(mut child: Child) -> io::Result<()> {
+    if child.try_wait()?.is_some() {
+        return Ok(());
+    }
+    terminate(&mut child)?;
+    let deadline = Instant::now() + GRACE_PERIOD;
+    while child.try_wait()?.is_none() {
+        if Instant::now() >= deadline {
+            child.kill()?;
+            child.wait()?;
+            break;
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+    Ok(())
+}
+
+/// Sends SIGTERM, which the driver's `launch-server` handles by closing its browsers before exiting.
+#[cfg(unix)]
+fn terminate(child: &mut Child) -> io::Result<()> {
+    match unsafe { libc::kill(child.id() as libc::pid_t, libc::SIGTERM) } {
+        0 => Ok(()),
+        _ => Err(io::Error::last_os_error())
+    }
+}
+
+/// Windows has no signal the driver could handle, so it is terminated right away.
+#[cfg(not(unix))]
+fn terminate(child: &mut Child) -> io::Result<()> { child.kill() }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    async fn launch_with(name: &str, script: &str, timeout: f64) -> Error {
+        use std::os::unix::fs::PermissionsExt;
+        let dir =
+            std::env::temp_dir().join(format!("playwright-rust-{}-server-{}", name, process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let node = dir.join("node");
+        fs::write(&node, script).unwrap();
+        fs::set_permissions(&node, fs::Permissions::from_mode(0o755)).unwrap();
+        let args = LaunchServerArgs {
+            timeout: Some(timeout),
+            ..LaunchServerArgs::default()
+        };
+        let err = BrowserServer::launch(&Driver::new(&dir), "chromium", args)
+            .await
+            .unwrap_err();
+        fs::remove_dir_all(&dir).ok();
+        Arc::try_unwrap(err).unwrap()
+    }
+
+    #[cfg(unix)]
+    crate::runtime_test!(exited_server_stderr, {
+        let script = "#!/bin/sh\necho 'Error: server exploded' >&2\nexit 1\n";
+        match launch_with("exited", script, 30000.).await {
+            Error::DriverStartup { message, stderr } => {
+                assert_eq!(message, Error::BrowserServerExited.to_string());
+                assert!(stderr.contains("server exploded"), "{}", stderr);
+            }
+            e => panic!("{:?}", e)
+        }
+    });
+
+    #[cfg(unix)]
+    crate::runtime_test!(hanging_server_times_out, {
+        let script = "#!/bin/sh\necho 'Starting' >&2\nexec sleep 30\n";
+        match launch_with("hanging", script, 500.).await {
+            Error::DriverStartup { message, stderr } => {
+                assert_eq!(message, Error::Timeout.to_string());
+                assert_eq!(stderr, "Starting");
+            }
+            e => panic!("{:?}", e)
+        }
+    });
+}
@@ -1,9 +1,14 @@
 use crate::imp::{
     browser::Browser,
     browser_context::BrowserContext,
+    browser_server::BrowserServer,
     core::*,
+    playwright::Playwright,
     prelude::*,
-    utils::{BrowserChannel, ColorScheme, Geolocation, HttpCredentials, ProxySettings, Viewport},
+    utils::{
+        serialize_extra_http_headers, BrowserChannel, ColorScheme, Geolocation, HarMode,
+        HttpCredentials, ProxySettings, Viewport
+    }
 };
 
 #[derive(Debug)]
@@ -45,17 +50,38 @@ impl BrowserType {
         &self,
         args: LaunchPersistentContextArgs<'_, '_, '_, '_, '_, '_, '_, '_, '_, '_, '_>,
     ) -> Result<Weak<BrowserContext>, Arc<Error>> {
+        if let Some(g) = &args.geolocation {
+            g.validate()?;
+        }
+        let har_path = args.record_har.as_ref().map(|h| h.path.to_owned());
         let res = send_message!(self, "launchPersistentContext", args);
         let guid = only_guid(&res)?;
         let b = get_object!(self.context()?.lock().unwrap(), guid, BrowserContext)?;
+        upgrade(&b)?.set_har_path(har_path);
+        upgrade(&b)?.update_subscription("console", true).await?;
         Ok(b)
     }
 
+    pub(crate) async fn launch_server(
+        &self,
+        args: LaunchServerArgs<'_, '_>
+    ) -> ArcResult<BrowserServer> {
+        let driver = self.context()?.lock().unwrap().driver().clone();
+        BrowserServer::launch(&driver, self.name(), args).await
+    }
+
     pub(crate) async fn connect_over_cdp(
         &self,
         args: ConnectOverCdpArgs<'_>,
     ) -> ArcResult<Weak<Browser>> {
-        let res = send_message!(self, "connectOverCDP", args);
+        let endpoint = args.endpoint_url.to_owned();
+        let m: Str<Method> = "connectOverCDP".to_owned().try_into().unwrap();
+        let r = self.channel().create_request(m).set_args(args)?;
+        let fut = self.channel().send_message(r).await?;
+        let res = fut.await?.map_err(|e| Error::ConnectOverCdp {
+            endpoint,
+            message: e.message.clone()
+        })?;
         #[derive(Deserialize)]
         #[serde(rename_all = "camelCase")]
         struct Response {
@@ -75,12 +101,31 @@ impl BrowserType {
             let arc_context = upgrade(&default_context)?;
             arc_browser.push_context(default_context);
             arc_context.set_browser(browser.clone());
+            arc_context.update_subscription("console", true).await?;
         }
         Ok(browser)
     }
 
-    pub(crate) async fn connect(&self, args: ConnectArgs<'_>) -> ArcResult<Weak<Browser>> {
-        todo!()
+    /// The returned connection must outlive the browser; it is not owned by the browser, which it keeps alive itself.
+    pub(crate) async fn connect(
+        &self,
+        mut args: ConnectArgs<'_>
+    ) -> ArcResult<(Weak<Browser>, Connection)> {
+        let driver = self.context()?.lock().unwrap().driver().clone();
+        let headers = args.headers.get_or_insert_with(HashMap::new);
+        headers.insert("x-playwright-browser".into(), self.name().into());
+        let options = serde_json::to_value(&args).map_err(Error::Serde)?;
+        let mut conn = Connection::connect(&driver, &options).map_err(Error::Io)?;
+        // The bridge reports why it couldn't connect on stderr
+        let playwright = match Playwright::wait_initial_object(&conn).await {
+            Ok(p) => p,
+            Err(e) => return Err(conn.startup_error(e).into())
+        };
+        let browser = upgrade(&playwright)?
+            .pre_launched_browser()
+            .ok_or(Error::ObjectNotFound)?;
+        upgrade(&browser)?.set_is_remote_true();
+        Ok((browser, conn))
     }
 }
 
@@ -195,7 +240,10 @@ pub(crate) struct LaunchPersistentContextArgs<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i
     pub(crate) timezone_id: Option<&'g str>,
     pub(crate) geolocation: Option<Geolocation>,
     pub(crate) permissions: Option<&'h [String]>,
-    #[serde(rename = "extraHTTPHeaders")]
+    #[serde(
+        rename = "extraHTTPHeaders",
+        serialize_with = "serialize_extra_http_headers"
+    )]
     pub(crate) extra_http_headers: Option<HashMap<String, String>>,
     pub(crate) offline: Option<bool>,
     pub(crate) http_credentials: Option<&'i HttpCredentials>,
@@ -219,12 +267,46 @@ pub struct RecordVideo<'a> {
     pub size: Option<Viewport>,
 }
 
+/// The HAR is written to `path` once the context is closed. With a `.zip` path the response bodies are stored as
+/// separate entries of the archive.
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct RecordHar<'a> {
     pub path: &'a Path,
+    /// Whether to omit request content from the HAR. Defaults to `false`.
+    #[serde(
+        rename = "content",
+        skip_serializing_if = "not_omitted",
+        serialize_with = "serialize_omitted"
+    )]
     pub omit_content: Option<bool>,
+    /// With [`HarMode::Minimal`], only the information needed to route from the HAR is recorded. Defaults to
+    /// [`HarMode::Full`].
+    pub mode: Option<HarMode>,
+    /// A glob such as `**/api/**`; only requests with a matching url are stored in the HAR.
+    #[serde(rename = "urlGlob")]
+    pub url_filter: Option<&'a str>
+}
+
+impl<'a> RecordHar<'a> {
+    pub fn new(path: &'a Path) -> Self {
+        Self {
+            path,
+            omit_content: None,
+            mode: None,
+            url_filter: None
+        }
+    }
+}
+
+fn not_omitted(omit_content: &Option<bool>) -> bool { *omit_content != Some(true) }
+
+fn serialize_omitted<S>(_: &Option<bool>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer
+{
+    serializer.serialize_str("omit")
 }
 
 impl<'a> LaunchPersistentContextArgs<'a, '_, '_, '_, '_, '_, '_, '_, '_, '_, '_> {
@@ -273,22 +355,54 @@ impl<'a> LaunchPersistentContextArgs<'a, '_, '_, '_, '_, '_, '_, '_, '_, '_, '_>
     }
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LaunchServerArgs<'a, 'b> {
+    #[serde(rename = "executablePath")]
+    pub(crate) executable: Option<&'a Path>,
+    pub(crate) args: Option<&'b [String]>,
+    pub(crate) timeout: Option<f64>,
+    pub(crate) headless: Option<bool>,
+    pub(crate) chromium_sandbox: Option<bool>,
+    pub(crate) channel: Option<BrowserChannel>,
+    pub(crate) port: Option<u16>,
+    pub(crate) ws_path: Option<String>
+}
+
+impl Default for LaunchServerArgs<'_, '_> {
+    fn default() -> Self {
+        Self {
+            executable: None,
+            args: None,
+            timeout: Some(30000.0),
+            headless: None,
+            chromium_sandbox: None,
+            channel: None,
+            port: None,
+            ws_path: None
+        }
+    }
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct ConnectArgs<'a> {
     ws_endpoint: &'a str,
+    pub(crate) headers: Option<HashMap<String, String>>,
     pub(crate) timeout: Option<f64>,
     #[serde(rename = "slowMo")]
-    pub(crate) slowmo: Option<f64>,
+    pub(crate) slowmo: Option<f64>
 }
 
 impl<'a> ConnectArgs<'a> {
     pub(crate) fn new(ws_endpoint: &'a str) -> Self {
         Self {
             ws_endpoint,
+            headers: None,
             timeout: Some(30000.0),
-            slowmo: None,
+            slowmo: None
         }
     }
 }
@@ -351,4 +465,37 @@ mod tests {
             }
         }
     });
+
+    #[test]
+    fn record_har() {
+        let path = Path::new("a.har");
+        let har = RecordHar::new(path);
+        assert_eq!(
+            serde_json::to_value(&har).unwrap(),
+            serde_json::json!({ "path": "a.har" })
+        );
+        let har = RecordHar {
+            omit_content: Some(true),
+            mode: Some(HarMode::Minimal),
+            url_filter: Some("**/api/**"),
+            ..RecordHar::new(path)
+        };
+        assert_eq!(
+            serde_json::to_value(&har).unwrap(),
+            serde_json::json!({
+                "path": "a.har",
+                "content": "omit",
+                "mode": "minimal",
+                "urlGlob": "**/api/**"
+            })
+        );
+        let har = RecordHar {
+            omit_content: Some(false),
+            ..RecordHar::new(path)
+        };
+        assert_eq!(
+            serde_json::to_value(&har).unwrap(),
+            serde_json::json!({ "path": "a.har" })
+        );
+    }
 }
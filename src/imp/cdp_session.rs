@@ -0,0 +1,27 @@
+use crate::imp::{core::*, prelude::*};
+
+/// A raw Chrome DevTools Protocol session, only available in Chromium.
+#[derive(Debug)]
+pub(crate) struct CdpSession {
+    channel: ChannelOwner
+}
+
+impl CdpSession {
+    pub(crate) fn new(channel: ChannelOwner) -> Self { Self { channel } }
+
+    pub(crate) async fn send<T: Serialize>(&self, method: &str, params: T) -> ArcResult<Value> {
+        #[derive(Serialize)]
+        struct Args<'a, T> {
+            method: &'a str,
+            params: T
+        }
+        let args = Args { method, params };
+        let v = send_message!(self, "send", args);
+        Ok(v.get("result").cloned().unwrap_or_default())
+    }
+}
+
+impl RemoteObject for CdpSession {
+    fn channel(&self) -> &ChannelOwner { &self.channel }
+    fn channel_mut(&mut self) -> &mut ChannelOwner { &mut self.channel }
+}
@@ -1,53 +1,92 @@
-use crate::imp::{core::*, js_handle::JsHandle, prelude::*, utils::SourceLocation};
+use crate::imp::{core::*, js_handle::JsHandle, page::Page, prelude::*, utils::SourceLocation};
 
+/// Built from the `console` event of the browser context; the driver no longer sends it as a channel object.
 #[derive(Debug)]
 pub(crate) struct ConsoleMessage {
-    channel: ChannelOwner,
+    ctx: Weak<Mutex<Context>>,
+    r#type: String,
+    text: String,
     location: SourceLocation,
-    args: Vec<Weak<JsHandle>>
+    args: Vec<Str<Guid>>,
+    page: Option<Weak<Page>>
 }
 
 impl ConsoleMessage {
-    pub(crate) fn try_new(ctx: &Context, channel: ChannelOwner) -> Result<Self, Error> {
+    pub(crate) fn try_new(
+        ctx: &Context,
+        weak_ctx: Weak<Mutex<Context>>,
+        params: Map<String, Value>
+    ) -> Result<Self, Error> {
         #[derive(Deserialize)]
         struct De {
+            r#type: String,
+            text: String,
             location: SourceLocation,
-            args: Vec<OnlyGuid>
+            args: Vec<OnlyGuid>,
+            page: Option<OnlyGuid>
         }
-        let De { location, args } = serde_json::from_value(channel.initializer.clone())?;
-        let args = args
-            .iter()
-            .map(|OnlyGuid { guid }| get_object!(ctx, guid, JsHandle))
-            .collect::<Result<Vec<_>, _>>()?;
+        let De {
+            r#type,
+            text,
+            location,
+            args,
+            page
+        } = serde_json::from_value(params.into())?;
+        let page = match page {
+            Some(OnlyGuid { guid }) => Some(get_object!(ctx, &guid, Page)?),
+            None => None
+        };
         Ok(Self {
-            channel,
+            ctx: weak_ctx,
+            r#type,
+            text,
             location,
-            args
+            args: args.into_iter().map(|OnlyGuid { guid }| guid).collect(),
+            page
         })
     }
 
-    pub(crate) fn r#type(&self) -> &str {
-        self.channel()
-            .initializer
-            .get("type")
-            .and_then(|v| v.as_str())
-            .unwrap_or_default()
-    }
+    pub(crate) fn r#type(&self) -> &str { &self.r#type }
 
-    pub(crate) fn text(&self) -> &str {
-        self.channel()
-            .initializer
-            .get("text")
-            .and_then(|v| v.as_str())
-            .unwrap_or_default()
-    }
+    pub(crate) fn text(&self) -> &str { &self.text }
 
     pub(crate) fn location(&self) -> &SourceLocation { &self.location }
 
-    pub(crate) fn args(&self) -> &[Weak<JsHandle>] { &self.args }
-}
+    pub(crate) fn page(&self) -> Option<Weak<Page>> { self.page.clone() }
 
-impl RemoteObject for ConsoleMessage {
-    fn channel(&self) -> &ChannelOwner { &self.channel }
-    fn channel_mut(&mut self) -> &mut ChannelOwner { &mut self.channel }
+    /// Handles are looked up on demand. They stay registered until the driver disposes them,
+    /// which happens when they are disposed explicitly or when the page navigates.
+    pub(crate) fn args(&self) -> Result<Vec<Weak<JsHandle>>, Error> {
+        let ctx = upgrade(&self.ctx)?;
+        let ctx = ctx.lock().unwrap();
+        self.args
+            .iter()
+            .map(|guid| match ctx.find_object(guid) {
+                Some(RemoteWeak::JsHandle(x)) => Ok(x),
+                _ => Err(Error::ObjectNotFound)
+            })
+            .collect()
+    }
+
+    /// Handles that are already gone, such as after a navigation, are skipped.
+    pub(crate) async fn dispose_args(&self) {
+        let handles: Vec<Arc<JsHandle>> = match self.ctx.upgrade() {
+            Some(ctx) => {
+                let ctx = ctx.lock().unwrap();
+                self.args
+                    .iter()
+                    .filter_map(|guid| match ctx.find_object(guid) {
+                        Some(RemoteWeak::JsHandle(x)) => x.upgrade(),
+                        _ => None
+                    })
+                    .collect()
+            }
+            None => return
+        };
+        for handle in handles {
+            if let Err(e) = handle.dispose().await {
+                log::trace!("Failed to dispose console argument: {:?}", e);
+            }
+        }
+    }
 }
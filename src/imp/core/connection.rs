@@ -1,12 +1,14 @@
 use super::driver::Driver;
 use crate::imp::{core::*, prelude::*};
 use std::{
-    io,
-    process::{Child, Command, Stdio},
+    collections::VecDeque,
+    io::{self, BufRead, BufReader},
+    process::{Child, ChildStderr, Command, Stdio},
     sync::{
         atomic::{AtomicBool, Ordering},
         TryLockError,
     },
+    thread::JoinHandle
 };
 
 #[derive(Debug)]
@@ -16,53 +18,104 @@ pub(crate) struct Context {
     id: i32,
     callbacks: HashMap<i32, WaitPlaces<WaitMessageResult>>,
     writer: Writer,
+    driver: Driver
 }
 
 #[derive(Debug)]
 pub(crate) struct Connection {
-    _child: Child,
+    child: Child,
     ctx: Am<Context>,
     reader: Am<Reader>,
     should_stop: Arc<AtomicBool>,
+    stderr: Arc<DriverStderr>,
+    stderr_reader: Option<JoinHandle<()>>
+}
+
+/// Tail of what the driver writes to stderr. It is only meant for diagnostics, such as why the driver failed to start.
+#[derive(Debug, Default)]
+pub(crate) struct DriverStderr {
+    lines: Mutex<VecDeque<String>>,
+    closed: AtomicBool
+}
+
+impl DriverStderr {
+    const MAX_LINES: usize = 1000;
+
+    /// `handler` is called with each line as it is read, see [`Driver::on_stderr`].
+    pub(crate) fn read(
+        self: &Arc<Self>,
+        stderr: ChildStderr,
+        handler: Option<StderrHandler>
+    ) -> JoinHandle<()> {
+        let this = self.clone();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                log::debug!("driver: {}", line);
+                if let Some(handler) = &handler {
+                    handler(&line);
+                }
+                let lines = &mut this.lines.lock().unwrap();
+                if lines.len() == Self::MAX_LINES {
+                    lines.pop_front();
+                }
+                lines.push_back(line);
+            }
+            this.closed.store(true, Ordering::Relaxed);
+        })
+    }
+
+    /// Whether the driver closed stderr, which it only does when exiting.
+    pub(crate) fn is_closed(&self) -> bool { self.closed.load(Ordering::Relaxed) }
+
+    pub(crate) fn text(&self) -> String {
+        let lines = self.lines.lock().unwrap();
+        lines
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
 }
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
-    #[error(transparent)]
+    #[error("I/O error")]
     Io(#[from] io::Error),
     #[error("Failed to initialize")]
     InitializationError,
+    #[error("Driver failed to start ({message}): {stderr}")]
+    DriverStartup { message: String, stderr: String },
     #[error("Disconnected")]
     ReceiverClosed,
     #[error("Invalid message")]
     InvalidParams,
     #[error("Object not found")]
     ObjectNotFound,
-    #[error(transparent)]
+    #[error("Failed to handle JSON")]
     Serde(#[from] serde_json::Error),
     #[error("Failed to send")]
     Channel,
-    #[error(transparent)]
+    #[error("Failed to communicate with the driver")]
     Transport(#[from] TransportError),
     #[error("Callback not found")]
     CallbackNotFound,
-    #[error(transparent)]
+    #[error("Error responded by the driver")]
     ErrorResponded(#[from] Arc<ErrorMessage>),
     #[error("Value is not Object")]
     NotObject,
     #[error("guid not found in {0:?}")]
     GuidNotFound(Value),
-    #[error(transparent)]
+    #[error("Invalid base64")]
     InvalidBase64(#[from] base64::DecodeError),
-    #[error(transparent)]
+    #[error("Invalid UTF-8")]
     InvalidUtf8(#[from] std::string::FromUtf8Error),
-    #[error(transparent)]
+    #[error("Failed to serialize a message")]
     SerializationPwJson(#[from] ser::Error),
-    #[error(transparent)]
+    #[error("Failed to deserialize a message")]
     DeserializationPwJson(#[from] de::Error),
-    #[error(transparent)]
-    Arc(#[from] Arc<Error>),
-    #[error(transparent)]
+    #[error("Shared error")]
+    Arc(#[source] SharedError),
+    #[error("Failed to receive an event")]
     Event(#[from] broadcast::error::RecvError),
     #[error("Path is not available when using BrowserType.connect(). Use save_as() to save a local copy.")]
     RemoteArtifact,
@@ -70,12 +123,91 @@ pub enum Error {
     ResolvePath(PathBuf),
     #[error("Timed out")]
     Timeout,
-    #[error(transparent)]
+    #[error("Invalid geolocation: {0}")]
+    InvalidGeolocation(String),
+    #[error("Invalid cookie {0}")]
+    InvalidCookie(String),
+    #[error("PDF generation is only supported in headless Chromium, not {0}")]
+    PdfNotSupported(String),
+    #[error("Chrome DevTools Protocol is only available in Chromium, not {0}")]
+    CdpNotSupported(String),
+    #[error("Invalid expression: {0}")]
+    InvalidExpression(String),
+    #[error("{0} is already exposed with another needs_handle, which can't be changed")]
+    BindingNeedsHandle(String),
+    #[error("Page has no viewport to restore")]
+    NoViewport,
+    #[error("Evaluation failed: {0}")]
+    Evaluation(String),
+    #[error("Network error: {0}")]
+    Network(String),
+    #[error("Navigation failed: {0}")]
+    Navigation(String),
+    #[error("Input files must be given either all as paths or all as payloads")]
+    MixedInputFiles,
+    #[error("Page is not recording a video segment started with start_video")]
+    NotRecording,
+    #[error("Browser server exited before reporting its endpoint")]
+    BrowserServerExited,
+    #[error("Failed to connect over CDP to {endpoint}: {message}")]
+    ConnectOverCdp { endpoint: String, message: String },
+    #[error("Task failed")]
     Join(#[from] JoinError),
 }
 
+/// An [`Error`] handed to everyone waiting on the same operation. [`Error::Arc`] reports the shared error itself as its
+/// [`source`](std::error::Error::source), which is why this only derefs to it.
+#[derive(Debug, Clone)]
+pub struct SharedError(pub Arc<Error>);
+
+impl std::ops::Deref for SharedError {
+    type Target = Error;
+
+    fn deref(&self) -> &Error { &self.0 }
+}
+
+impl From<Arc<Error>> for Error {
+    fn from(e: Arc<Error>) -> Self { Self::Arc(SharedError(e)) }
+}
+
 pub(crate) type ArcResult<T> = Result<T, Arc<Error>>;
 
+/// Maps a `TimeoutError` responded by the driver to [`Error::Timeout`], for calls documented to time out that way.
+pub(crate) fn responded_timeout(e: Arc<Error>) -> Arc<Error> {
+    match &*e {
+        Error::ErrorResponded(m) if m.name == "TimeoutError" => Arc::new(Error::Timeout),
+        _ => e
+    }
+}
+
+/// Maps an error responded by the driver because the browser couldn't reach the network, such as when the context
+/// is offline, to [`Error::Network`].
+pub(crate) fn responded_network_error(e: Arc<Error>) -> Arc<Error> {
+    // Chromium, Firefox and WebKit respectively
+    const MARKERS: &[&str] = &[
+        "net::ERR_",
+        "NS_ERROR_",
+        "The Internet connection appears to be offline",
+        "Could not connect",
+        "The network connection was lost",
+        "A server with the specified hostname could not be found"
+    ];
+    match &*e {
+        Error::ErrorResponded(m) if MARKERS.iter().any(|x| m.message.contains(x)) => {
+            Arc::new(Error::Network(m.message.clone()))
+        }
+        _ => e
+    }
+}
+
+/// The messages of `e` and of its sources, as they only display themselves.
+fn describe(e: &Error) -> String {
+    std::iter::successors(Some(e as &dyn std::error::Error), |e| e.source())
+        .map(|e| e.to_string())
+        .collect::<Vec<_>>()
+        .join(": ")
+}
+
 impl Drop for Connection {
     fn drop(&mut self) {
         self.notify_closed(Error::ReceiverClosed);
@@ -86,60 +218,109 @@ impl Drop for Connection {
 impl Connection {
     fn try_new(driver: &Driver) -> io::Result<Connection> {
         // For Playwright 1.50+, we run: node package/cli.js run-driver
-        let executable = driver.executable();
-        let cli_script = driver.cli_script();
-        let mut child = Command::new(&executable)
-            .arg(&cli_script)
-            .args(&["run-driver"])
+        let mut command = Command::new(driver.executable());
+        command.arg(driver.cli_script()).args(&["run-driver"]);
+        Self::spawn(driver, &mut command)
+    }
+
+    fn spawn(driver: &Driver, command: &mut Command) -> io::Result<Connection> {
+        let mut child = command
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
-            .stderr(Stdio::null())
+            .stderr(Stdio::piped())
             .spawn()?;
         // TODO: env "NODE_OPTIONS"
         let stdin = child.stdin.take().unwrap();
         let stdout = child.stdout.take().unwrap();
+        let stderr = Arc::new(DriverStderr::default());
+        let stderr_reader = stderr.read(child.stderr.take().unwrap(), driver.stderr_handler());
         let reader = Reader::new(stdout);
         let writer = Writer::new(stdin);
-        let ctx = Context::new(writer);
+        let ctx = Context::new(writer, driver.clone());
         Ok(Self {
-            _child: child,
+            child,
             ctx,
             should_stop: Arc::new(false.into()),
             reader: Arc::new(Mutex::new(reader)),
+            stderr,
+            stderr_reader: Some(stderr_reader)
         })
     }
 
-    pub(crate) fn run(driver: &Driver) -> io::Result<Connection> {
-        let conn = Self::try_new(driver)?;
+    /// Fails with [`Error::DriverStartup`] carrying what the driver wrote to stderr if it exits before initializing.
+    pub(crate) fn run(driver: &Driver) -> Result<Connection, Error> {
+        let mut conn = Self::try_new(driver)?;
+        if let Err(e) = conn.initialize() {
+            return Err(conn.startup_error(e.into()));
+        }
+        conn.start();
+        Ok(conn)
+    }
 
-        // Playwright 1.50+ requires an "initialize" message before sending objects
-        {
-            let empty_guid: &S<Guid> = S::validate("").unwrap();
-            let initialize_method: &S<Method> = S::validate("initialize").unwrap();
-            let mut params = Map::new();
-            params.insert(
-                "sdkLanguage".to_string(),
-                Value::String("javascript".to_string()),
-            );
-
-            let req = Req {
-                id: 0,
-                guid: empty_guid,
-                method: initialize_method,
-                params,
-                metadata: Map::new(),
-            };
+    pub(crate) fn stderr(&self) -> &Arc<DriverStderr> { &self.stderr }
 
-            let mut ctx = conn.ctx.lock().unwrap();
-            ctx.writer
-                .send(&req)
-                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+    /// Stops the driver and turns `e` into [`Error::DriverStartup`] once all of its stderr has been read. Errors
+    /// are kept as they are when the driver wrote nothing.
+    pub(crate) fn startup_error(&mut self, e: Error) -> Error {
+        if let Ok(None) = self.child.try_wait() {
+            self.child.kill().ok();
+        }
+        self.child.wait().ok();
+        if let Some(reader) = self.stderr_reader.take() {
+            reader.join().ok();
         }
+        let stderr = self.stderr.text();
+        if stderr.is_empty() {
+            return e;
+        }
+        Error::DriverStartup {
+            message: describe(&e),
+            stderr
+        }
+    }
 
+    /// Connects to a Playwright server listening on a websocket. The driver's node runs a bridge relaying the pipe
+    /// protocol over the socket, so that the connection otherwise behaves like a local one.
+    ///
+    /// `options` are the connect options the bridge reads: `wsEndpoint`, `headers`, `timeout` and `slowMo`.
+    pub(crate) fn connect(driver: &Driver, options: &Value) -> io::Result<Connection> {
+        const BRIDGE: &str = include_str!("ws_bridge.js");
+        let mut command = Command::new(driver.executable());
+        command
+            .arg("-e")
+            .arg(BRIDGE)
+            .env("PLAYWRIGHT_RUST_CONNECT", options.to_string());
+        let conn = Self::spawn(driver, &mut command)?;
+        conn.initialize()?;
         conn.start();
         Ok(conn)
     }
 
+    fn initialize(&self) -> io::Result<()> {
+        // Playwright 1.50+ requires an "initialize" message before sending objects
+        let empty_guid: &S<Guid> = S::validate("").unwrap();
+        let initialize_method: &S<Method> = S::validate("initialize").unwrap();
+        let mut params = Map::new();
+        params.insert(
+            "sdkLanguage".to_string(),
+            Value::String("javascript".to_string())
+        );
+
+        let req = Req {
+            id: 0,
+            guid: empty_guid,
+            method: initialize_method,
+            params,
+            metadata: Map::new()
+        };
+
+        let mut ctx = self.ctx.lock().unwrap();
+        ctx.writer
+            .send(&req)
+            .map_err(io::Error::other)?;
+        Ok(())
+    }
+
     fn start(&self) {
         let c2 = Arc::downgrade(&self.ctx);
         let r2 = Arc::downgrade(&self.reader);
@@ -212,7 +393,7 @@ impl Connection {
 }
 
 impl Context {
-    fn new(writer: Writer) -> Am<Context> {
+    fn new(writer: Writer, driver: Driver) -> Am<Context> {
         let objects = {
             let mut d = HashMap::new();
             let root = RootObject::new();
@@ -225,12 +406,18 @@ impl Context {
             id: 0,
             callbacks: HashMap::new(),
             writer,
+            driver
         };
         let am = Arc::new(Mutex::new(ctx));
         am.lock().unwrap().ctx = Arc::downgrade(&am);
         am
     }
 
+    /// The driver this connection runs, used to spawn further driver processes.
+    pub(crate) fn driver(&self) -> &Driver { &self.driver }
+
+    pub(crate) fn object_count(&self) -> usize { self.objects.len() }
+
     fn notify_closed(&mut self, e: Error) {
         let err = Arc::new(e);
         for p in self.callbacks.iter().map(|(_, v)| v) {
@@ -368,7 +555,56 @@ impl Context {
 
 #[cfg(test)]
 mod tests {
-    use crate::imp::core::*;
+    use super::describe;
+    use crate::imp::{core::*, prelude::*};
+
+    #[test]
+    fn network_error() {
+        let responded = |name: &str, message: &str| {
+            Arc::new(Error::ErrorResponded(Arc::new(ErrorMessage {
+                name: name.into(),
+                message: message.into(),
+                stack: String::new()
+            })))
+        };
+        let e = responded(
+            "Error",
+            "page.goto: net::ERR_INTERNET_DISCONNECTED at http://a/"
+        );
+        assert!(matches!(&*responded_network_error(e), Error::Network(_)));
+        let e = responded("Error", "page.goto: NS_ERROR_OFFLINE");
+        assert!(matches!(&*responded_network_error(e), Error::Network(_)));
+        let e = responded("Error", "page.goto: The network connection was lost.");
+        assert!(matches!(&*responded_network_error(e), Error::Network(_)));
+        let e = responded("TimeoutError", "Timeout 30000ms exceeded.");
+        assert!(matches!(
+            &*responded_network_error(e),
+            Error::ErrorResponded(_)
+        ));
+    }
+
+    #[test]
+    fn source_chain() {
+        use std::error::Error as _;
+        let io = || std::io::Error::new(std::io::ErrorKind::NotFound, "missing");
+        let e = Error::from(io());
+        assert_eq!(e.to_string(), "I/O error");
+        assert_eq!(describe(&e), "I/O error: missing");
+        let source = e
+            .source()
+            .unwrap()
+            .downcast_ref::<std::io::Error>()
+            .unwrap();
+        assert_eq!(source.kind(), std::io::ErrorKind::NotFound);
+        let wrapped = Error::from(Arc::new(Error::from(io())));
+        let inner = wrapped
+            .source()
+            .unwrap()
+            .downcast_ref::<Error>()
+            .unwrap();
+        assert!(matches!(inner, Error::Io(_)));
+        assert!(inner.source().unwrap().is::<std::io::Error>());
+    }
 
     crate::runtime_test!(start, {
         let driver = Driver::install().unwrap();
@@ -2,32 +2,144 @@ use crate::imp::prelude::*;
 use std::{env, fs, io};
 use zip::{result::ZipError, ZipArchive};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Clone)]
 pub struct Driver {
     path: PathBuf,
+    stderr_handler: Option<StderrHandler>
+}
+
+/// Called with every line the driver process writes to stderr, see [`Driver::on_stderr`].
+pub(crate) type StderrHandler = Arc<dyn Fn(&str) + Send + Sync>;
+
+impl std::fmt::Debug for Driver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Driver")
+            .field("path", &self.path)
+            .field("stderr_handler", &self.stderr_handler.is_some())
+            .finish()
+    }
+}
+
+impl PartialEq for Driver {
+    fn eq(&self, other: &Self) -> bool {
+        let handlers_eq = match (&self.stderr_handler, &other.stderr_handler) {
+            (None, None) => true,
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            _ => false
+        };
+        self.path == other.path && handlers_eq
+    }
 }
 
 impl Driver {
+    #[cfg(not(feature = "driver-download"))]
     const ZIP: &'static [u8] = include_bytes!(concat!(env!("OUT_DIR"), env!("SEP"), "driver.zip"));
+    #[cfg(feature = "driver-download")]
+    const URL: &'static str = include_str!(concat!(env!("OUT_DIR"), env!("SEP"), "driver_url"));
     const PLATFORM: &'static str = include_str!(concat!(env!("OUT_DIR"), env!("SEP"), "platform"));
 
     pub fn install() -> io::Result<Self> {
         let this = Self::new(Self::default_dest());
-        if !this.path.is_dir() {
+        if !this.is_installed() {
             this.prepare()?;
         }
         Ok(this)
     }
 
+    /// Whether the driver is fully extracted, which a directory left behind by an interrupted install is not.
+    fn is_installed(&self) -> bool { self.cli_script().is_file() }
+
     /// Without prepare
     pub fn new<P: Into<PathBuf>>(path: P) -> Self {
-        Self { path: path.into() }
+        Self {
+            path: path.into(),
+            stderr_handler: None
+        }
     }
+
+    /// Calls `handler` with every line the processes started from this driver write to stderr, as they are read, on
+    /// top of keeping the last of them for [`Playwright::driver_stderr`](crate::Playwright::driver_stderr) and
+    /// [`Error::DriverStartup`](crate::Error::DriverStartup). Their stdout carries the protocol, so there is nothing
+    /// else to capture.
+    ///
+    /// ```
+    /// # async fn example() -> Result<(), playwright::Error> {
+    /// use playwright::{Driver, Playwright};
     ///
+    /// let driver = Driver::install()?.on_stderr(|line| eprintln!("driver: {}", line));
+    /// let playwright = Playwright::with_driver(driver).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_stderr<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&str) + Send + Sync + 'static
+    {
+        self.stderr_handler = Some(Arc::new(handler));
+        self
+    }
+
+    pub(crate) fn stderr_handler(&self) -> Option<StderrHandler> { self.stderr_handler.clone() }
+    ///
+    /// With the `driver-download` feature the driver archive is downloaded here instead of being embedded in the binary.
+    ///
+    /// The archive is extracted next to the destination and moved into place once complete, so that a failed or
+    /// interrupted install never leaves a partial driver behind.
     pub fn prepare(&self) -> Result<(), ZipError> {
-        fs::create_dir_all(&self.path)?;
-        let mut a = ZipArchive::new(io::Cursor::new(Self::ZIP))?;
-        a.extract(&self.path)
+        let archive = Self::archive()?;
+        let partial = self.partial_path();
+        if partial.exists() {
+            fs::remove_dir_all(&partial)?;
+        }
+        fs::create_dir_all(&partial)?;
+        let extracted =
+            ZipArchive::new(io::Cursor::new(archive)).and_then(|mut a| a.extract(&partial));
+        if let Err(e) = extracted {
+            fs::remove_dir_all(&partial).ok();
+            return Err(e);
+        }
+        if self.path.exists() {
+            fs::remove_dir_all(&self.path)?;
+        }
+        match fs::rename(&partial, &self.path) {
+            Ok(()) => Ok(()),
+            // Another process installed the driver in the meantime
+            Err(_) if self.is_installed() => {
+                fs::remove_dir_all(&partial).ok();
+                Ok(())
+            }
+            Err(e) => {
+                fs::remove_dir_all(&partial).ok();
+                Err(e.into())
+            }
+        }
+    }
+
+    fn partial_path(&self) -> PathBuf {
+        let name = self
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        self.path
+            .with_file_name(format!(".{}.{}.partial", name, std::process::id()))
+    }
+
+    #[cfg(not(feature = "driver-download"))]
+    fn archive() -> io::Result<&'static [u8]> { Ok(Self::ZIP) }
+
+    #[cfg(feature = "driver-download")]
+    fn archive() -> io::Result<Vec<u8>> {
+        let to_io = |e: reqwest::Error| io::Error::other(e);
+        // reqwest::blocking panics when used from an async runtime, so it gets a thread of its own
+        std::thread::spawn(move || {
+            let resp = reqwest::blocking::get(Self::URL)
+                .and_then(|r| r.error_for_status())
+                .map_err(to_io)?;
+            Ok(resp.bytes().map_err(to_io)?.to_vec())
+        })
+        .join()
+        .map_err(|_| io::Error::other("driver download panicked"))?
     }
 
     pub fn default_dest() -> PathBuf {
@@ -66,6 +178,56 @@ impl Driver {
     pub fn cli_script(&self) -> PathBuf {
         self.path.join("package").join("cli.js")
     }
+
+    /// Directory the browsers are installed to, following the same rules as the Playwright CLI:
+    /// `PLAYWRIGHT_BROWSERS_PATH` if set (`0` meaning next to the driver package),
+    /// otherwise `$CACHE_DIR/ms-playwright`.
+    pub fn browsers_path(&self) -> PathBuf {
+        match env::var_os("PLAYWRIGHT_BROWSERS_PATH") {
+            Some(p) if p == "0" => self.path.join("package").join(".local-browsers"),
+            Some(p) if !p.is_empty() => PathBuf::from(p),
+            _ => dirs::cache_dir()
+                .unwrap_or_else(env::temp_dir)
+                .join("ms-playwright")
+        }
+    }
+
+    /// Whether every browser that `playwright install` downloads by default is present,
+    /// at the revision pinned by this driver. Does not spawn the CLI.
+    pub fn browsers_installed(&self) -> bool { self.browsers_installed_in(&self.browsers_path()) }
+
+    fn browsers_installed_in(&self, dir: &Path) -> bool {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Browser {
+            name: String,
+            revision: String,
+            #[serde(default)]
+            install_by_default: bool
+        }
+        #[derive(Deserialize)]
+        struct Browsers {
+            browsers: Vec<Browser>
+        }
+        let path = self.path.join("package").join("browsers.json");
+        let browsers: Browsers = match fs::read(path)
+            .ok()
+            .and_then(|b| serde_json::from_slice(&b).ok())
+        {
+            Some(x) => x,
+            None => return false
+        };
+        browsers
+            .browsers
+            .iter()
+            .filter(|b| b.install_by_default)
+            .all(|b| {
+                // The CLI writes this marker once a browser is fully extracted
+                dir.join(format!("{}-{}", b.name.replace('-', "_"), b.revision))
+                    .join("INSTALLATION_COMPLETE")
+                    .is_file()
+            })
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -84,4 +246,56 @@ mod tests {
     fn install() {
         let _driver = Driver::install().unwrap();
     }
+
+    #[test]
+    fn browsers_installed_in() {
+        let driver_dir = tempdir::TempDir::new("playwright-rust-driver").unwrap();
+        let browsers_dir = tempdir::TempDir::new("playwright-rust-browsers").unwrap();
+        let driver = Driver::new(driver_dir.path());
+        assert!(!driver.browsers_installed_in(browsers_dir.path()));
+        fs::create_dir_all(driver_dir.path().join("package")).unwrap();
+        fs::write(
+            driver_dir.path().join("package").join("browsers.json"),
+            r#"{"browsers": [
+                {"name": "chromium-headless-shell", "revision": "1200", "installByDefault": true},
+                {"name": "firefox", "revision": "1490", "installByDefault": true},
+                {"name": "chromium-tip-of-tree", "revision": "1370", "installByDefault": false}
+            ]}"#
+        )
+        .unwrap();
+        assert!(!driver.browsers_installed_in(browsers_dir.path()));
+        for name in ["chromium_headless_shell-1200", "firefox-1490"] {
+            let d = browsers_dir.path().join(name);
+            fs::create_dir_all(&d).unwrap();
+            fs::write(d.join("INSTALLATION_COMPLETE"), "").unwrap();
+        }
+        assert!(driver.browsers_installed_in(browsers_dir.path()));
+    }
+
+    #[test]
+    fn prepare_replaces_partial_install() {
+        let dir = tempdir::TempDir::new("playwright-rust-driver").unwrap();
+        let driver = Driver::new(dir.path().join("driver"));
+        // As left behind by an interrupted install
+        fs::create_dir_all(driver.path.join("package")).unwrap();
+        assert!(!driver.is_installed());
+        driver.prepare().unwrap();
+        assert!(driver.is_installed());
+        let entries: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .collect();
+        assert_eq!(entries, vec!["driver"]);
+    }
+
+    #[cfg(feature = "driver-download")]
+    #[test]
+    fn download() {
+        let zip: &Path = concat!(env!("OUT_DIR"), env!("SEP"), "driver.zip").as_ref();
+        assert!(!zip.exists());
+        let dir = tempdir::TempDir::new("playwright-rust-driver").unwrap();
+        let driver = Driver::new(dir.path());
+        driver.prepare().unwrap();
+        assert!(driver.cli_script().is_file());
+    }
 }
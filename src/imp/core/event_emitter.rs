@@ -36,22 +36,35 @@ pub(crate) trait IsEvent: Clone {
     fn event_type(&self) -> Self::EventType;
 }
 
-#[cfg(any(feature = "rt-tokio", feature = "rt-actix"))]
 pub(crate) async fn expect_event<E>(
-    mut rx: broadcast::Receiver<E>,
+    rx: broadcast::Receiver<E>,
     evt: E::EventType,
     timeout: u32
 ) -> Result<E, Error>
 where
     E: IsEvent + Send + Sync + 'static,
     <E as event_emitter::IsEvent>::EventType: Send + Sync
+{
+    expect_event_matching(rx, move |x: &E| x.event_type() == evt, timeout).await
+}
+
+/// Resolves with the first event emitted from now on for which `f` returns `true`.
+#[cfg(any(feature = "rt-tokio", feature = "rt-actix"))]
+pub(crate) async fn expect_event_matching<E, F>(
+    mut rx: broadcast::Receiver<E>,
+    f: F,
+    timeout: u32
+) -> Result<E, Error>
+where
+    E: IsEvent + Send + Sync + 'static,
+    F: Fn(&E) -> bool + Send + 'static
 {
     consume(&mut rx).await?;
     let sleep = sleep(Duration::from_millis(timeout as u64));
     let event = spawn(async move {
         loop {
             match rx.recv().await {
-                Ok(x) if x.event_type() == evt => break Ok(x),
+                Ok(x) if f(&x) => break Ok(x),
                 Ok(_) => continue,
                 Err(e) => break Err(e)
             }
@@ -63,22 +76,23 @@ where
     }
 }
 
+/// Resolves with the first event emitted from now on for which `f` returns `true`.
 #[cfg(feature = "rt-async-std")]
-pub(crate) async fn expect_event<E>(
+pub(crate) async fn expect_event_matching<E, F>(
     mut rx: broadcast::Receiver<E>,
-    evt: E::EventType,
+    f: F,
     timeout: u32
 ) -> Result<E, Error>
 where
     E: IsEvent + Send + Sync + 'static,
-    <E as event_emitter::IsEvent>::EventType: Send + Sync
+    F: Fn(&E) -> bool + Send + 'static
 {
     consume(&mut rx).await?;
     let sleep = sleep(Duration::from_millis(timeout as u64));
     let event = spawn(async move {
         loop {
             match rx.recv().await {
-                Ok(x) if x.event_type() == evt => break Ok(x),
+                Ok(x) if f(&x) => break Ok(x),
                 Ok(_) => continue,
                 Err(e) => break Err(e)
             }
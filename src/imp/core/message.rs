@@ -92,7 +92,7 @@ pub struct ErrorMessage {
     pub(crate) stack: String,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub(crate) struct OnlyGuid {
     pub(crate) guid: Str<Guid>,
 }
@@ -15,7 +15,7 @@ pub enum Error {
     TypeMismatch,
     #[error("{0:} isn't supported")]
     NotSupported(&'static str),
-    #[error(transparent)]
+    #[error("{0}")]
     Serde(#[from] serde_json::Error)
 }
 
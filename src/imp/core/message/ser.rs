@@ -19,7 +19,7 @@ pub enum Error {
     JsHandle,
     #[error("Failed to serialize DateTime")]
     DateTime,
-    #[error(transparent)]
+    #[error("{0}")]
     Serde(#[from] serde_json::error::Error)
 }
 
@@ -136,11 +136,11 @@ mod remote_enum {
     use super::{DummyObject as Dummy, RootObject as Root, *};
     use crate::imp::{
         artifact::Artifact, binding_call::BindingCall, browser::Browser,
-        browser_context::BrowserContext, browser_type::BrowserType,
-        console_message::ConsoleMessage, dialog::Dialog, element_handle::ElementHandle,
-        frame::Frame, js_handle::JsHandle, page::Page, playwright::Playwright, request::Request,
-        response::Response, route::Route, selectors::Selectors, stream::Stream,
-        websocket::WebSocket, worker::Worker
+        browser_context::BrowserContext, browser_type::BrowserType, cdp_session::CdpSession,
+        dialog::Dialog, element_handle::ElementHandle, frame::Frame, js_handle::JsHandle,
+        page::Page, playwright::Playwright, request::Request, response::Response, route::Route,
+        selectors::Selectors, stream::Stream, tracing::Tracing, websocket::WebSocket,
+        worker::Worker
     };
 
     macro_rules! upgrade {
@@ -228,8 +228,7 @@ mod remote_enum {
         Browser,
         BrowserContext,
         BrowserType,
-        // CdpSession
-        ConsoleMessage,
+        CdpSession,
         Dialog,
         // Electron
         // ElectronApplication
@@ -243,6 +242,7 @@ mod remote_enum {
         Route,
         Stream,
         Selectors,
+        Tracing,
         WebSocket,
         Worker
     }
@@ -255,16 +255,14 @@ mod remote_enum {
         ) -> Result<RemoteArc, Error> {
             let r = match typ.as_str() {
                 "Artifact" => RemoteArc::Artifact(Arc::new(Artifact::try_new(c)?)),
-                "BindingCall" => RemoteArc::BindingCall(Arc::new(BindingCall::new(c))),
+                "BindingCall" => RemoteArc::BindingCall(Arc::new(BindingCall::try_new(ctx, c)?)),
                 "Browser" => RemoteArc::Browser(Arc::new(Browser::try_new(c)?)),
                 "BrowserContext" => {
-                    RemoteArc::BrowserContext(Arc::new(BrowserContext::try_new(c)?))
+                    RemoteArc::BrowserContext(Arc::new(BrowserContext::try_new(ctx, c)?))
                 }
                 "BrowserType" => RemoteArc::BrowserType(Arc::new(BrowserType::try_new(c)?)),
-                "ConsoleMessage" => {
-                    RemoteArc::ConsoleMessage(Arc::new(ConsoleMessage::try_new(ctx, c)?))
-                }
-                "Dialog" => RemoteArc::Dialog(Arc::new(Dialog::new(c))),
+                "CDPSession" => RemoteArc::CdpSession(Arc::new(CdpSession::new(c))),
+                "Dialog" => RemoteArc::Dialog(Arc::new(Dialog::try_new(ctx, c)?)),
                 "ElementHandle" => RemoteArc::ElementHandle(Arc::new(ElementHandle::new(c))),
                 "Frame" => RemoteArc::Frame(Arc::new(Frame::try_new(ctx, c)?)),
                 "JSHandle" => RemoteArc::JsHandle(Arc::new(JsHandle::try_new(c)?)),
@@ -275,6 +273,7 @@ mod remote_enum {
                 "Route" => RemoteArc::Route(Arc::new(Route::try_new(ctx, c)?)),
                 "Stream" => RemoteArc::Stream(Arc::new(Stream::new(c))),
                 "Selectors" => RemoteArc::Selectors(Arc::new(Selectors::new(c))),
+                "Tracing" => RemoteArc::Tracing(Arc::new(Tracing::new(c))),
                 "WebSocket" => RemoteArc::WebSocket(Arc::new(WebSocket::try_new(c)?)),
                 "Worker" => RemoteArc::Worker(Arc::new(Worker::try_new(c)?)),
                 _ => RemoteArc::Dummy(Arc::new(DummyObject::new(c)))
@@ -21,9 +21,9 @@ pub(super) struct Writer {
 
 #[derive(Error, Debug)]
 pub enum TransportError {
-    #[error(transparent)]
+    #[error("{0}")]
     Serde(#[from] serde_json::error::Error),
-    #[error(transparent)]
+    #[error("{0}")]
     Io(#[from] io::Error),
 }
 
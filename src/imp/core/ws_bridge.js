@@ -0,0 +1,139 @@
+// Relays the length-prefixed pipe protocol on stdio to a Playwright server over a websocket.
+// Run with `node -e`; the connect options are read from PLAYWRIGHT_RUST_CONNECT.
+// Only node's own modules are used, so that the bridge doesn't depend on the driver's internals.
+const crypto = require('crypto');
+const http = require('http');
+const https = require('https');
+
+const options = JSON.parse(process.env.PLAYWRIGHT_RUST_CONNECT);
+
+const fail = message => {
+  process.stderr.write(`${message}\n`);
+  process.exit(1);
+};
+
+const url = new URL(options.wsEndpoint);
+const secure = url.protocol === 'wss:' || url.protocol === 'https:';
+const key = crypto.randomBytes(16).toString('base64');
+const request = (secure ? https : http).request({
+  protocol: secure ? 'https:' : 'http:',
+  hostname: url.hostname.replace(/^\[|\]$/g, ''),
+  port: url.port || (secure ? 443 : 80),
+  path: url.pathname + url.search,
+  headers: {
+    ...(options.headers || {}),
+    'Connection': 'Upgrade',
+    'Upgrade': 'websocket',
+    'Sec-WebSocket-Version': '13',
+    'Sec-WebSocket-Key': key,
+  },
+});
+if (options.timeout)
+  request.setTimeout(options.timeout, () => request.destroy(new Error(`Opening handshake has timed out`)));
+request.on('error', error => fail(error.message));
+request.on('response', response => fail(`Unexpected server response: ${response.statusCode}`));
+request.end();
+
+// Frames sent by a client must be masked (RFC 6455, section 5.3)
+const frame = (opcode, payload) => {
+  let header;
+  if (payload.length < 126) {
+    header = Buffer.alloc(2);
+    header[1] = 0x80 | payload.length;
+  } else if (payload.length < 0x10000) {
+    header = Buffer.alloc(4);
+    header[1] = 0x80 | 126;
+    header.writeUInt16BE(payload.length, 2);
+  } else {
+    header = Buffer.alloc(10);
+    header[1] = 0x80 | 127;
+    header.writeBigUInt64BE(BigInt(payload.length), 2);
+  }
+  header[0] = 0x80 | opcode;
+  const mask = crypto.randomBytes(4);
+  const masked = Buffer.alloc(payload.length);
+  for (let i = 0; i < payload.length; i++)
+    masked[i] = payload[i] ^ mask[i & 3];
+  return Buffer.concat([header, mask, masked]);
+};
+
+const write = bytes => {
+  const header = Buffer.alloc(4);
+  header.writeUInt32LE(bytes.length, 0);
+  process.stdout.write(Buffer.concat([header, bytes]));
+};
+
+request.on('upgrade', (response, socket, head) => {
+  const accept = crypto.createHash('sha1').update(key + '258EAFA5-E914-47DA-95CA-C5AB0DC85B11').digest('base64');
+  if (response.headers['sec-websocket-accept'] !== accept)
+    fail('Invalid Sec-WebSocket-Accept header');
+  request.setTimeout(0);
+  socket.setNoDelay(true);
+
+  const send = message => {
+    const data = frame(0x1, message);
+    if (options.slowMo) setTimeout(() => socket.write(data), options.slowMo);
+    else socket.write(data);
+  };
+  let input = Buffer.alloc(0);
+  process.stdin.on('data', chunk => {
+    input = Buffer.concat([input, chunk]);
+    while (input.length >= 4) {
+      const length = input.readUInt32LE(0);
+      if (input.length < 4 + length) break;
+      send(input.subarray(4, 4 + length));
+      input = input.subarray(4 + length);
+    }
+  });
+  process.stdin.on('end', () => {
+    socket.write(frame(0x8, Buffer.alloc(0)));
+    socket.end();
+  });
+
+  let buffer = head;
+  let fragments = [];
+  const receive = () => {
+    while (buffer.length >= 2) {
+      const fin = (buffer[0] & 0x80) !== 0;
+      const opcode = buffer[0] & 0x0f;
+      let length = buffer[1] & 0x7f;
+      let offset = 2;
+      if (length === 126) {
+        if (buffer.length < 4) return;
+        length = buffer.readUInt16BE(2);
+        offset = 4;
+      } else if (length === 127) {
+        if (buffer.length < 10) return;
+        length = Number(buffer.readBigUInt64BE(2));
+        offset = 10;
+      }
+      if (buffer[1] & 0x80) offset += 4;
+      if (buffer.length < offset + length) return;
+      let payload = buffer.subarray(offset, offset + length);
+      if (buffer[1] & 0x80) {
+        const mask = buffer.subarray(offset - 4, offset);
+        payload = Buffer.from(payload.map((b, i) => b ^ mask[i & 3]));
+      }
+      buffer = buffer.subarray(offset + length);
+      if (opcode === 0x8) {
+        socket.end(frame(0x8, Buffer.alloc(0)));
+        process.exit(0);
+      } else if (opcode === 0x9) {
+        socket.write(frame(0xa, payload));
+      } else if (opcode === 0x0 || opcode === 0x1 || opcode === 0x2) {
+        fragments.push(payload);
+        if (fin) {
+          write(Buffer.concat(fragments));
+          fragments = [];
+        }
+      }
+    }
+  };
+  socket.on('data', chunk => {
+    buffer = Buffer.concat([buffer, chunk]);
+    receive();
+  });
+  socket.on('close', () => process.exit(0));
+  socket.on('error', error => fail(error.message));
+  receive();
+});
@@ -1,15 +1,105 @@
-use crate::imp::core::*;
+use crate::imp::{core::*, page::Page, prelude::*};
+use futures::future::BoxFuture;
+use tokio::sync::mpsc;
+
+pub(crate) type DialogHandler = Arc<dyn Fn(Weak<Dialog>) -> BoxFuture<'static, ()> + Send + Sync>;
 
 #[derive(Debug)]
 pub(crate) struct Dialog {
-    channel: ChannelOwner
+    channel: ChannelOwner,
+    r#type: String,
+    message: String,
+    default_value: String,
+    page: Option<Weak<Page>>
 }
 
 impl Dialog {
-    pub(crate) fn new(channel: ChannelOwner) -> Self { Self { channel } }
+    pub(crate) fn try_new(ctx: &Context, channel: ChannelOwner) -> Result<Self, Error> {
+        let Initializer {
+            r#type,
+            message,
+            default_value,
+            page
+        } = serde_json::from_value(channel.initializer.clone())?;
+        let page = match page {
+            Some(OnlyGuid { guid }) => Some(get_object!(ctx, &guid, Page)?),
+            None => None
+        };
+        Ok(Self {
+            channel,
+            r#type,
+            message,
+            default_value,
+            page
+        })
+    }
+
+    pub(crate) fn r#type(&self) -> &str { &self.r#type }
+
+    pub(crate) fn message(&self) -> &str { &self.message }
+
+    pub(crate) fn default_value(&self) -> &str { &self.default_value }
+
+    pub(crate) fn page(&self) -> Option<Weak<Page>> { self.page.clone() }
+
+    pub(crate) async fn accept(&self, prompt_text: Option<&str>) -> ArcResult<()> {
+        #[skip_serializing_none]
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Args<'a> {
+            prompt_text: Option<&'a str>
+        }
+        let args = Args { prompt_text };
+        let _ = send_message!(self, "accept", args);
+        Ok(())
+    }
+
+    pub(crate) async fn dismiss(&self) -> ArcResult<()> {
+        let _ = send_message!(self, "dismiss", Map::new());
+        Ok(())
+    }
+
+    /// Runs `handler` for every dialog sent through the returned channel.
+    pub(crate) fn serve(handler: DialogHandler) -> mpsc::UnboundedSender<Weak<Dialog>> {
+        let (tx, mut rx) = mpsc::unbounded_channel::<Weak<Dialog>>();
+        spawn(async move {
+            while let Some(dialog) = rx.recv().await {
+                spawn(handler(dialog));
+            }
+        });
+        tx
+    }
+
+    /// What happens to a dialog nobody handles: `beforeunload` is accepted so that the page can close, anything else is
+    /// dismissed.
+    pub(crate) fn close_unhandled(dialog: Weak<Dialog>) {
+        spawn(async move {
+            let dialog = match dialog.upgrade() {
+                Some(d) => d,
+                None => return
+            };
+            let closed = if dialog.r#type() == "beforeunload" {
+                dialog.accept(None).await
+            } else {
+                dialog.dismiss().await
+            };
+            if let Err(e) = closed {
+                log::trace!("Failed to close dialog: {:?}", e);
+            }
+        });
+    }
 }
 
 impl RemoteObject for Dialog {
     fn channel(&self) -> &ChannelOwner { &self.channel }
     fn channel_mut(&mut self) -> &mut ChannelOwner { &mut self.channel }
 }
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Initializer {
+    r#type: String,
+    message: String,
+    default_value: String,
+    page: Option<OnlyGuid>
+}
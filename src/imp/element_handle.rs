@@ -3,8 +3,8 @@ use crate::imp::{
     frame::Frame,
     prelude::*,
     utils::{
-        ElementState, File, FloatRect, KeyboardModifier, MouseButton, Position, ScreenshotType,
-        WaitForSelectorState
+        ElementState, FloatRect, InputFiles, KeyboardModifier, MouseButton, Position,
+        ScreenshotAnimations, ScreenshotType, WaitForSelectorState
     }
 };
 
@@ -209,6 +209,11 @@ impl ElementHandle {
         Ok(bytes)
     }
 
+    pub(crate) async fn dispose(&self) -> ArcResult<()> {
+        let _ = send_message!(self, "dispose", Map::new());
+        Ok(())
+    }
+
     pub(crate) async fn wait_for_element_state(
         &self,
         state: ElementState,
@@ -394,7 +399,27 @@ pub(crate) struct ScreenshotArgs<'a> {
     pub(crate) timeout: Option<f64>,
     pub(crate) r#type: Option<ScreenshotType>,
     pub(crate) quality: Option<i64>,
-    pub(crate) omit_background: Option<bool>
+    pub(crate) omit_background: Option<bool>,
+    pub(crate) animations: Option<ScreenshotAnimations>,
+    pub(crate) mask: Option<Vec<MaskedElement>>
+}
+
+/// Elements matching `selector` in `frame` are covered with a box in the screenshot.
+#[derive(Debug, Serialize)]
+pub(crate) struct MaskedElement {
+    pub(crate) frame: OnlyGuid,
+    pub(crate) selector: String
+}
+
+impl MaskedElement {
+    pub(crate) fn new(frame: &Frame, selector: String) -> Self {
+        Self {
+            frame: OnlyGuid {
+                guid: frame.guid().to_owned()
+            },
+            selector
+        }
+    }
 }
 
 #[skip_serializing_none]
@@ -432,7 +457,9 @@ pub(crate) struct SelectOptionArgs {
     pub(crate) no_wait_after: Option<bool>
 }
 
+/// Serialized as an object with a single `value`, `index` or `label` key.
 #[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
 pub(crate) enum Opt {
     Value(String),
     Index(usize),
@@ -443,7 +470,8 @@ pub(crate) enum Opt {
 #[derive(Serialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct SetInputFilesArgs {
-    pub(crate) files: Vec<File>,
+    #[serde(flatten)]
+    pub(crate) files: InputFiles,
     pub(crate) timeout: Option<f64>,
     pub(crate) no_wait_after: Option<bool>
 }
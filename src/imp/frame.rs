@@ -1,14 +1,17 @@
 pub(crate) use crate::imp::element_handle::Opt;
 use crate::imp::{
     core::*,
-    element_handle::ElementHandle,
+    element_handle::{ClickArgs as ElementClickArgs, ElementHandle},
     js_handle::JsHandle,
     page::Page,
     prelude::*,
     response::Response,
-    utils::{DocumentLoadState, File, KeyboardModifier, MouseButton, Position}
+    utils::{
+        remaining_timeout, validate_expression, DocumentLoadState, InputFiles, KeyboardModifier,
+        MouseButton, Position, UrlMatcher
+    }
 };
-use std::{collections::HashSet, iter::FromIterator};
+use std::{collections::HashSet, iter::FromIterator, time::Instant};
 
 #[derive(Debug)]
 pub(crate) struct Frame {
@@ -29,15 +32,17 @@ struct Variable {
 
 macro_rules! is_checked {
     ($f: ident, $m: literal) => {
-        pub(crate) async fn $f(&self, selector: &str, timeout: Option<f64>) -> ArcResult<bool> {
-            #[skip_serializing_none]
-            #[derive(Serialize)]
-            #[serde(rename_all = "camelCase")]
-            struct Args<'a> {
-                selector: &'a str,
-                timeout: Option<f64>
-            }
-            let args = Args { selector, timeout };
+        pub(crate) async fn $f(
+            &self,
+            selector: &str,
+            timeout: Option<f64>,
+            strict: Option<bool>
+        ) -> ArcResult<bool> {
+            let args = SelectorTimeout {
+                selector,
+                timeout,
+                strict
+            };
             let v = send_message!(self, $m, args);
             let b = first(&v)
                 .ok_or(Error::InvalidParams)?
@@ -99,8 +104,12 @@ impl Frame {
         Ok(())
     }
 
+    /// Fails with [`Error::Network`] when the browser can't reach the server, e.g. while the context is offline.
     pub(crate) async fn goto(&self, args: GotoArgs<'_, '_>) -> ArcResult<Option<Weak<Response>>> {
-        let v = send_message!(self, "goto", args);
+        let v = self
+            .send_goto(args)
+            .await
+            .map_err(responded_network_error)?;
         let guid = match as_only_guid(&v) {
             Some(g) => g,
             None => return Ok(None)
@@ -109,6 +118,30 @@ impl Frame {
         Ok(Some(r))
     }
 
+    /// Runs [`Frame::goto`] again, after a short delay, while it fails with a transient network error and `retries` are
+    /// left.
+    pub(crate) async fn goto_retrying(
+        &self,
+        args: GotoArgs<'_, '_>,
+        retries: u32
+    ) -> ArcResult<Option<Weak<Response>>> {
+        let mut attempt = 0;
+        loop {
+            match self.goto(args.clone()).await {
+                Err(e) if attempt < retries && is_transient(&e) => {
+                    attempt += 1;
+                    log::debug!("Retrying navigation to {} after {}", args.url, e);
+                    sleep(Duration::from_millis(100 * u64::from(attempt.min(5)))).await;
+                }
+                r => return r
+            }
+        }
+    }
+
+    async fn send_goto(&self, args: GotoArgs<'_, '_>) -> ArcResult<Arc<Value>> {
+        Ok(send_message!(self, "goto", args))
+    }
+
     pub(crate) async fn click(&self, args: ClickArgs<'_>) -> ArcResult<()> {
         let _ = send_message!(self, "click", args);
         Ok(())
@@ -130,7 +163,11 @@ impl Frame {
     }
 
     pub(crate) async fn focus(&self, selector: &str, timeout: Option<f64>) -> ArcResult<()> {
-        let args = SelectorTimeout { selector, timeout };
+        let args = SelectorTimeout {
+            selector,
+            timeout,
+            strict: None
+        };
         let _ = send_message!(self, "focus", args);
         Ok(())
     }
@@ -138,9 +175,14 @@ impl Frame {
     pub(crate) async fn text_content(
         &self,
         selector: &str,
-        timeout: Option<f64>
+        timeout: Option<f64>,
+        strict: Option<bool>
     ) -> ArcResult<Option<String>> {
-        let args = SelectorTimeout { selector, timeout };
+        let args = SelectorTimeout {
+            selector,
+            timeout,
+            strict
+        };
         let v = send_message!(self, "textContent", args);
         let s = maybe_only_str(&v)?;
         Ok(s.map(Into::into))
@@ -149,20 +191,46 @@ impl Frame {
     pub(crate) async fn inner_text(
         &self,
         selector: &str,
-        timeout: Option<f64>
+        timeout: Option<f64>,
+        strict: Option<bool>
     ) -> ArcResult<String> {
-        let args = SelectorTimeout { selector, timeout };
+        let args = SelectorTimeout {
+            selector,
+            timeout,
+            strict
+        };
         let v = send_message!(self, "innerText", args);
         let s = only_str(&v)?;
         Ok(s.into())
     }
 
+    /// The driver requires a timeout for this call, so the frame's default one is sent when none is given.
+    pub(crate) async fn aria_snapshot(
+        &self,
+        selector: &str,
+        timeout: Option<f64>
+    ) -> ArcResult<String> {
+        let timeout = Some(timeout.unwrap_or_else(|| self.default_timeout() as f64));
+        let args = SelectorTimeout {
+            selector,
+            timeout,
+            strict: None
+        };
+        let v = send_message!(self, "ariaSnapshot", args);
+        let s = only_str(&v)?;
+        Ok(s.into())
+    }
+
     pub(crate) async fn inner_html(
         &self,
         selector: &str,
         timeout: Option<f64>
     ) -> ArcResult<String> {
-        let args = SelectorTimeout { selector, timeout };
+        let args = SelectorTimeout {
+            selector,
+            timeout,
+            strict: None
+        };
         let v = send_message!(self, "innerHTML", args);
         let s = only_str(&v)?;
         Ok(s.into())
@@ -207,6 +275,17 @@ impl Frame {
         Ok(Some(e))
     }
 
+    pub(crate) async fn query_count(&self, selector: &str) -> ArcResult<usize> {
+        let mut args = HashMap::new();
+        args.insert("selector", selector);
+        let v = send_message!(self, "queryCount", args);
+        let n = first(&v)
+            .ok_or(Error::InvalidParams)?
+            .as_u64()
+            .ok_or(Error::InvalidParams)?;
+        Ok(n as usize)
+    }
+
     pub(crate) async fn query_selector_all(
         &self,
         selector: &str
@@ -237,15 +316,35 @@ impl Frame {
         &self,
         args: WaitForSelectorArgs<'_>
     ) -> ArcResult<Option<Weak<ElementHandle>>> {
-        let v = send_message!(self, "waitForSelector", args);
+        let check_actionability = args.check_actionability.unwrap_or_default();
+        let timeout = args.timeout;
+        let started = Instant::now();
+        let v = self
+            .send_wait_for_selector(args)
+            .await
+            .map_err(responded_timeout)?;
         let guid = match as_only_guid(&v) {
             Some(g) => g,
             None => return Ok(None)
         };
         let e = get_object!(self.context()?.lock().unwrap(), guid, ElementHandle)?;
+        if check_actionability {
+            // A trial click runs every actionability check without performing the action. Both steps share the
+            // timeout.
+            let args = ElementClickArgs {
+                timeout: remaining_timeout(timeout, started),
+                trial: Some(true),
+                ..ElementClickArgs::default()
+            };
+            upgrade(&e)?.click(args).await?;
+        }
         Ok(Some(e))
     }
 
+    async fn send_wait_for_selector(&self, args: WaitForSelectorArgs<'_>) -> ArcResult<Arc<Value>> {
+        Ok(send_message!(self, "waitForSelector", args))
+    }
+
     pub(crate) async fn title(&self) -> ArcResult<String> {
         let v = send_message!(self, "title", Map::new());
         let s = only_str(&v)?;
@@ -267,6 +366,11 @@ impl Frame {
         Ok(())
     }
 
+    pub(crate) async fn drag_and_drop(&self, args: DragAndDropArgs<'_, '_>) -> ArcResult<()> {
+        let _ = send_message!(self, "dragAndDrop", args);
+        Ok(())
+    }
+
     is_checked!(is_checked, "isChecked");
     is_checked!(is_disabled, "isDisabled");
     is_checked!(is_editable, "isEditable");
@@ -295,6 +399,14 @@ impl Frame {
         Ok(())
     }
 
+    pub(crate) async fn set_checked(&self, checked: bool, args: CheckArgs<'_>) -> ArcResult<()> {
+        if checked {
+            self.check(args).await
+        } else {
+            self.uncheck(args).await
+        }
+    }
+
     pub(crate) async fn add_script_tag(
         &self,
         args: AddScriptTagArgs<'_, '_, '_>
@@ -339,6 +451,7 @@ impl Frame {
             expression: &'a str,
             arg: Value
         }
+        validate_expression(expression)?;
         let arg = ser::to_value(&arg).map_err(Error::SerializationPwJson)?;
         let args = Args { expression, arg };
         let v = send_message!(self, "evaluateExpression", args);
@@ -356,6 +469,7 @@ impl Frame {
             expression: &'a str,
             arg: Value
         }
+        validate_expression(expression)?;
         let arg = ser::to_value(&arg).map_err(Error::SerializationPwJson)?;
         let args = Args { expression, arg };
         let v = send_message!(self, "evaluateExpressionHandle", args);
@@ -390,6 +504,7 @@ impl Frame {
             expression: &'b str,
             arg: Value
         }
+        validate_expression(expression)?;
         let arg = ser::to_value(&arg).map_err(Error::SerializationPwJson)?;
         let args = Args {
             selector,
@@ -418,6 +533,7 @@ impl Frame {
             expression: &'b str,
             arg: Value
         }
+        validate_expression(expression)?;
         let arg = ser::to_value(&arg).map_err(Error::SerializationPwJson)?;
         let args = Args {
             selector,
@@ -477,11 +593,171 @@ impl Frame {
         &self,
         args: WaitForFunctionArgs<'_>
     ) -> ArcResult<Weak<JsHandle>> {
-        let v = send_message!(self, "waitForFunction", args);
+        let v = self
+            .send_wait_for_function(args)
+            .await
+            .map_err(responded_timeout)?;
         let guid = only_guid(&v)?;
         let h = get_object!(self.context()?.lock().unwrap(), guid, JsHandle)?;
         Ok(h)
     }
+
+    async fn send_wait_for_function(&self, args: WaitForFunctionArgs<'_>) -> ArcResult<Arc<Value>> {
+        #[skip_serializing_none]
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Args<'a> {
+            expression: &'a str,
+            timeout: Option<f64>,
+            polling_interval: Option<f64>,
+            arg: Value
+        }
+        let WaitForFunctionArgs {
+            expression,
+            timeout,
+            polling,
+            arg
+        } = args;
+        let arg = match arg {
+            Some(x) => x,
+            None => ser::to_value(&None::<()>).map_err(Error::SerializationPwJson)?
+        };
+        let args = Args {
+            expression,
+            timeout,
+            polling_interval: polling.as_ref().and_then(Polling::interval),
+            arg
+        };
+        Ok(send_message!(self, "waitForFunction", args))
+    }
+
+    /// Same-document navigations (`history.pushState`, `history.replaceState`, `popstate` and hash changes) are reported by
+    /// the driver as `navigated` events without a new document, so they resolve this as well as full loads. A `timeout`
+    /// of `0` waits forever.
+    pub(crate) async fn wait_for_url(
+        &self,
+        url: &UrlMatcher,
+        timeout: Option<f64>
+    ) -> ArcResult<()> {
+        let mut rx = self.subscribe_event();
+        if url.is_match(&self.url()) {
+            return Ok(());
+        }
+        let timeout = match timeout {
+            Some(t) => t as u64,
+            None => self.default_timeout() as u64
+        };
+        let navigated = async {
+            loop {
+                match rx.recv().await {
+                    Ok(Evt::Navigated(e)) if e.error.is_none() && url.is_match(&e.url) => {
+                        break Ok(())
+                    }
+                    Ok(_) => continue,
+                    Err(e) => break Err(Error::Event(e))
+                }
+            }
+        };
+        if timeout == 0 {
+            return navigated.await.map_err(Arc::new);
+        }
+        let sleep = sleep(Duration::from_millis(timeout));
+        tokio::select! {
+            _ = sleep => Err(Arc::new(Error::Timeout)),
+            x = navigated => x.map_err(Arc::new)
+        }
+    }
+
+    pub(crate) async fn wait_for_load_state(
+        &self,
+        state: DocumentLoadState,
+        timeout: Option<f64>
+    ) -> ArcResult<()> {
+        let mut rx = self.subscribe_event();
+        let timeout = match timeout {
+            Some(t) => t as u64,
+            None => self.default_navigation_timeout() as u64
+        };
+        let sleep = sleep(Duration::from_millis(timeout));
+        tokio::select! {
+            _ = sleep => Err(Arc::new(Error::Timeout)),
+            x = self.reach_load_state(&mut rx, state) => x.map_err(Arc::new)
+        }
+    }
+
+    /// Waits on `rx` for `state` unless it has already been reached.
+    async fn reach_load_state(
+        &self,
+        rx: &mut broadcast::Receiver<Evt>,
+        state: DocumentLoadState
+    ) -> Result<(), Error> {
+        if self.var.lock().unwrap().load_states.contains(&state) {
+            return Ok(());
+        }
+        loop {
+            match rx.recv().await {
+                Ok(Evt::LoadState(x)) if x == state => return Ok(()),
+                Ok(_) => continue,
+                Err(e) => return Err(Error::Event(e))
+            }
+        }
+    }
+
+    /// Only navigations committed after this is first polled are considered, so it is meant to be joined with the
+    /// action that navigates. Resolves to the response of the last redirect of the new document, or `None` for
+    /// same-document navigations.
+    pub(crate) async fn expect_navigation(
+        &self,
+        url: Option<UrlMatcher>,
+        wait_until: DocumentLoadState,
+        timeout: Option<f64>
+    ) -> ArcResult<Option<Weak<Response>>> {
+        let mut rx = self.subscribe_event();
+        let timeout = match timeout {
+            Some(t) => t as u64,
+            None => self.default_navigation_timeout() as u64
+        };
+        let navigated = async {
+            let e = loop {
+                match rx.recv().await {
+                    Ok(Evt::Navigated(e)) => match &url {
+                        Some(u) if !u.is_match(&e.url) => continue,
+                        _ => break e
+                    },
+                    Ok(_) => continue,
+                    Err(e) => return Err(Arc::new(Error::Event(e)))
+                }
+            };
+            if let Some(error) = e.error {
+                return Err(Arc::new(Error::Navigation(error)));
+            }
+            let request = match e.new_document.and_then(|d| d.request) {
+                Some(OnlyGuid { guid }) => Some(get_object!(
+                    self.context()?.lock().unwrap(),
+                    &guid,
+                    Request
+                )?),
+                None => None
+            };
+            let response = match request {
+                Some(r) => {
+                    let mut request = upgrade(&r)?;
+                    while let Some(next) = request.redirected_to().and_then(|r| r.upgrade()) {
+                        request = next;
+                    }
+                    request.response().await?
+                }
+                None => None
+            };
+            self.reach_load_state(&mut rx, wait_until).await?;
+            Ok(response)
+        };
+        let sleep = sleep(Duration::from_millis(timeout));
+        tokio::select! {
+            _ = sleep => Err(Arc::new(Error::Timeout)),
+            x = navigated => x
+        }
+    }
 }
 
 // mutable
@@ -494,6 +770,20 @@ impl Frame {
 
     pub(crate) fn set_page(&self, page: Weak<Page>) { self.var.lock().unwrap().page = Some(page); }
 
+    fn default_timeout(&self) -> u32 {
+        self.page()
+            .and_then(|p| p.upgrade())
+            .map(|p| p.default_timeout())
+            .unwrap_or(30000)
+    }
+
+    fn default_navigation_timeout(&self) -> u32 {
+        self.page()
+            .and_then(|p| p.upgrade())
+            .map(|p| p.default_navigation_timeout())
+            .unwrap_or(30000)
+    }
+
     pub(crate) fn parent_frame(&self) -> Option<Weak<Frame>> { self.parent_frame.clone() }
 
     pub(crate) fn child_frames(&self) -> Vec<Weak<Frame>> {
@@ -596,8 +886,35 @@ enum Handle {
     Element(Weak<ElementHandle>)
 }
 
+/// Whether a navigation failure is caused by the connection or name resolution, which may work on the next attempt,
+/// rather than by the request itself, such as an aborted request or an invalid certificate.
+fn is_transient(e: &Error) -> bool {
+    // Chromium and Firefox codes, then WebKit messages
+    const TRANSIENT: &[&str] = &[
+        "CONNECTION_",
+        "NAME_NOT_RESOLVED",
+        "NAME_RESOLUTION_FAILED",
+        "NETWORK_CHANGED",
+        "ERR_TIMED_OUT",
+        "EMPTY_RESPONSE",
+        "INTERNET_DISCONNECTED",
+        "ADDRESS_UNREACHABLE",
+        "NS_ERROR_NET_RESET",
+        "NS_ERROR_NET_INTERRUPT",
+        "NS_ERROR_NET_TIMEOUT",
+        "NS_ERROR_UNKNOWN_HOST",
+        "Could not connect",
+        "network connection was lost",
+        "hostname could not be found"
+    ];
+    match e {
+        Error::Network(m) => TRANSIENT.iter().any(|x| m.contains(x)),
+        _ => false
+    }
+}
+
 #[skip_serializing_none]
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct GotoArgs<'a, 'b> {
     url: &'a str,
@@ -607,9 +924,14 @@ pub(crate) struct GotoArgs<'a, 'b> {
 }
 
 impl<'a> GotoArgs<'a, '_> {
+    /// An empty `url` is taken as `about:blank`, which the driver would otherwise reject as invalid.
     pub(crate) fn new(url: &'a str) -> Self {
         Self {
-            url,
+            url: if url.trim().is_empty() {
+                "about:blank"
+            } else {
+                url
+            },
             timeout: Some(30000.0),
             wait_until: None,
             referer: None
@@ -631,7 +953,8 @@ pub(crate) struct ClickArgs<'a> {
     pub(crate) timeout: Option<f64>,
     pub(crate) force: Option<bool>,
     pub(crate) no_wait_after: Option<bool>,
-    pub(crate) trial: Option<bool>
+    pub(crate) trial: Option<bool>,
+    pub(crate) strict: Option<bool>
 }
 
 impl<'a> ClickArgs<'a> {
@@ -647,7 +970,8 @@ impl<'a> ClickArgs<'a> {
             timeout: Some(30000.0),
             force: None,
             no_wait_after: None,
-            trial: None
+            trial: None,
+            strict: None
         }
     }
 }
@@ -658,7 +982,10 @@ impl<'a> ClickArgs<'a> {
 pub(crate) struct WaitForSelectorArgs<'a> {
     selector: &'a str,
     pub(crate) timeout: Option<f64>,
-    pub(crate) state: Option<FrameState>
+    pub(crate) state: Option<FrameState>,
+    pub(crate) strict: Option<bool>,
+    #[serde(skip)]
+    pub(crate) check_actionability: Option<bool>
 }
 
 impl<'a> WaitForSelectorArgs<'a> {
@@ -666,7 +993,9 @@ impl<'a> WaitForSelectorArgs<'a> {
         Self {
             selector,
             timeout: Some(30000.0),
-            state: None
+            state: None,
+            strict: None,
+            check_actionability: None
         }
     }
 }
@@ -682,6 +1011,7 @@ pub enum FrameState {
 
 macro_rules! type_args {
     ($t:ident, $f:ident) => {
+        #[skip_serializing_none]
         #[derive(Serialize)]
         #[serde(rename_all = "camelCase")]
         pub(crate) struct $t<'a, 'b> {
@@ -689,7 +1019,8 @@ macro_rules! type_args {
             $f: &'b str,
             pub(crate) delay: Option<f64>,
             pub(crate) timeout: Option<f64>,
-            pub(crate) no_wait_after: Option<bool>
+            pub(crate) no_wait_after: Option<bool>,
+            pub(crate) strict: Option<bool>
         }
 
         impl<'a, 'b> $t<'a, 'b> {
@@ -699,7 +1030,8 @@ macro_rules! type_args {
                     $f,
                     delay: None,
                     timeout: Some(30000.0),
-                    no_wait_after: None
+                    no_wait_after: None,
+                    strict: None
                 }
             }
         }
@@ -734,6 +1066,33 @@ impl<'a> HoverArgs<'a> {
     }
 }
 
+#[skip_serializing_none]
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DragAndDropArgs<'a, 'b> {
+    source: &'a str,
+    target: &'b str,
+    pub(crate) source_position: Option<Position>,
+    pub(crate) target_position: Option<Position>,
+    pub(crate) timeout: Option<f64>,
+    pub(crate) force: Option<bool>,
+    pub(crate) trial: Option<bool>
+}
+
+impl<'a, 'b> DragAndDropArgs<'a, 'b> {
+    pub(crate) fn new(source: &'a str, target: &'b str) -> Self {
+        Self {
+            source,
+            target,
+            source_position: None,
+            target_position: None,
+            timeout: Some(30000.0),
+            force: None,
+            trial: None
+        }
+    }
+}
+
 #[skip_serializing_none]
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -787,7 +1146,8 @@ pub(crate) struct FillArgs<'a, 'b> {
     selector: &'a str,
     value: &'b str,
     pub(crate) timeout: Option<f64>,
-    pub(crate) no_wait_after: Option<bool>
+    pub(crate) no_wait_after: Option<bool>,
+    pub(crate) strict: Option<bool>
 }
 
 impl<'a, 'b> FillArgs<'a, 'b> {
@@ -796,7 +1156,8 @@ impl<'a, 'b> FillArgs<'a, 'b> {
             selector,
             value,
             timeout: Some(30000.0),
-            no_wait_after: None
+            no_wait_after: None,
+            strict: None
         }
     }
 }
@@ -806,7 +1167,8 @@ impl<'a, 'b> FillArgs<'a, 'b> {
 #[serde(rename_all = "camelCase")]
 struct SelectorTimeout<'a> {
     selector: &'a str,
-    timeout: Option<f64>
+    timeout: Option<f64>,
+    strict: Option<bool>
 }
 
 #[skip_serializing_none]
@@ -883,8 +1245,8 @@ impl<'a> SelectOptionArgs<'a> {
 #[serde(rename_all = "camelCase")]
 pub(crate) struct SetInputFilesArgs<'a> {
     selector: &'a str,
-
-    pub(crate) files: Vec<File>,
+    #[serde(flatten)]
+    pub(crate) files: InputFiles,
     pub(crate) timeout: Option<f64>,
     pub(crate) no_wait_after: Option<bool>
 }
@@ -893,21 +1255,17 @@ impl<'a> SetInputFilesArgs<'a> {
     pub(crate) fn new(selector: &'a str) -> Self {
         Self {
             selector,
-            files: Vec::new(),
+            files: InputFiles::default(),
             timeout: Some(30000.0),
             no_wait_after: None
         }
     }
 }
 
-#[skip_serializing_none]
-#[derive(Serialize)]
-#[serde(rename_all = "camelCase")]
 pub(crate) struct WaitForFunctionArgs<'a> {
     expression: &'a str,
     pub(crate) timeout: Option<f64>,
     pub(crate) polling: Option<Polling>,
-    // XXX
     pub(crate) arg: Option<Value>
 }
 
@@ -916,14 +1274,12 @@ pub enum Polling {
     Millis(u32)
 }
 
-impl Serialize for Polling {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::ser::Serializer
-    {
+impl Polling {
+    /// The driver polls in `requestAnimationFrame` callbacks unless it is given an interval.
+    fn interval(&self) -> Option<f64> {
         match self {
-            Self::Millis(x) => x.serialize(serializer),
-            Self::RequestAnimationFrame => "raf".serialize(serializer)
+            Self::Millis(x) => Some(f64::from(*x)),
+            Self::RequestAnimationFrame => None
         }
     }
 }
@@ -960,8 +1316,7 @@ pub struct FrameNavigatedEvent {
 #[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Document {
-    #[serde(default)]
-    request: Value
+    request: Option<OnlyGuid>
 }
 
 #[cfg(test)]
@@ -975,6 +1330,22 @@ mod tests {
         playwright::Playwright
     };
 
+    #[test]
+    fn transient_network_errors() {
+        let network = |m: &str| Error::Network(m.into());
+        assert!(is_transient(&network(
+            "net::ERR_CONNECTION_RESET at http://a/"
+        )));
+        assert!(is_transient(&network("NS_ERROR_UNKNOWN_HOST")));
+        assert!(is_transient(&network("Could not connect to server")));
+        assert!(is_transient(&network(
+            "A server with the specified hostname could not be found."
+        )));
+        assert!(!is_transient(&network("net::ERR_ABORTED at http://a/")));
+        assert!(!is_transient(&network("net::ERR_CERT_AUTHORITY_INVALID")));
+        assert!(!is_transient(&Error::Timeout));
+    }
+
     crate::runtime_test!(eval_handle, {
         let driver = Driver::install().unwrap();
         let conn = Connection::run(&driver).unwrap();
@@ -1003,10 +1374,8 @@ mod tests {
     });
 
     #[test]
-    fn serialize_enum() {
-        let s = serde_json::to_string(&Polling::Millis(3)).unwrap();
-        assert_eq!(s, "3");
-        let s = serde_json::to_string(&Polling::RequestAnimationFrame).unwrap();
-        assert_eq!(s, r#""raf""#);
+    fn polling_interval() {
+        assert_eq!(Polling::Millis(100).interval(), Some(100.));
+        assert_eq!(Polling::RequestAnimationFrame.interval(), None);
     }
 }
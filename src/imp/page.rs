@@ -1,22 +1,30 @@
 use crate::imp::{
+    binding_call::{BindingCall, BindingCallback, Bindings},
+    browser::NewContextArgs,
     browser_context::BrowserContext,
+    browser_type::RecordVideo,
+    cdp_session::CdpSession,
     console_message::ConsoleMessage,
     core::*,
+    dialog::{Dialog, DialogHandler},
     download::Download,
     element_handle::may_save,
     file_hooser::FileChooser,
-    frame::Frame,
+    frame::{Frame, GotoArgs},
     prelude::*,
     request::Request,
     response::Response,
+    route::{self, ContinueArgs, Route, RouteEntry, RouteHandler},
     utils::{
-        ColorScheme, DocumentLoadState, FloatRect, Header, Length, MouseButton, PdfMargins,
-        ScreenshotType, Viewport
+        extra_http_headers, validate_expression, ColorScheme, DocumentLoadState, FloatRect, Header,
+        Length, MouseButton, PdfMargins, ScreenshotType, UrlMatcher, Viewport, VisionDeficiency,
+        WindowBounds
     },
     video::Video,
     websocket::WebSocket,
     worker::Worker
 };
+use tokio::sync::mpsc;
 
 #[derive(Debug)]
 pub(crate) struct Page {
@@ -34,7 +42,14 @@ pub(crate) struct Variable {
     timeout: Option<u32>,
     navigation_timeout: Option<u32>,
     workers: Vec<Weak<Worker>>,
-    video: Option<Video>
+    video: Option<Video>,
+    bindings: Bindings,
+    routes: Vec<RouteEntry>,
+    dialogs: Option<mpsc::UnboundedSender<Weak<Dialog>>>,
+    cdp_session: Option<Weak<CdpSession>>,
+    isolated_world: Option<i64>,
+    owns_context: bool,
+    records_segment: bool
 }
 
 macro_rules! navigation {
@@ -114,10 +129,80 @@ impl Page {
 
     pub(crate) fn main_frame(&self) -> Weak<Frame> { self.main_frame.clone() }
 
-    navigation! {reload, "reload"}
+    navigation! {soft_reload, "reload"}
     navigation! {go_back, "goBack"}
     navigation! {go_forward, "goForward"}
 
+    pub(crate) async fn reload(&self, args: ReloadArgs) -> ArcResult<Option<Weak<Response>>> {
+        if !args.bypass_cache.unwrap_or_default() {
+            return self.soft_reload(args).await;
+        }
+        match self.cdp_session().await {
+            Ok(session) => self.reload_ignoring_cache(&session, args).await,
+            Err(e) if matches!(*e, Error::CdpNotSupported(_)) => {
+                self.reload_revalidating(args).await
+            }
+            Err(e) => Err(e)
+        }
+    }
+
+    /// Chromium can skip its cache for a single reload, which is then awaited like any other navigation.
+    async fn reload_ignoring_cache(
+        &self,
+        session: &CdpSession,
+        args: ReloadArgs
+    ) -> ArcResult<Option<Weak<Response>>> {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Params {
+            ignore_cache: bool
+        }
+        let frame = upgrade(&self.main_frame)?;
+        let wait_until = args.wait_until.unwrap_or(DocumentLoadState::Load);
+        let (response, _) = futures::try_join!(
+            frame.expect_navigation(None, wait_until, args.timeout),
+            session.send("Page.reload", Params { ignore_cache: true })
+        )?;
+        Ok(response)
+    }
+
+    /// Other browsers have no such reload, so the document request is revalidated with no-cache headers by a route that
+    /// only matches its URL and only lives for this reload.
+    async fn reload_revalidating(&self, args: ReloadArgs) -> ArcResult<Option<Weak<Response>>> {
+        let url = upgrade(&self.main_frame)?.url();
+        let pattern = format!("^{}$", regex::escape(&url));
+        let matcher =
+            UrlMatcher::from(regex::Regex::new(&pattern).map_err(|_| Error::InvalidParams)?);
+        let handler: RouteHandler = Arc::new(|route: Weak<Route>| {
+            Box::pin(async move {
+                let route = match route.upgrade() {
+                    Some(r) => r,
+                    None => return
+                };
+                let mut headers = route
+                    .request()
+                    .upgrade()
+                    .map(|r| r.headers().clone())
+                    .unwrap_or_default();
+                headers.insert("cache-control".into(), "no-cache".into());
+                headers.insert("pragma".into(), "no-cache".into());
+                let args = ContinueArgs {
+                    headers: Some(headers.into_iter().map(Header::from).collect()),
+                    ..ContinueArgs::default()
+                };
+                if let Err(e) = route.r#continue(args).await {
+                    log::trace!("Failed to continue the reload: {:?}", e);
+                }
+            })
+        });
+        self.route(matcher, handler.clone()).await?;
+        let result = self.soft_reload(args).await;
+        if let Err(e) = self.unroute_handler(&handler).await {
+            log::trace!("Failed to remove the reload route: {:?}", e);
+        }
+        result
+    }
+
     pub(crate) async fn key_down(&self, key: &str) -> Result<(), Arc<Error>> {
         let mut args = HashMap::new();
         args.insert("key", key);
@@ -132,7 +217,7 @@ impl Page {
         Ok(())
     }
 
-    pub(crate) async fn key_input_text(&self, text: &str) -> Result<(), Arc<Error>> {
+    pub(crate) async fn key_insert_text(&self, text: &str) -> Result<(), Arc<Error>> {
         let mut args = HashMap::new();
         args.insert("text", text);
         let _ = send_message!(self, "keyboardInsertText", args);
@@ -148,19 +233,19 @@ impl Page {
             delay: Option<f64>
         }
         let args = Args { text, delay };
-        let _ = send_message!(self, "keyboardInsertText", args);
+        let _ = send_message!(self, "keyboardType", args);
         Ok(())
     }
 
-    pub(crate) async fn key_press(&self, text: &str, delay: Option<f64>) -> Result<(), Arc<Error>> {
+    pub(crate) async fn key_press(&self, key: &str, delay: Option<f64>) -> Result<(), Arc<Error>> {
         #[skip_serializing_none]
         #[derive(Serialize)]
         #[serde(rename_all = "camelCase")]
         struct Args<'b> {
-            text: &'b str,
+            key: &'b str,
             delay: Option<f64>
         }
-        let args = Args { text, delay };
+        let args = Args { key, delay };
         let _ = send_message!(self, "keyboardPress", args);
         Ok(())
     }
@@ -199,6 +284,18 @@ impl Page {
     mouse_down! {mouse_down, "mouseDown"}
     mouse_down! {mouse_up, "mouseUp"}
 
+    pub(crate) async fn mouse_wheel(&self, delta_x: f64, delta_y: f64) -> Result<(), Arc<Error>> {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Args {
+            delta_x: f64,
+            delta_y: f64
+        }
+        let args = Args { delta_x, delta_y };
+        let _ = send_message!(self, "mouseWheel", args);
+        Ok(())
+    }
+
     pub(crate) async fn mouse_click(&self, args: MouseClickArgs) -> Result<(), Arc<Error>> {
         let _ = send_message!(self, "mouseClick", args);
         Ok(())
@@ -242,6 +339,13 @@ impl Page {
         &self,
         args: PdfArgs<'_, '_, '_, '_, '_, '_, '_, '_, '_, '_>
     ) -> ArcResult<Vec<u8>> {
+        let browser = self
+            .browser_context()
+            .upgrade()
+            .and_then(|c| c.browser_name());
+        if let Some(name) = browser.filter(|n| n != "chromium") {
+            return Err(Error::PdfNotSupported(name).into());
+        }
         let path = args.path.clone();
         let v = send_message!(self, "pdf", args);
         let b64 = only_str(&v)?;
@@ -250,6 +354,157 @@ impl Page {
         Ok(bytes)
     }
 
+    /// The session is kept for the lifetime of the page, as detaching it would revert what was emulated through it.
+    async fn cdp_session(&self) -> ArcResult<Arc<CdpSession>> {
+        let cached = self.var.lock().unwrap().cdp_session.clone();
+        if let Some(s) = cached.as_ref().and_then(Weak::upgrade) {
+            return Ok(s);
+        }
+        let context = upgrade(&self.browser_context)?;
+        if let Some(name) = context.browser_name().filter(|n| n != "chromium") {
+            return Err(Error::CdpNotSupported(name).into());
+        }
+        let session = context.new_cdp_session(self).await?;
+        self.var.lock().unwrap().cdp_session = Some(session.clone());
+        Ok(upgrade(&session)?)
+    }
+
+    pub(crate) async fn set_vision_deficiency(&self, x: VisionDeficiency) -> ArcResult<()> {
+        #[derive(Serialize)]
+        struct Params {
+            r#type: VisionDeficiency
+        }
+        let session = self.cdp_session().await?;
+        let params = Params { r#type: x };
+        session
+            .send("Emulation.setEmulatedVisionDeficiency", params)
+            .await?;
+        Ok(())
+    }
+
+    /// Evaluates in an isolated world of the main frame, which shares the DOM but not the JavaScript globals of the
+    /// page. The world is created through CDP, so this is only available in Chromium, and kept until the document
+    /// changes.
+    pub(crate) async fn evaluate_in_isolated_world<U>(
+        &self,
+        expression: &str,
+        arg: Option<Value>
+    ) -> ArcResult<U>
+    where
+        U: DeserializeOwned
+    {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Params {
+            expression: String,
+            context_id: i64,
+            return_by_value: bool,
+            await_promise: bool
+        }
+        validate_expression(expression)?;
+        let session = self.cdp_session().await?;
+        let arg = serde_json::to_string(&arg).map_err(Error::Serde)?;
+        // The argument is passed as a JSON literal, which is also valid JavaScript
+        let expression = format!(
+            r#"(async () => {{
+    const value = (
+{}
+    );
+    return typeof value === 'function' ? value({}) : value;
+}})()"#,
+            expression, arg
+        );
+        let cached = self.var.lock().unwrap().isolated_world;
+        let mut context_id = match cached {
+            Some(x) => x,
+            None => self.create_isolated_world(&session).await?
+        };
+        loop {
+            let params = Params {
+                expression: expression.clone(),
+                context_id,
+                return_by_value: true,
+                await_promise: true
+            };
+            match session.send("Runtime.evaluate", params).await {
+                Ok(v) => return isolated_world_value(v),
+                // The world went away with the document it was created for
+                Err(e) if cached == Some(context_id) && is_missing_context(&e) => {
+                    context_id = self.create_isolated_world(&session).await?;
+                }
+                Err(e) => return Err(e)
+            }
+        }
+    }
+
+    async fn create_isolated_world(&self, session: &CdpSession) -> ArcResult<i64> {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Params {
+            frame_id: String,
+            world_name: &'static str
+        }
+        let tree = session.send("Page.getFrameTree", Map::new()).await?;
+        let frame_id = tree
+            .pointer("/frameTree/frame/id")
+            .and_then(Value::as_str)
+            .ok_or(Error::InvalidParams)?
+            .to_owned();
+        let params = Params {
+            frame_id,
+            world_name: "playwright-rust"
+        };
+        let v = session.send("Page.createIsolatedWorld", params).await?;
+        let id = v
+            .get("executionContextId")
+            .and_then(Value::as_i64)
+            .ok_or(Error::InvalidParams)?;
+        self.var.lock().unwrap().isolated_world = Some(id);
+        Ok(id)
+    }
+
+    async fn window_id(&self, session: &CdpSession) -> ArcResult<i64> {
+        let v = session
+            .send("Browser.getWindowForTarget", Map::new())
+            .await?;
+        let id = v
+            .get("windowId")
+            .and_then(Value::as_i64)
+            .ok_or(Error::InvalidParams)?;
+        Ok(id)
+    }
+
+    pub(crate) async fn window_bounds(&self) -> ArcResult<WindowBounds> {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Params {
+            window_id: i64
+        }
+        let session = self.cdp_session().await?;
+        let window_id = self.window_id(&session).await?;
+        let v = session
+            .send("Browser.getWindowBounds", Params { window_id })
+            .await?;
+        let bounds = v.get("bounds").cloned().ok_or(Error::InvalidParams)?;
+        let bounds = serde_json::from_value(bounds).map_err(Error::Serde)?;
+        Ok(bounds)
+    }
+
+    pub(crate) async fn set_window_bounds(&self, bounds: WindowBounds) -> ArcResult<()> {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Params {
+            window_id: i64,
+            bounds: WindowBounds
+        }
+        let session = self.cdp_session().await?;
+        let window_id = self.window_id(&session).await?;
+        session
+            .send("Browser.setWindowBounds", Params { window_id, bounds })
+            .await?;
+        Ok(())
+    }
+
     pub(crate) async fn close(&self, run_before_unload: Option<bool>) -> Result<(), Arc<Error>> {
         #[skip_serializing_none]
         #[derive(Serialize)]
@@ -259,18 +514,69 @@ impl Page {
         }
         let args = Args { run_before_unload };
         let _ = send_message!(self, "close", args);
+        let owns_context = self.var.lock().unwrap().owns_context;
+        if owns_context {
+            if let Some(c) = self.browser_context.upgrade() {
+                c.close().await?;
+            }
+        }
         Ok(())
     }
 
-    pub(crate) async fn screenshot(&self, args: ScreenshotArgs) -> ArcResult<Vec<u8>> {
+    /// The driver only records whole pages of a context created with `recordVideo`, so a segment is recorded by a copy
+    /// of this page in a context of its own. It starts with the storage state, viewport and URL of this page.
+    pub(crate) async fn start_video(&self, record_video: RecordVideo<'_>) -> ArcResult<Weak<Page>> {
+        let context = upgrade(&self.browser_context)?;
+        let browser = context.browser().ok_or(Error::ObjectNotFound)?;
+        let mut args = NewContextArgs::default();
+        args.record_video = Some(record_video);
+        args.viewport = Some(self.viewport_size());
+        args.storage_state = Some(context.storage_state(None).await?);
+        let p = upgrade(&browser)?.new_page(args).await?;
+        let url = upgrade(&self.main_frame)?.url();
+        if url != "about:blank" {
+            let frame = upgrade(&upgrade(&p)?.main_frame())?;
+            frame.goto(GotoArgs::new(&url)).await?;
+        }
+        upgrade(&p)?.set_records_segment();
+        Ok(p)
+    }
+
+    /// Closing the context finishes writing the video, so the path is complete once this returns.
+    pub(crate) async fn stop_video(&self) -> ArcResult<PathBuf> {
+        if !self.var.lock().unwrap().records_segment {
+            return Err(Error::NotRecording.into());
+        }
+        self.close(None).await?;
+        let video = self.video().ok_or(Error::NotRecording)?;
+        Ok(video.path()?)
+    }
+
+    pub(crate) async fn screenshot(&self, mut args: ScreenshotArgs) -> ArcResult<Vec<u8>> {
+        args.timeout.get_or_insert(self.default_timeout() as f64);
         let path = args.path.clone();
-        let v = send_message!(self, "screenshot", args);
+        let v = match args.viewport.take() {
+            Some(viewport) => {
+                // Pages without a viewport can't go back to having none
+                let previous = self.viewport_size().ok_or(Error::NoViewport)?;
+                self.set_viewport_size(viewport).await?;
+                let v = self.send_screenshot(args).await;
+                self.set_viewport_size(previous).await?;
+                v
+            }
+            None => self.send_screenshot(args).await
+        }
+        .map_err(responded_timeout)?;
         let b64 = only_str(&v)?;
         let bytes = base64::decode(b64).map_err(Error::InvalidBase64)?;
         may_save(path.as_deref(), &bytes)?;
         Ok(bytes)
     }
 
+    async fn send_screenshot(&self, args: ScreenshotArgs) -> ArcResult<Arc<Value>> {
+        Ok(send_message!(self, "screenshot", args))
+    }
+
     pub(crate) async fn emulate_media(&self, args: EmulateMediaArgs) -> ArcResult<()> {
         let _ = send_message!(self, "emulateMedia", args);
         Ok(())
@@ -296,15 +602,105 @@ impl Page {
             headers: Vec<Header>
         }
         let args = Args {
-            headers: headers.into_iter().map(Header::from).collect()
+            headers: extra_http_headers(headers)
         };
         let _ = send_message!(self, "setExtraHTTPHeaders", args);
         Ok(())
     }
+
+    pub(crate) async fn expose_binding(
+        &self,
+        name: &str,
+        needs_handle: bool,
+        callback: BindingCallback
+    ) -> ArcResult<()> {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Args<'a> {
+            name: &'a str,
+            needs_handle: bool
+        }
+        let unregistered =
+            self.var
+                .lock()
+                .unwrap()
+                .bindings
+                .insert(name, needs_handle, callback)?;
+        if unregistered {
+            let args = Args { name, needs_handle };
+            let _ = send_message!(self, "exposeBinding", args);
+            self.var
+                .lock()
+                .unwrap()
+                .bindings
+                .set_registered(name, needs_handle);
+        }
+        Ok(())
+    }
+
+    /// The function stays in the frames' globals, so that exposing the name again makes it work anew, but calls are
+    /// rejected until then.
+    pub(crate) fn remove_exposed_binding(&self, name: &str) {
+        self.var.lock().unwrap().bindings.remove(name);
+    }
+
+    /// The handler is only kept once the driver has taken the new patterns.
+    pub(crate) async fn route(&self, matcher: UrlMatcher, handler: RouteHandler) -> ArcResult<()> {
+        let entry = RouteEntry { matcher, handler };
+        let patterns = {
+            let mut routes = self.var.lock().unwrap().routes.clone();
+            routes.push(entry.clone());
+            route::interception_patterns(&routes)
+        };
+        let _ = send_message!(self, "setNetworkInterceptionPatterns", patterns);
+        self.var.lock().unwrap().routes.push(entry);
+        Ok(())
+    }
+
+    /// Removes every handler registered for `matcher`.
+    pub(crate) async fn unroute(&self, matcher: &UrlMatcher) -> ArcResult<()> {
+        let patterns = {
+            let routes = &mut self.var.lock().unwrap().routes;
+            routes.retain(|r| &r.matcher != matcher);
+            route::interception_patterns(routes)
+        };
+        let _ = send_message!(self, "setNetworkInterceptionPatterns", patterns);
+        Ok(())
+    }
+
+    /// Removes `handler` only, leaving any other handler registered for the same URL.
+    async fn unroute_handler(&self, handler: &RouteHandler) -> ArcResult<()> {
+        let patterns = {
+            let routes = &mut self.var.lock().unwrap().routes;
+            routes.retain(|r| !Arc::ptr_eq(&r.handler, handler));
+            route::interception_patterns(routes)
+        };
+        let _ = send_message!(self, "setNetworkInterceptionPatterns", patterns);
+        Ok(())
+    }
+
+    /// Once a handler is set the driver stops dismissing the dialogs of this page by itself.
+    pub(crate) async fn set_dialog_handler(&self, handler: DialogHandler) -> ArcResult<()> {
+        self.var.lock().unwrap().dialogs = Some(Dialog::serve(handler));
+        self.update_subscription("dialog", true).await
+    }
+
+    pub(crate) async fn update_subscription(&self, event: &str, enabled: bool) -> ArcResult<()> {
+        let mut args = Map::new();
+        args.insert("event".into(), event.into());
+        args.insert("enabled".into(), enabled.into());
+        let _ = send_message!(self, "updateSubscription", args);
+        Ok(())
+    }
 }
 
 // mutable
 impl Page {
+    /// Marks the context as created for this page alone, to be closed with it.
+    pub(crate) fn set_owns_context(&self) { self.var.lock().unwrap().owns_context = true; }
+
+    fn set_records_segment(&self) { self.var.lock().unwrap().records_segment = true; }
+
     pub(crate) fn viewport_size(&self) -> Option<Viewport> {
         self.var.lock().unwrap().viewport.clone()
     }
@@ -336,8 +732,12 @@ impl Page {
         this.unwrap_or_else(parent)
     }
 
+    /// Falls back to the default timeout of the page, then to the navigation timeout of the context, as the driver does.
     pub(crate) fn default_navigation_timeout(&self) -> u32 {
-        let this = self.var.lock().unwrap().navigation_timeout;
+        let this = {
+            let var = self.var.lock().unwrap();
+            var.navigation_timeout.or(var.timeout)
+        };
         let parent = || {
             self.browser_context
                 .upgrade()
@@ -478,6 +878,22 @@ impl Page {
         let artifact = get_object!(ctx, &guid, Artifact)?;
         // TODO: set_is_remote
         // artifactObject._isRemote = !!this._browserContext._browser && this._browserContext._browser._isRemote;
+        let download_path = self
+            .browser_context()
+            .upgrade()
+            .and_then(|c| c.download_path());
+        if let Some(dir) = download_path {
+            let (artifact, path) = (artifact.clone(), dir.join(&suggested_filename));
+            spawn(async move {
+                let saved = match artifact.upgrade() {
+                    Some(a) => a.save_as(&path).await,
+                    None => return
+                };
+                if let Err(e) = saved {
+                    log::trace!("Failed to save download to {:?}: {:?}", path, e);
+                }
+            });
+        }
         let download = Download::new(artifact, url, suggested_filename);
         self.emit_event(Evt::Download(Arc::new(download)));
         Ok(())
@@ -493,6 +909,55 @@ impl Page {
         Ok(())
     }
 
+    fn on_binding(&self, ctx: &Context, params: Map<String, Value>) -> Result<(), Error> {
+        let first = first_object(&params).ok_or(Error::InvalidParams)?;
+        let OnlyGuid { guid } = serde_json::from_value((*first).clone())?;
+        let call = get_object!(ctx, &guid, BindingCall)?;
+        self.handle_binding(call)
+    }
+
+    /// Calls no page function matches go on to the functions exposed by the browser context.
+    fn handle_binding(&self, call: Weak<BindingCall>) -> Result<(), Error> {
+        let name = upgrade(&call)?.name().to_owned();
+        let tx = self.var.lock().unwrap().bindings.get(&name);
+        match (tx, self.browser_context.upgrade()) {
+            (Some(tx), _) => {
+                let _ = tx.send(call);
+            }
+            (None, Some(c)) => c.handle_binding(call)?,
+            (None, None) => BindingCall::reject_removed(call)
+        }
+        Ok(())
+    }
+
+    /// Dialogs are dispatched by the browser context.
+    pub(crate) fn on_dialog(&self, dialog: Weak<Dialog>) {
+        self.emit_event(Evt::Dialog(dialog.clone()));
+        let tx = self.var.lock().unwrap().dialogs.clone();
+        match tx {
+            Some(tx) => {
+                if let Err(e) = tx.send(dialog) {
+                    Dialog::close_unhandled(e.0);
+                }
+            }
+            None => Dialog::close_unhandled(dialog)
+        }
+    }
+
+    /// Requests no page handler matches go on to the handlers of the browser context.
+    fn on_route(&self, ctx: &Context, params: Map<String, Value>) -> Result<(), Error> {
+        let first = first_object(&params).ok_or(Error::InvalidParams)?;
+        let OnlyGuid { guid } = serde_json::from_value((*first).clone())?;
+        let route = get_object!(ctx, &guid, Route)?;
+        let request = upgrade(&upgrade(&route)?.request())?;
+        let handler = route::find_handler(&self.var.lock().unwrap().routes, request.url());
+        match (handler, self.browser_context.upgrade()) {
+            (None, Some(c)) => c.handle_route(route)?,
+            (handler, _) => Route::handle(route, handler)
+        }
+        Ok(())
+    }
+
     fn on_file_chooser(&self, ctx: &Context, params: Map<String, Value>) -> Result<(), Error> {
         #[derive(Deserialize)]
         #[serde(rename_all = "camelCase")]
@@ -507,11 +972,32 @@ impl Page {
         let element = get_object!(ctx, &guid, ElementHandle)?;
         let this = get_object!(ctx, self.guid(), Page)?;
         let file_chooser = FileChooser::new(this, element, is_multiple);
-        // self.emit_event(Evt::FileChooser(file_chooser));
+        self.emit_event(Evt::FileChooser(file_chooser));
         Ok(())
     }
 }
 
+fn is_missing_context(e: &Error) -> bool {
+    matches!(e, Error::ErrorResponded(m) if m.message.contains("Cannot find context"))
+}
+
+/// Reads the result of `Runtime.evaluate`, which reports exceptions thrown by the expression in `exceptionDetails`.
+fn isolated_world_value<U>(v: Value) -> ArcResult<U>
+where
+    U: DeserializeOwned
+{
+    if let Some(details) = v.get("exceptionDetails") {
+        let message = details
+            .pointer("/exception/description")
+            .or_else(|| details.get("text"))
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        return Err(Error::Evaluation(message.to_owned()).into());
+    }
+    let value = v.pointer("/result/value").cloned().unwrap_or_default();
+    Ok(serde_json::from_value(value).map_err(Error::Serde)?)
+}
+
 impl RemoteObject for Page {
     fn channel(&self) -> &ChannelOwner { &self.channel }
     fn channel_mut(&mut self) -> &mut ChannelOwner { &mut self.channel }
@@ -537,12 +1023,6 @@ impl RemoteObject for Page {
             "load" => self.emit_event(Evt::Load),
             "domcontentloaded" => self.emit_event(Evt::DomContentLoaded),
             "crash" => self.emit_event(Evt::Crash),
-            "console" => {
-                let first = first_object(&params).ok_or(Error::InvalidParams)?;
-                let OnlyGuid { guid } = serde_json::from_value((*first).clone())?;
-                let console = get_object!(ctx, &guid, ConsoleMessage)?;
-                self.emit_event(Evt::Console(console));
-            }
             "request" => {
                 let first = first_object(&params).ok_or(Error::InvalidParams)?;
                 let OnlyGuid { guid } = serde_json::from_value((*first).clone())?;
@@ -577,7 +1057,9 @@ impl RemoteObject for Page {
             }
             "download" => self.on_download(ctx, params)?,
             "video" => self.on_video(ctx, params)?,
-            "filechooser" => self.on_file_chooser(ctx, params)?,
+            "fileChooser" => self.on_file_chooser(ctx, params)?,
+            "bindingCall" => self.on_binding(ctx, params)?,
+            "route" => self.on_route(ctx, params)?,
             _ => {}
         }
         Ok(())
@@ -588,12 +1070,10 @@ impl RemoteObject for Page {
 pub(crate) enum Evt {
     Close,
     Crash,
-    Console(Weak<ConsoleMessage>),
-    /// Not Implemented Yet
-    Dialog,
+    Console(Arc<ConsoleMessage>),
+    Dialog(Weak<Dialog>),
     Download(Arc<Download>),
-    /// Not Implemented Yet
-    // FileChooser(FileChooser),
+    FileChooser(FileChooser),
     DomContentLoaded,
     /// Not Implemented Yet
     PageError,
@@ -624,7 +1104,7 @@ pub enum EventType {
     Console,
     Dialog,
     Download,
-    // FileChooser,
+    FileChooser,
     DomContentLoaded,
     PageError,
     Request,
@@ -649,9 +1129,9 @@ impl IsEvent for Evt {
             Self::Close => EventType::Close,
             Self::Crash => EventType::Crash,
             Self::Console(_) => EventType::Console,
-            Self::Dialog => EventType::Dialog,
+            Self::Dialog(_) => EventType::Dialog,
             Self::Download(_) => EventType::Download,
-            // Self::FileChooser(_) => EventType::FileChooser,
+            Self::FileChooser(_) => EventType::FileChooser,
             Self::DomContentLoaded => EventType::DomContentLoaded,
             Self::PageError => EventType::PageError,
             Self::Request(_) => EventType::Request,
@@ -757,7 +1237,10 @@ pub enum Mixed {
 #[serde(rename_all = "camelCase")]
 pub(crate) struct ReloadArgs {
     pub(crate) timeout: Option<f64>,
-    pub(crate) wait_until: Option<DocumentLoadState>
+    pub(crate) wait_until: Option<DocumentLoadState>,
+    /// Only honored by reload
+    #[serde(skip)]
+    pub(crate) bypass_cache: Option<bool>
 }
 
 #[skip_serializing_none]
@@ -790,7 +1273,10 @@ pub(crate) struct ScreenshotArgs {
     pub(crate) omit_background: Option<bool>,
     pub(crate) full_page: Option<bool>,
     pub(crate) clip: Option<FloatRect>,
-    pub(crate) path: Option<PathBuf>
+    pub(crate) path: Option<PathBuf>,
+    /// Viewport to capture with, the page's own is restored afterwards
+    #[serde(skip)]
+    pub(crate) viewport: Option<Viewport>
 }
 
 #[skip_serializing_none]
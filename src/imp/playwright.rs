@@ -1,8 +1,8 @@
 use crate::{
     api::{browser::ContextBuilder, browser_type::PersistentContextLauncher},
     imp::{
-        browser_type::BrowserType, core::*, impl_future::*, prelude::*, selectors::Selectors,
-        utils::Viewport
+        browser::Browser, browser_type::BrowserType, core::*, impl_future::*, prelude::*,
+        selectors::Selectors, utils::Viewport
     }
 };
 use serde::Deserialize;
@@ -15,6 +15,7 @@ pub(crate) struct Playwright {
     firefox: Weak<BrowserType>,
     webkit: Weak<BrowserType>,
     selectors: Option<Weak<Selectors>>,
+    pre_launched_browser: Option<Weak<Browser>>,
     devices: Vec<DeviceDescriptor>
 }
 
@@ -30,13 +31,29 @@ impl Playwright {
         } else {
             None
         };
-        let devices = i.device_descriptors;
+        // Only given by servers, which hand out the browser they launched
+        let pre_launched_browser = match i.pre_launched_browser {
+            Some(b) => Some(get_object!(ctx, &b.guid, Browser)?),
+            None => None
+        };
+        let devices = match (i.device_descriptors.is_empty(), &i.utils) {
+            // Playwright 1.50+ gives them to LocalUtils instead
+            (true, Some(utils)) => {
+                let utils = upgrade(&get_object!(ctx, &utils.guid, Dummy)?)?;
+                match utils.channel().initializer.get("deviceDescriptors") {
+                    Some(v) => serde_json::from_value(v.clone())?,
+                    None => Vec::new()
+                }
+            }
+            _ => i.device_descriptors
+        };
         Ok(Self {
             channel,
             chromium,
             firefox,
             webkit,
             selectors,
+            pre_launched_browser,
             devices
         })
     }
@@ -55,8 +72,12 @@ impl Playwright {
 
     pub(crate) fn selectors(&self) -> Option<Weak<Selectors>> { self.selectors.clone() }
 
+    pub(crate) fn pre_launched_browser(&self) -> Option<Weak<Browser>> {
+        self.pre_launched_browser.clone()
+    }
+
     pub(crate) fn wait_initial_object(conn: &Connection) -> WaitInitialObject {
-        WaitInitialObject::new(conn.context())
+        WaitInitialObject::new(conn.context(), Arc::downgrade(conn.stderr()))
     }
 }
 
@@ -79,18 +100,22 @@ struct Initializer {
     #[serde(default)]
     utils: Option<OnlyGuid>,
     #[serde(default)]
+    pre_launched_browser: Option<OnlyGuid>,
+    #[serde(default)]
     device_descriptors: Vec<DeviceDescriptor>
 }
 
 pub(crate) struct WaitInitialObject {
     ctx: Wm<Context>,
+    stderr: Weak<DriverStderr>,
     started: Instant
 }
 
 impl WaitInitialObject {
-    fn new(ctx: Wm<Context>) -> Self {
+    fn new(ctx: Wm<Context>, stderr: Weak<DriverStderr>) -> Self {
         Self {
             ctx,
+            stderr,
             started: Instant::now()
         }
     }
@@ -105,7 +130,9 @@ impl Future for WaitInitialObject {
         macro_rules! pending {
             () => {{
                 cx.waker().wake_by_ref();
-                if this.started.elapsed().as_secs() > 10 {
+                // The driver exited without sending the initial object
+                let exited = this.stderr.upgrade().map(|s| s.is_closed());
+                if this.started.elapsed().as_secs() > 10 || exited.unwrap_or(true) {
                     return Poll::Ready(Err(Error::InitializationError));
                 }
                 return Poll::Pending;
@@ -3,7 +3,8 @@ use crate::imp::{
     frame::Frame,
     prelude::*,
     response::Response,
-    utils::{Header, ResponseTiming}
+    utils::{Header, ResponseTiming},
+    worker::Worker
 };
 
 #[derive(Debug)]
@@ -15,6 +16,7 @@ pub(crate) struct Request {
     is_navigation_request: bool,
     post_data: Option<String>,
     frame: Weak<Frame>,
+    service_worker: Option<Weak<Worker>>,
     headers: HashMap<String, String>,
     redirected_from: Option<Weak<Request>>,
     var: Mutex<Variable>
@@ -35,6 +37,7 @@ impl Request {
             resource_type,
             method,
             frame,
+            service_worker,
             is_navigation_request,
             post_data,
             headers,
@@ -48,7 +51,12 @@ impl Request {
                 (k, v)
             })
             .collect();
-        let frame = get_object!(ctx, &frame.guid, Frame)?;
+        // Requests of service workers have no frame
+        let frame = match frame {
+            Some(OnlyGuid { guid }) => get_object!(ctx, &guid, Frame)?,
+            None => Weak::new()
+        };
+        let service_worker = service_worker.and_then(|w| get_object!(ctx, &w.guid, Worker).ok());
         let redirected_from =
             match redirected_from.map(|OnlyGuid { guid }| get_object!(ctx, &guid, Request)) {
                 None => None,
@@ -64,6 +72,7 @@ impl Request {
             is_navigation_request,
             post_data,
             frame,
+            service_worker,
             headers,
             redirected_from,
             var
@@ -85,6 +94,8 @@ impl Request {
 
     pub(crate) fn frame(&self) -> Weak<Frame> { self.frame.clone() }
 
+    pub(crate) fn service_worker(&self) -> Option<Weak<Worker>> { self.service_worker.clone() }
+
     pub(crate) fn post_data(&self) -> Option<Vec<u8>> {
         base64::decode(self.post_data.as_ref()?).ok()
     }
@@ -162,7 +173,8 @@ struct Initializer {
     url: String,
     resource_type: String,
     method: String,
-    frame: OnlyGuid,
+    frame: Option<OnlyGuid>,
+    service_worker: Option<OnlyGuid>,
     is_navigation_request: bool,
     // base64
     post_data: Option<String>,
@@ -11,7 +11,15 @@ pub(crate) struct Response {
     url: String,
     status: i32,
     status_text: String,
-    request: Weak<Request>
+    headers: Vec<Header>,
+    from_service_worker: bool,
+    request: Weak<Request>,
+    var: Mutex<Variable>
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Variable {
+    body: Option<Vec<u8>>
 }
 
 impl Response {
@@ -20,6 +28,8 @@ impl Response {
             url,
             status,
             status_text,
+            headers,
+            from_service_worker,
             request,
             timing
         } = serde_json::from_value(channel.initializer.clone())?;
@@ -30,7 +40,10 @@ impl Response {
             url,
             status,
             status_text,
-            request
+            headers,
+            from_service_worker,
+            request,
+            var: Mutex::default()
         })
     }
 
@@ -38,6 +51,8 @@ impl Response {
     pub(crate) fn status(&self) -> i32 { self.status }
     pub(crate) fn status_text(&self) -> &str { &self.status_text }
 
+    pub(crate) fn is_from_service_worker(&self) -> bool { self.from_service_worker }
+
     pub(crate) fn ok(&self) -> bool { self.status == 0 || (200..300).contains(&self.status) }
 
     pub(crate) async fn finished(&self) -> ArcResult<Option<String>> {
@@ -46,28 +61,55 @@ impl Response {
         Ok(s.map(ToOwned::to_owned))
     }
 
+    /// The body is fetched once and kept, so it can be read any number of times.
     pub(crate) async fn body(&self) -> ArcResult<Vec<u8>> {
+        if let Some(body) = &self.var.lock().unwrap().body {
+            return Ok(body.clone());
+        }
         let v = send_message!(self, "body", Map::new());
         let s = only_str(&v)?;
         let bytes = base64::decode(s).map_err(Error::InvalidBase64)?;
+        self.var.lock().unwrap().body = Some(bytes.clone());
         Ok(bytes)
     }
 
-    pub(crate) async fn text(&self) -> ArcResult<String> {
-        Ok(String::from_utf8(self.body().await?).map_err(Error::InvalidUtf8)?)
-    }
-
     pub(crate) fn request(&self) -> Weak<Request> { self.request.clone() }
 
-    pub(crate) async fn headers(&self) -> ArcResult<Vec<Header>> {
-        let v = send_message!(self, "body", Map::new());
-        let first = first(&v).ok_or(Error::InvalidParams)?;
-        let mut headers: Vec<Header> =
-            serde_json::from_value((*first).clone()).map_err(Error::Serde)?;
-        for h in headers.iter_mut() {
-            h.name.make_ascii_lowercase();
+    /// The driver doesn't tell whether a response came from the cache. Revalidated responses have a `304` status, and
+    /// others are recognized by the resource timing entry of the request, which reports no bytes transferred for a
+    /// non-empty body.
+    pub(crate) async fn is_from_cache(&self) -> ArcResult<bool> {
+        const CACHED: &str = r#"([url, start]) => {
+            const distance = e => Math.abs(performance.timeOrigin + e.startTime - start);
+            const entry = performance
+                .getEntriesByName(url)
+                .reduce((a, e) => (a && distance(a) <= distance(e) ? a : e), null);
+            return !!entry && entry.transferSize === 0 && entry.decodedBodySize > 0;
+        }"#;
+        if self.status == 304 {
+            return Ok(true);
         }
-        Ok(headers)
+        let request = upgrade(&self.request)?;
+        let (frame, timing) = match (request.frame().upgrade(), request.timing()) {
+            (Some(f), Some(t)) => (f, t),
+            _ => return Ok(false)
+        };
+        // Timing entries are only added once the body is received
+        self.finished().await?;
+        frame
+            .evaluate(CACHED, Some((&self.url, timing.start_time)))
+            .await
+    }
+
+    /// Headers as they were received along with the response, names lowercased.
+    pub(crate) fn headers(&self) -> Vec<Header> {
+        self.headers
+            .iter()
+            .map(|h| Header {
+                name: h.name.to_ascii_lowercase(),
+                value: h.value.clone()
+            })
+            .collect()
     }
 }
 
@@ -82,6 +124,9 @@ struct Initializer {
     url: String,
     status: i32,
     status_text: String,
+    headers: Vec<Header>,
+    #[serde(default)]
+    from_service_worker: bool,
     request: OnlyGuid,
     timing: ResponseTiming
 }
@@ -1,4 +1,12 @@
-use crate::imp::{core::*, prelude::*, request::Request, utils::Header};
+use crate::imp::{
+    core::*,
+    prelude::*,
+    request::Request,
+    utils::{AbortErrorCode, Header, UrlMatcher}
+};
+use futures::future::BoxFuture;
+
+pub(crate) type RouteHandler = Arc<dyn Fn(Weak<Route>) -> BoxFuture<'static, ()> + Send + Sync>;
 
 #[derive(Debug)]
 pub(crate) struct Route {
@@ -15,24 +23,125 @@ impl Route {
 
     pub(crate) fn request(&self) -> Weak<Request> { self.request.clone() }
 
-    pub(crate) async fn abort(&self, err_code: Option<&str>) -> Result<(), Arc<Error>> {
-        let mut args = HashMap::new();
-        if let Some(x) = err_code {
-            args.insert("errCode", x);
+    pub(crate) async fn abort(&self, error_code: Option<AbortErrorCode>) -> Result<(), Arc<Error>> {
+        #[skip_serializing_none]
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Args {
+            error_code: Option<AbortErrorCode>
         }
+        let args = Args { error_code };
         let _ = send_message!(self, "abort", args);
         Ok(())
     }
 
     pub(crate) async fn fulfill(&self, args: FulfillArgs<'_, '_>) -> ArcResult<()> {
+        #[skip_serializing_none]
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Args {
+            status: Option<i32>,
+            headers: Vec<Header>,
+            body: Option<String>,
+            is_base64: bool
+        }
+        let FulfillArgs {
+            body,
+            is_base64,
+            status,
+            headers,
+            content_type,
+            path
+        } = args;
+        let mut headers = headers.unwrap_or_default();
+        let (body, is_base64, content_type) = match path {
+            Some(path) => {
+                let bytes = std::fs::read(&path).map_err(Error::from)?;
+                let guessed = content_type.unwrap_or_else(|| mime_type(&path));
+                (Some(base64::encode(bytes)), true, Some(guessed))
+            }
+            None => (
+                body.map(str::to_owned),
+                is_base64.unwrap_or_default(),
+                content_type
+            )
+        };
+        if let Some(content_type) = content_type {
+            headers.retain(|h| !h.name.eq_ignore_ascii_case("content-type"));
+            headers.push(Header {
+                name: "content-type".into(),
+                value: content_type.into()
+            });
+        }
+        let args = Args {
+            status,
+            headers,
+            body,
+            is_base64
+        };
         let _ = send_message!(self, "fulfill", args);
         Ok(())
     }
 
     pub(crate) async fn r#continue(&self, args: ContinueArgs<'_, '_, '_>) -> ArcResult<()> {
+        self.send_continue(args, false).await
+    }
+
+    /// Lets the request through untouched when no handler claimed it.
+    pub(crate) async fn fallback(&self) -> ArcResult<()> {
+        self.send_continue(ContinueArgs::default(), true).await
+    }
+
+    async fn send_continue(
+        &self,
+        args: ContinueArgs<'_, '_, '_>,
+        is_fallback: bool
+    ) -> ArcResult<()> {
+        #[skip_serializing_none]
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Args<'a, 'b> {
+            url: Option<&'a str>,
+            method: Option<&'b str>,
+            headers: Option<Vec<Header>>,
+            post_data: Option<String>,
+            is_fallback: bool
+        }
+        let ContinueArgs {
+            url,
+            method,
+            headers,
+            post_data
+        } = args;
+        let args = Args {
+            url,
+            method,
+            headers,
+            post_data: post_data.map(base64::encode),
+            is_fallback
+        };
         let _ = send_message!(self, "continue", args);
         Ok(())
     }
+
+    /// Runs `handler` on a task of its own so that it may await anything, other requests included, without holding
+    /// back the connection. Without a handler the request continues as if it had not been intercepted.
+    pub(crate) fn handle(route: Weak<Route>, handler: Option<RouteHandler>) {
+        spawn(async move {
+            match handler {
+                Some(handler) => handler(route).await,
+                None => {
+                    let route = match route.upgrade() {
+                        Some(r) => r,
+                        None => return
+                    };
+                    if let Err(e) = route.fallback().await {
+                        log::trace!("Failed to continue route: {:?}", e);
+                    }
+                }
+            }
+        });
+    }
 }
 
 impl RemoteObject for Route {
@@ -46,32 +155,71 @@ struct Initializer {
     request: OnlyGuid
 }
 
-#[skip_serializing_none]
-#[derive(Serialize)]
-#[serde(rename_all = "camelCase")]
-pub(crate) struct FulfillArgs<'a, 'b> {
-    body: &'a str,
-    is_base64: bool,
-    pub(crate) status: Option<i32>,
-    pub(crate) headers: Option<Vec<Header>>,
-    pub(crate) content_type: Option<&'b str>
+/// A handler registered with `route`, kept by the page or context it was registered on.
+#[derive(Clone)]
+pub(crate) struct RouteEntry {
+    pub(crate) matcher: UrlMatcher,
+    pub(crate) handler: RouteHandler
 }
 
-impl<'a, 'b> FulfillArgs<'a, 'b> {
-    pub(crate) fn new(body: &'a str, is_base64: bool) -> Self {
-        Self {
-            body,
-            is_base64,
-            status: None,
-            headers: None,
-            content_type: None
-        }
+impl std::fmt::Debug for RouteEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RouteEntry")
+            .field("matcher", &self.matcher)
+            .finish()
     }
 }
 
-#[skip_serializing_none]
-#[derive(Serialize, Default)]
-#[serde(rename_all = "camelCase")]
+/// Later registrations take precedence, so entries are searched from the back.
+pub(crate) fn find_handler(routes: &[RouteEntry], url: &str) -> Option<RouteHandler> {
+    routes
+        .iter()
+        .rev()
+        .find(|r| r.matcher.is_match(url))
+        .map(|r| r.handler.clone())
+}
+
+/// Sent with `setNetworkInterceptionPatterns` so that the driver only pauses matching requests.
+pub(crate) fn interception_patterns(routes: &[RouteEntry]) -> Value {
+    let patterns: Vec<&UrlMatcher> = routes.iter().map(|r| &r.matcher).collect();
+    serde_json::json!({ "patterns": patterns })
+}
+
+fn mime_type(path: &Path) -> &'static str {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase());
+    match ext.as_deref() {
+        Some("html") | Some("htm") => "text/html",
+        Some("css") => "text/css",
+        Some("js") | Some("mjs") => "application/javascript",
+        Some("json") => "application/json",
+        Some("txt") => "text/plain",
+        Some("png") => "image/png",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("svg") => "image/svg+xml",
+        Some("webp") => "image/webp",
+        Some("ico") => "image/x-icon",
+        Some("woff") => "font/woff",
+        Some("woff2") => "font/woff2",
+        Some("pdf") => "application/pdf",
+        _ => "application/octet-stream"
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct FulfillArgs<'a, 'b> {
+    pub(crate) body: Option<&'a str>,
+    pub(crate) is_base64: Option<bool>,
+    pub(crate) status: Option<i32>,
+    pub(crate) headers: Option<Vec<Header>>,
+    pub(crate) content_type: Option<&'b str>,
+    pub(crate) path: Option<PathBuf>
+}
+
+#[derive(Default)]
 pub(crate) struct ContinueArgs<'a, 'b, 'c> {
     pub(crate) url: Option<&'a str>,
     pub(crate) method: Option<&'b str>,
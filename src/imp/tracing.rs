@@ -0,0 +1,114 @@
+use crate::imp::{core::*, prelude::*};
+use std::io::{self, Write};
+
+#[derive(Debug)]
+pub(crate) struct Tracing {
+    channel: ChannelOwner,
+    var: Mutex<Variable>
+}
+
+#[derive(Debug, Default)]
+struct Variable {
+    sources: Vec<PathBuf>
+}
+
+impl Tracing {
+    pub(crate) fn new(channel: ChannelOwner) -> Self {
+        Self {
+            channel,
+            var: Mutex::default()
+        }
+    }
+
+    pub(crate) async fn start(&self, args: StartArgs) -> ArcResult<()> {
+        let sources = match (args.sources, &args.caller) {
+            (Some(true), Some(caller)) => vec![caller.clone()],
+            _ => Vec::new()
+        };
+        let name = args.name.clone();
+        let _ = send_message!(self, "tracingStart", args);
+        self.start_chunk(name.as_deref()).await?;
+        self.var.lock().unwrap().sources = sources;
+        Ok(())
+    }
+
+    pub(crate) async fn start_chunk(&self, name: Option<&str>) -> ArcResult<()> {
+        #[skip_serializing_none]
+        #[derive(Serialize)]
+        struct Args<'a> {
+            name: Option<&'a str>
+        }
+        let _ = send_message!(self, "tracingStartChunk", Args { name });
+        Ok(())
+    }
+
+    /// Saves the chunk to `path` if given, discards it otherwise.
+    pub(crate) async fn stop_chunk(&self, path: Option<&Path>) -> ArcResult<()> {
+        let mut args = Map::new();
+        let mode = if path.is_some() { "archive" } else { "discard" };
+        args.insert("mode".into(), mode.into());
+        let res = send_message!(self, "tracingStopChunk", args);
+        if let Some(path) = path {
+            let guid = only_guid(&res)?;
+            let artifact = get_object!(self.context()?.lock().unwrap(), guid, Artifact)?;
+            let artifact = upgrade(&artifact)?;
+            artifact.save_as(path).await?;
+            artifact.delete().await?;
+            let sources = self.var.lock().unwrap().sources.clone();
+            add_sources(path, &sources).map_err(Error::from)?;
+        }
+        Ok(())
+    }
+
+    /// Saves the current chunk to `path` if given, discards it otherwise.
+    pub(crate) async fn stop(&self, path: Option<&Path>) -> ArcResult<()> {
+        self.stop_chunk(path).await?;
+        let _ = send_message!(self, "tracingStop", Map::new());
+        self.var.lock().unwrap().sources = Vec::new();
+        Ok(())
+    }
+}
+
+/// The driver only knows about JavaScript call stacks, so the Rust sources are added to the archive here, under
+/// `sources/` with the path they were compiled from. Files that can't be read at run time are skipped.
+fn add_sources(zip: &Path, sources: &[PathBuf]) -> io::Result<()> {
+    if sources.is_empty() {
+        return Ok(());
+    }
+    let file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(zip)?;
+    let mut w = zip::ZipWriter::new_append(file).map_err(io::Error::from)?;
+    for source in sources {
+        let content = match std::fs::read(source) {
+            Ok(c) => c,
+            Err(_) => continue
+        };
+        let name = format!("sources/{}", source.to_string_lossy().replace('\\', "/"));
+        w.start_file(name, zip::write::SimpleFileOptions::default())
+            .map_err(io::Error::from)?;
+        w.write_all(&content)?;
+    }
+    w.finish().map_err(io::Error::from)?;
+    Ok(())
+}
+
+impl RemoteObject for Tracing {
+    fn channel(&self) -> &ChannelOwner { &self.channel }
+    fn channel_mut(&mut self) -> &mut ChannelOwner { &mut self.channel }
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StartArgs {
+    pub(crate) name: Option<String>,
+    pub(crate) screenshots: Option<bool>,
+    pub(crate) snapshots: Option<bool>,
+    #[serde(skip)]
+    pub(crate) sources: Option<bool>,
+    /// Source file of the code that started tracing
+    #[serde(skip)]
+    pub(crate) caller: Option<PathBuf>
+}
@@ -1,4 +1,4 @@
-use crate::imp::prelude::*;
+use crate::imp::{core::Error, prelude::*};
 
 #[derive(Debug, Deserialize, Clone, Serialize, PartialEq, Eq)]
 pub struct Viewport {
@@ -9,16 +9,31 @@ pub struct Viewport {
 #[skip_serializing_none]
 #[derive(Debug, Deserialize, Clone, Serialize)]
 pub struct ProxySettings {
-    /// Proxy to be used for all requests. HTTP and SOCKS proxies are supported, for example `http://myproxy.com:3128` or\n`socks5://myproxy.com:3128`. Short form `myproxy.com:3128` is considered an HTTP proxy.
+    /// Proxy to be used for all requests. HTTP and SOCKS proxies are supported, for example `http://myproxy.com:3128`,
+    /// `https://myproxy.com:3129` or `socks5://myproxy.com:3128`. Short form `myproxy.com:3128` is considered an HTTP proxy.
     pub server: String,
-    /// Optional coma-separated domains to bypass proxy, for example `\".com, chromium.org, .domain.com\"`.
+    /// Optional comma-separated domains to bypass proxy, for example `".com, chromium.org, .domain.com"`.
     pub bypass: Option<String>,
+    /// Optional username to use if HTTP proxy requires authentication.
     pub username: Option<String>,
+    /// Optional password to use if HTTP proxy requires authentication.
     pub password: Option<String>
 }
 
+impl ProxySettings {
+    /// Routes every request through `server`, without authentication nor bypassed domains.
+    pub fn new<S: Into<String>>(server: S) -> Self {
+        Self {
+            server: server.into(),
+            bypass: None,
+            username: None,
+            password: None
+        }
+    }
+}
+
 #[skip_serializing_none]
-#[derive(Debug, Deserialize, Clone, Serialize)]
+#[derive(Debug, Deserialize, Clone, Serialize, PartialEq)]
 pub struct Geolocation {
     /// Latitude between -90 and 90.
     pub latitude: f64,
@@ -28,10 +43,71 @@ pub struct Geolocation {
     pub accuracy: Option<f64>
 }
 
+impl Geolocation {
+    pub fn new(latitude: f64, longitude: f64) -> Self {
+        Self {
+            latitude,
+            longitude,
+            accuracy: None
+        }
+    }
+
+    /// Checks that every field is in the range accepted by browsers.
+    /// Called before a geolocation is sent to the driver.
+    pub fn validate(&self) -> Result<(), Error> {
+        if !(-90.0..=90.0).contains(&self.latitude) {
+            return Err(Error::InvalidGeolocation(format!(
+                "latitude {} is out of range -90..90",
+                self.latitude
+            )));
+        }
+        if !(-180.0..=180.0).contains(&self.longitude) {
+            return Err(Error::InvalidGeolocation(format!(
+                "longitude {} is out of range -180..180",
+                self.longitude
+            )));
+        }
+        match self.accuracy {
+            Some(a) if a.is_nan() || a < 0. => Err(Error::InvalidGeolocation(format!(
+                "accuracy {} is negative",
+                a
+            ))),
+            _ => Ok(())
+        }
+    }
+}
+
+#[skip_serializing_none]
 #[derive(Debug, Deserialize, Clone, Serialize)]
 pub struct HttpCredentials {
     pub username: String,
-    pub password: String
+    pub password: String,
+    /// Restricts sending the credentials to a specific origin, such as `https://example.com`.
+    pub origin: Option<String>,
+    /// When to send the credentials. Defaults to [`HttpCredentialsSend::Unauthorized`].
+    pub send: Option<HttpCredentialsSend>
+}
+
+impl HttpCredentials {
+    /// Credentials sent to any origin once it answers `401 Unauthorized`.
+    pub fn new<U: Into<String>, P: Into<String>>(username: U, password: P) -> Self {
+        Self {
+            username: username.into(),
+            password: password.into(),
+            origin: None,
+            send: None
+        }
+    }
+}
+
+/// When [`HttpCredentials`] are sent along with requests.
+#[derive(Debug, Deserialize, Serialize, Eq, PartialEq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum HttpCredentialsSend {
+    /// Only in answer to a `401 Unauthorized` response with a `WWW-Authenticate` header.
+    Unauthorized,
+    /// Preemptively with every request, as an `Authorization` header.
+    Always
 }
 
 #[derive(Debug, Deserialize, Serialize, Eq, PartialEq, Clone, Copy)]
@@ -42,6 +118,43 @@ pub enum ColorScheme {
     NoPreference
 }
 
+/// Vision deficiency emulated by [`Page::set_vision_deficiency`](crate::api::Page::set_vision_deficiency).
+#[derive(Debug, Deserialize, Serialize, Eq, PartialEq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum VisionDeficiency {
+    None,
+    Achromatopsia,
+    Deuteranopia,
+    Protanopia,
+    Tritanopia,
+    BlurredVision
+}
+
+/// State of the browser window showing a page, see [`WindowBounds`].
+#[derive(Debug, Deserialize, Serialize, Eq, PartialEq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum WindowState {
+    Normal,
+    Minimized,
+    Maximized,
+    Fullscreen
+}
+
+/// Position and size of the browser window showing a page, in screen pixels. Fields left unset are unchanged by
+/// [`Page::set_window_bounds`](crate::api::Page::set_window_bounds).
+///
+/// The position and size can only be changed while the window is in the [`WindowState::Normal`] state.
+#[skip_serializing_none]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowBounds {
+    pub left: Option<i32>,
+    pub top: Option<i32>,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub window_state: Option<WindowState>
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Deserialize, Serialize)]
 pub struct StorageState {
@@ -93,6 +206,20 @@ impl Cookie {
             same_site: None
         }
     }
+
+    /// Checks that the cookie can be added: it needs either a `url`, or both a `domain` and a `path`.
+    /// Called by `add_cookies` before anything is sent to the driver.
+    pub fn validate(&self) -> Result<(), Error> {
+        let invalid = |msg: &str| Error::InvalidCookie(format!("{}: {}", self.name, msg));
+        match (&self.url, &self.domain, &self.path) {
+            (Some(url), _, _) if url.starts_with("about:") || url.starts_with("data:") => {
+                Err(invalid("url must be a network url"))
+            }
+            (Some(_), None, None) | (None, Some(_), Some(_)) => Ok(()),
+            (Some(_), _, _) => Err(invalid("url can't be combined with domain or path")),
+            (None, _, _) => Err(invalid("either url or both domain and path are required"))
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Eq, PartialEq, Clone, Copy)]
@@ -168,6 +295,374 @@ pub enum ScreenshotType {
     Png
 }
 
+/// Whether CSS animations, transitions and Web Animations run while a screenshot is taken. `Disabled` fast-forwards
+/// finite animations to their end and cancels infinite ones.
+#[derive(Debug, Deserialize, Serialize, Eq, PartialEq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum ScreenshotAnimations {
+    Disabled,
+    Allow
+}
+
+/// Network error reported to the page when a route is aborted with [`Route::abort`](crate::api::Route::abort).
+#[derive(Debug, Deserialize, Serialize, Eq, PartialEq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum AbortErrorCode {
+    /// An operation was aborted (due to user action)
+    Aborted,
+    /// Permission to access a resource, other than the network, was denied
+    AccessDenied,
+    /// The IP address is unreachable. This usually means that there is no route to the specified host or network.
+    AddressUnreachable,
+    /// The client chose to block the request.
+    BlockedByClient,
+    /// The request failed because the response was delivered along with requirements which are not met
+    /// ('X-Frame-Options' and 'Content-Security-Policy' ancestor checks, for instance).
+    BlockedByResponse,
+    /// A connection timed out as a result of not receiving an ACK for data sent.
+    ConnectionAborted,
+    /// A connection was closed (corresponding to a TCP FIN).
+    ConnectionClosed,
+    /// A connection attempt failed.
+    ConnectionFailed,
+    /// A connection attempt was refused.
+    ConnectionRefused,
+    /// A connection was reset (corresponding to a TCP RST).
+    ConnectionReset,
+    /// The Internet connection has been lost.
+    InternetDisconnected,
+    /// The host name could not be resolved.
+    NameNotResolved,
+    /// An operation timed out.
+    TimedOut,
+    /// A generic failure occurred.
+    Failed
+}
+
+/// URL pattern of [`Page::route`](crate::api::Page::route), [`BrowserContext::route`](crate::api::BrowserContext::route)
+/// and [`Frame::wait_for_url`](crate::api::Frame::wait_for_url).
+///
+/// A glob such as `**/*.png` is matched against the whole URL: `*` matches any characters except `/`, `**` matches any
+/// path segments and `{a,b}` matches either alternative. Both `&str` and [`regex::Regex`] convert into a matcher, and
+/// [`UrlMatcher::predicate`] makes one of a function.
+///
+/// Regexes and predicates are only ever matched here, as the syntax of [`regex`] is not that of JavaScript; the driver
+/// is asked for every URL and the ones that don't match are let through.
+#[derive(Clone)]
+pub struct UrlMatcher {
+    glob: Option<String>,
+    pattern: Pattern
+}
+
+#[derive(Clone)]
+enum Pattern {
+    /// The glob compiled, or the regex the matcher was made of. A glob that doesn't compile matches nothing.
+    Regex(Option<regex::Regex>),
+    Predicate(Arc<dyn Fn(&str) -> bool + Send + Sync>)
+}
+
+impl UrlMatcher {
+    /// Matches the URLs for which `f` returns `true`.
+    pub fn predicate<F>(f: F) -> Self
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static
+    {
+        Self {
+            glob: None,
+            pattern: Pattern::Predicate(Arc::new(f))
+        }
+    }
+
+    pub fn is_match(&self, url: &str) -> bool {
+        match &self.pattern {
+            Pattern::Regex(r) => r.as_ref().map(|r| r.is_match(url)).unwrap_or_default(),
+            Pattern::Predicate(f) => f(url)
+        }
+    }
+}
+
+impl std::fmt::Debug for UrlMatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.glob, &self.pattern) {
+            (Some(g), _) => f.debug_tuple("Glob").field(g).finish(),
+            (None, Pattern::Regex(r)) => f.debug_tuple("Regex").field(r).finish(),
+            (None, Pattern::Predicate(_)) => f.write_str("Predicate")
+        }
+    }
+}
+
+impl PartialEq for UrlMatcher {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.pattern, &other.pattern) {
+            (Pattern::Regex(a), Pattern::Regex(b)) => {
+                self.glob == other.glob
+                    && a.as_ref().map(|r| r.as_str()) == b.as_ref().map(|r| r.as_str())
+            }
+            (Pattern::Predicate(a), Pattern::Predicate(b)) => Arc::ptr_eq(a, b),
+            _ => false
+        }
+    }
+}
+
+impl From<&str> for UrlMatcher {
+    fn from(glob: &str) -> Self { Self::from(glob.to_owned()) }
+}
+
+impl From<&String> for UrlMatcher {
+    fn from(glob: &String) -> Self { Self::from(glob.clone()) }
+}
+
+impl From<String> for UrlMatcher {
+    fn from(glob: String) -> Self {
+        let regex = regex::Regex::new(&glob_to_regex(&glob)).ok();
+        Self {
+            glob: Some(glob),
+            pattern: Pattern::Regex(regex)
+        }
+    }
+}
+
+impl From<regex::Regex> for UrlMatcher {
+    fn from(r: regex::Regex) -> Self {
+        Self {
+            glob: None,
+            pattern: Pattern::Regex(Some(r))
+        }
+    }
+}
+
+impl Serialize for UrlMatcher {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer
+    {
+        use serde::ser::SerializeStruct;
+        let mut s = serializer.serialize_struct("UrlMatcher", 1)?;
+        s.serialize_field("glob", self.glob.as_deref().unwrap_or("**/*"))?;
+        s.end()
+    }
+}
+
+// Port of Playwright's globToRegexPattern
+fn glob_to_regex(glob: &str) -> String {
+    const ESCAPED: &[char] = &[
+        '$', '^', '+', '.', '*', '(', ')', '|', '\\', '?', '{', '}', '[', ']'
+    ];
+    let cs: Vec<char> = glob.chars().collect();
+    let mut re = String::from("^");
+    let mut in_group = false;
+    let mut i = 0;
+    while i < cs.len() {
+        let c = cs[i];
+        match c {
+            '\\' if i + 1 < cs.len() => {
+                i += 1;
+                if ESCAPED.contains(&cs[i]) {
+                    re.push('\\');
+                }
+                re.push(cs[i]);
+            }
+            '*' => {
+                let before = if i == 0 { None } else { Some(cs[i - 1]) };
+                let mut stars = 1;
+                while cs.get(i + 1) == Some(&'*') {
+                    stars += 1;
+                    i += 1;
+                }
+                let after = cs.get(i + 1).copied();
+                let deep = stars > 1
+                    && matches!(before, None | Some('/'))
+                    && matches!(after, None | Some('/'));
+                if deep {
+                    re.push_str("((?:[^/]*(?:/|$))*)");
+                    i += 1;
+                } else {
+                    re.push_str("([^/]*)");
+                }
+            }
+            '{' => {
+                in_group = true;
+                re.push('(');
+            }
+            '}' => {
+                in_group = false;
+                re.push(')');
+            }
+            ',' if in_group => re.push('|'),
+            c => {
+                if ESCAPED.contains(&c) {
+                    re.push('\\');
+                }
+                re.push(c);
+            }
+        }
+        i += 1;
+    }
+    re.push('$');
+    re
+}
+
+/// Catches obviously broken JavaScript, such as unbalanced brackets or unterminated literals, before it is sent to the
+/// browser. Only input the JavaScript parser would reject too is refused: wherever a `/` could be either a division or
+/// the start of a regular expression the rest is left unchecked. Empty input is refused as well.
+pub(crate) fn validate_expression(expression: &str) -> Result<(), Error> {
+    let invalid = |msg: &str| Error::InvalidExpression(msg.to_owned());
+    if expression.trim().is_empty() {
+        return Err(invalid("expression is empty"));
+    }
+    let cs: Vec<char> = expression.chars().collect();
+    // Open brackets, '`' for a template literal and '$' for a substitution inside one
+    let mut stack: Vec<char> = Vec::new();
+    // Index of the last character outside whitespace and comments
+    let mut last: Option<usize> = None;
+    let mut i = 0;
+    while i < cs.len() {
+        let c = cs[i];
+        if stack.last() == Some(&'`') {
+            match c {
+                '\\' => i += 1,
+                '`' => {
+                    stack.pop();
+                    last = Some(i);
+                }
+                '$' if cs.get(i + 1) == Some(&'{') => {
+                    stack.push('$');
+                    i += 1;
+                    last = Some(i);
+                }
+                _ => {}
+            }
+            i += 1;
+            continue;
+        }
+        match c {
+            '"' | '\'' => {
+                i += 1;
+                while i < cs.len() && cs[i] != c && cs[i] != '\n' {
+                    if cs[i] == '\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                if cs.get(i) != Some(&c) {
+                    return Err(invalid("unterminated string literal"));
+                }
+            }
+            '`' => stack.push('`'),
+            '/' if cs.get(i + 1) == Some(&'/') => {
+                while i < cs.len() && cs[i] != '\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            '/' if cs.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i + 1 < cs.len() && !(cs[i] == '*' && cs[i + 1] == '/') {
+                    i += 1;
+                }
+                if i + 1 >= cs.len() {
+                    return Err(invalid("unterminated comment"));
+                }
+                i += 2;
+                continue;
+            }
+            '/' => match slash_starts_regex(&cs, last) {
+                Some(true) => {
+                    i += 1;
+                    let mut in_class = false;
+                    while i < cs.len() && cs[i] != '\n' && (in_class || cs[i] != '/') {
+                        match cs[i] {
+                            '\\' => i += 1,
+                            '[' => in_class = true,
+                            ']' => in_class = false,
+                            _ => {}
+                        }
+                        i += 1;
+                    }
+                    if cs.get(i) != Some(&'/') {
+                        return Err(invalid("unterminated regular expression"));
+                    }
+                }
+                Some(false) => {}
+                None => return Ok(())
+            },
+            '(' | '[' | '{' => stack.push(c),
+            ')' | ']' | '}' => {
+                let open = match c {
+                    ')' => '(',
+                    ']' => '[',
+                    _ => '{'
+                };
+                match stack.pop() {
+                    Some(o) if o == open => {}
+                    Some('$') if c == '}' => {}
+                    _ => return Err(invalid(&format!("unexpected `{}`", c)))
+                }
+            }
+            _ => {}
+        }
+        if !c.is_whitespace() {
+            last = Some(i);
+        }
+        i += 1;
+    }
+    match stack.last() {
+        None => Ok(()),
+        Some('`') => Err(invalid("unterminated template literal")),
+        Some('$') => Err(invalid("unclosed `${`")),
+        Some(c) => Err(invalid(&format!("unclosed `{}`", c)))
+    }
+}
+
+/// Whether a `/` following the character at `last` starts a regular expression rather than being a division, or
+/// `None` if that depends on more than the preceding token, as after `)`, `}`, `++` or a keyword.
+fn slash_starts_regex(cs: &[char], last: Option<usize>) -> Option<bool> {
+    const KEYWORDS: &[&str] = &[
+        "return",
+        "typeof",
+        "instanceof",
+        "in",
+        "of",
+        "new",
+        "delete",
+        "void",
+        "throw",
+        "case",
+        "do",
+        "else",
+        "yield",
+        "await",
+        "let",
+        "async"
+    ];
+    let last = match last {
+        None => return Some(true),
+        Some(last) => last
+    };
+    match cs[last] {
+        '+' | '-' if last > 0 && cs[last - 1] == cs[last] => None,
+        '(' | ',' | '=' | ':' | '[' | '!' | '&' | '|' | '?' | '{' | ';' | '~' | '*' | '%' | '<'
+        | '>' | '^' | '+' | '-' => Some(true),
+        ']' | '"' | '\'' | '`' => Some(false),
+        c if c.is_alphanumeric() || c == '_' || c == '$' => {
+            let word: String = cs[..=last]
+                .iter()
+                .rev()
+                .take_while(|c| c.is_alphanumeric() || **c == '_' || **c == '$')
+                .collect::<Vec<_>>()
+                .into_iter()
+                .rev()
+                .collect();
+            if KEYWORDS.contains(&word.as_str()) {
+                None
+            } else {
+                Some(false)
+            }
+        }
+        _ => None
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Eq, PartialEq, Clone, Copy)]
 #[serde(rename_all = "lowercase")]
 pub enum ElementState {
@@ -202,6 +697,40 @@ impl From<(String, String)> for Header {
     fn from((k, v): (String, String)) -> Self { Self { name: k, value: v } }
 }
 
+/// What is left of `timeout` milliseconds once the time since `started` is spent, for calls made in several steps. An
+/// exhausted budget is kept at 1ms because `0` disables the timeout.
+pub(crate) fn remaining_timeout(timeout: Option<f64>, started: std::time::Instant) -> Option<f64> {
+    timeout.map(|t| {
+        if t == 0. {
+            return t;
+        }
+        (t - started.elapsed().as_secs_f64() * 1000.).max(1.)
+    })
+}
+
+/// Extra HTTP headers as the driver takes them, with names lowercased the way Playwright normalizes them.
+pub(crate) fn extra_http_headers<T>(headers: T) -> Vec<Header>
+where
+    T: IntoIterator<Item = (String, String)>
+{
+    headers
+        .into_iter()
+        .map(|(k, v)| Header::from((k.to_ascii_lowercase(), v)))
+        .collect()
+}
+
+/// `extraHTTPHeaders` of context options is a list of name-value pairs rather than an object.
+pub(crate) fn serialize_extra_http_headers<S>(
+    headers: &Option<HashMap<String, String>>,
+    serializer: S
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer
+{
+    let headers = headers.clone().map(extra_http_headers);
+    headers.serialize(serializer)
+}
+
 #[derive(Debug, Serialize, PartialEq, Clone)]
 #[serde(untagged)]
 pub enum Length<'a> {
@@ -226,24 +755,76 @@ pub struct PdfMargins<'a, 'b, 'c, 'd> {
     pub left: Option<Length<'d>>
 }
 
-#[derive(Debug, Serialize, PartialEq)]
-pub struct File {
+/// A file given to `set_input_files` by its contents rather than by a path.
+#[derive(Debug, Serialize, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FilePayload {
     pub name: String,
-    pub mime: String,
-    pub buffer: String
+    pub mime_type: String,
+    #[serde(serialize_with = "serialize_base64")]
+    pub buffer: Vec<u8>
+}
+
+impl FilePayload {
+    pub fn new(name: String, mime_type: String, buffer: Vec<u8>) -> Self {
+        Self {
+            name,
+            mime_type,
+            buffer
+        }
+    }
+}
+
+fn serialize_base64<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer
+{
+    serializer.serialize_str(&base64::encode(bytes))
+}
+
+/// Files of `setInputFiles`. The driver takes either contents or paths, not both in one call.
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum InputFiles {
+    Payloads(Vec<FilePayload>),
+    LocalPaths(Vec<PathBuf>)
 }
 
-impl File {
-    pub fn new(name: String, mime: String, body: &[u8]) -> Self {
-        let buffer = base64::encode(body);
-        Self { name, mime, buffer }
+impl Default for InputFiles {
+    fn default() -> Self { Self::Payloads(Vec::new()) }
+}
+
+impl InputFiles {
+    /// Relative paths are resolved here, since the driver would resolve them against its own working directory.
+    pub(crate) fn new(payloads: Vec<FilePayload>, paths: Vec<PathBuf>) -> Result<Self, Error> {
+        if paths.is_empty() {
+            return Ok(Self::Payloads(payloads));
+        }
+        if !payloads.is_empty() {
+            return Err(Error::MixedInputFiles);
+        }
+        let cwd = std::env::current_dir()?;
+        Ok(Self::LocalPaths(
+            paths.into_iter().map(|p| cwd.join(p)).collect()
+        ))
     }
 }
+
+/// How much a recorded HAR keeps: `Minimal` only keeps what is needed to replay it.
+#[derive(Debug, Deserialize, Serialize, Eq, PartialEq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum HarMode {
+    Full,
+    Minimal
+}
+
 /// Browser distribution channel.
 // TODO: kebab case
 #[derive(Debug, Deserialize, Serialize, Eq, PartialEq, Clone, Copy)]
 #[serde(rename_all = "kebab-case")]
 pub enum BrowserChannel {
+    /// The full Chromium build rather than the headless shell, so headless mode can load extensions.
+    Chromium,
     Chrome,
     ChromeBeta,
     ChromeDev,
@@ -285,3 +866,188 @@ pub struct ResponseTiming {
     /// Time immediately after the browser starts requesting the resource from the server, cache, or local resource. The value\nis given in milliseconds relative to `startTime`, -1 if not available.
     pub response_start: f64
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remaining_timeout_is_never_disabled() {
+        let started = std::time::Instant::now() - std::time::Duration::from_millis(50);
+        let left = remaining_timeout(Some(1000.), started).unwrap();
+        assert!(left <= 950. && left > 900., "{}", left);
+        assert_eq!(remaining_timeout(Some(10.), started), Some(1.));
+        assert_eq!(remaining_timeout(Some(0.), started), Some(0.));
+        assert_eq!(remaining_timeout(None, started), None);
+    }
+
+    #[test]
+    fn geolocation() {
+        let g = Geolocation {
+            latitude: 59.95,
+            longitude: 30.31667,
+            accuracy: Some(10.)
+        };
+        assert!(g.validate().is_ok());
+        let s = serde_json::to_string(&g).unwrap();
+        let de: Geolocation = serde_json::from_str(&s).unwrap();
+        assert_eq!(de, g);
+        let s = serde_json::to_string(&Geolocation::new(0., 0.)).unwrap();
+        assert_eq!(s, r#"{"latitude":0.0,"longitude":0.0}"#);
+        assert!(matches!(
+            Geolocation::new(90.5, 0.).validate(),
+            Err(Error::InvalidGeolocation(_))
+        ));
+        assert!(Geolocation::new(0., -180.1).validate().is_err());
+        assert!(Geolocation::new(f64::NAN, 0.).validate().is_err());
+    }
+
+    #[test]
+    fn cookie() {
+        assert!(Cookie::with_url("a", "b", "https://example.com/")
+            .validate()
+            .is_ok());
+        assert!(Cookie::with_domain_path("a", "b", "example.com", "/")
+            .validate()
+            .is_ok());
+        let mut c = Cookie::with_domain_path("a", "b", "example.com", "/");
+        c.path = None;
+        assert!(matches!(c.validate(), Err(Error::InvalidCookie(_))));
+        c.url = Some("https://example.com/".into());
+        assert!(c.validate().is_err());
+        assert!(Cookie::with_url("a", "b", "about:blank")
+            .validate()
+            .is_err());
+        let s = serde_json::to_string(&Cookie::with_url("a", "b", "https://example.com/")).unwrap();
+        assert_eq!(
+            s,
+            r#"{"name":"a","value":"b","url":"https://example.com/"}"#
+        );
+    }
+
+    #[test]
+    fn extra_http_headers_are_lowercased() {
+        let headers = extra_http_headers(vec![("X-Test".to_owned(), "Value".to_owned())]);
+        assert_eq!(
+            headers,
+            vec![Header {
+                name: "x-test".into(),
+                value: "Value".into()
+            }]
+        );
+        #[derive(Serialize)]
+        struct Args {
+            #[serde(serialize_with = "serialize_extra_http_headers")]
+            headers: Option<HashMap<String, String>>
+        }
+        let mut map = HashMap::new();
+        map.insert("X-Foo".to_owned(), "bar".to_owned());
+        let s = serde_json::to_string(&Args { headers: Some(map) }).unwrap();
+        assert_eq!(s, r#"{"headers":[{"name":"x-foo","value":"bar"}]}"#);
+    }
+
+    #[test]
+    fn abort_error_code() {
+        let s = serde_json::to_string(&AbortErrorCode::ConnectionFailed).unwrap();
+        assert_eq!(s, r#""connectionfailed""#);
+        let s = serde_json::to_string(&AbortErrorCode::BlockedByClient).unwrap();
+        assert_eq!(s, r#""blockedbyclient""#);
+    }
+
+    #[test]
+    fn input_files() {
+        let payload = FilePayload::new("a.txt".into(), "text/plain".into(), b"hi".to_vec());
+        let files = InputFiles::new(vec![payload.clone()], Vec::new()).unwrap();
+        assert_eq!(
+            serde_json::to_string(&files).unwrap(),
+            r#"{"payloads":[{"name":"a.txt","mimeType":"text/plain","buffer":"aGk="}]}"#
+        );
+        let files = InputFiles::new(Vec::new(), vec!["a.txt".into()]).unwrap();
+        let cwd = std::env::current_dir().unwrap();
+        assert_eq!(files, InputFiles::LocalPaths(vec![cwd.join("a.txt")]));
+        assert!(matches!(
+            InputFiles::new(vec![payload], vec!["a.txt".into()]),
+            Err(Error::MixedInputFiles)
+        ));
+    }
+
+    #[test]
+    fn valid_expressions() {
+        for e in [
+            "() => 1",
+            "1 + 2",
+            "document.title",
+            "x => `a${x.map(y => `${y}}`)}b`",
+            "() => /[(]/.test('(')",
+            "() => { return /\\)/.source; }",
+            "a / b / c",
+            "// comment (\n1",
+            "/* ) */ 1",
+            "'}'",
+            r#""\"(""#,
+            "async () => { const x = [1, 2]; return x; }",
+            "x++ / 2",
+            "if (a) /[(]/.test(s)",
+            "a = {} / 1 /* ( */ + /[)]/.source",
+            "x => `${x}` / 2"
+        ] {
+            assert!(validate_expression(e).is_ok(), "{}", e);
+        }
+    }
+
+    #[test]
+    fn invalid_expressions() {
+        for e in [
+            "",
+            "  ",
+            "() => {",
+            "() => 1)",
+            "'abc",
+            "`abc",
+            "x => `${x`",
+            "{]",
+            "/* open",
+            "() => /abc"
+        ] {
+            assert!(
+                matches!(validate_expression(e), Err(Error::InvalidExpression(_))),
+                "{}",
+                e
+            );
+        }
+    }
+
+    #[test]
+    fn url_matcher() {
+        let png = UrlMatcher::from("**/*.png");
+        assert!(png.is_match("http://localhost:8080/a/b/logo.png"));
+        assert!(!png.is_match("http://localhost:8080/logo.png.html"));
+        let api = UrlMatcher::from("http://localhost/api/*");
+        assert!(api.is_match("http://localhost/api/users"));
+        assert!(!api.is_match("http://localhost/api/users/1"));
+        let images = UrlMatcher::from("**/*.{png,jpg}");
+        assert!(images.is_match("https://example.com/x.jpg"));
+        assert!(!images.is_match("https://example.com/x.gif"));
+        let r = UrlMatcher::from(regex::Regex::new(r"(?i)\.css$").unwrap());
+        assert!(r.is_match("https://example.com/STYLE.CSS"));
+        assert!(!r.is_match("https://example.com/style.js"));
+        assert!(!UrlMatcher::from("**/*.{png").is_match("https://example.com/x.png"));
+        let p = UrlMatcher::predicate(|url| url.ends_with("/spa"));
+        assert!(p.is_match("http://localhost/spa"));
+        assert!(!p.is_match("http://localhost/"));
+        assert_eq!(p, p.clone());
+        assert_ne!(p, UrlMatcher::predicate(|url| url.ends_with("/spa")));
+        assert_eq!(
+            serde_json::to_value(&png).unwrap(),
+            serde_json::json!({"glob": "**/*.png"})
+        );
+        assert_eq!(
+            serde_json::to_value(&r).unwrap(),
+            serde_json::json!({"glob": "**/*"})
+        );
+        assert_eq!(
+            serde_json::to_value(&p).unwrap(),
+            serde_json::json!({"glob": "**/*"})
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use crate::imp::{
-    browser_context::BrowserContext, core::*, js_handle::JsHandle, page::Page, prelude::*
+    browser_context::BrowserContext, core::*, js_handle::JsHandle, page::Page, prelude::*,
+    utils::validate_expression
 };
 
 #[derive(Debug)]
@@ -47,6 +48,7 @@ impl Worker {
             expression: &'a str,
             arg: Value
         }
+        validate_expression(expression)?;
         let arg = ser::to_value(&arg).map_err(Error::SerializationPwJson)?;
         let args = Args { expression, arg };
         let v = send_message!(self, "evaluateExpression", args);
@@ -72,6 +74,7 @@ impl Worker {
             expression: &'a str,
             arg: Value
         }
+        validate_expression(expression)?;
         let arg = ser::to_value(&arg).map_err(Error::SerializationPwJson)?;
         let args = Args { expression, arg };
         let v = send_message!(self, "evaluateExpressionHandle", args);
@@ -84,9 +87,9 @@ impl Worker {
 impl Worker {
     pub(crate) fn set_page(&self, page: Weak<Page>) { self.var.lock().unwrap().page = Some(page); }
 
-    // pub(crate) fn set_browser_context(&self, browser_context: Weak<BrowserContext>) {
-    //    self.var.lock().unwrap().browser_context = Some(browser_context);
-    //}
+    pub(crate) fn set_browser_context(&self, browser_context: Weak<BrowserContext>) {
+        self.var.lock().unwrap().browser_context = Some(browser_context);
+    }
 
     fn on_close(&self, ctx: &Context) -> Result<(), Error> {
         let this = get_object!(ctx, self.guid(), Worker)?;
@@ -94,7 +97,9 @@ impl Worker {
         if let Some(page) = var.page.as_ref().and_then(Weak::upgrade) {
             page.remove_worker(&this);
         }
-        // var.context.remove_service_worker(&this)
+        if let Some(c) = var.browser_context.as_ref().and_then(Weak::upgrade) {
+            c.remove_service_worker(&this);
+        }
         self.emit_event(Evt::Close);
         Ok(())
     }
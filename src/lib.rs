@@ -105,15 +105,16 @@ extern crate serde_with;
 pub mod api;
 mod imp;
 
-pub use crate::imp::core::{Driver, Error};
+pub use crate::imp::core::{Driver, Error, SharedError};
 pub use api::playwright::Playwright;
 
 #[doc(hidden)]
 #[macro_export]
 macro_rules! runtime_test {
-    ($name:tt, $main:stmt) => {
+    ($(#[$attr:meta])* $name:ident, $main:stmt) => {
         #[cfg(feature = "rt-tokio")]
         #[test]
+        $(#[$attr])*
         fn $name() {
             env_logger::builder().is_test(true).try_init().ok();
             tokio::runtime::Builder::new_current_thread()
@@ -125,6 +126,7 @@ macro_rules! runtime_test {
 
         #[cfg(feature = "rt-actix")]
         #[test]
+        $(#[$attr])*
         fn $name() {
             env_logger::builder().is_test(true).try_init().ok();
             actix_rt::System::new().block_on(async { $main });
@@ -132,6 +134,7 @@ macro_rules! runtime_test {
 
         #[cfg(feature = "rt-async-std")]
         #[test]
+        $(#[$attr])*
         fn $name() {
             env_logger::builder().is_test(true).try_init().ok();
             async_std::task::block_on(async { $main });
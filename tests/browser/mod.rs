@@ -1,5 +1,6 @@
 use super::Which;
-use playwright::api::{Browser, BrowserType};
+use futures::{channel::mpsc, stream::StreamExt};
+use playwright::api::{Browser, BrowserContext, BrowserType};
 
 pub async fn all(t: &BrowserType, which: Which) -> Browser {
     launch_close_browser(t).await;
@@ -7,6 +8,9 @@ pub async fn all(t: &BrowserType, which: Which) -> Browser {
     assert!(b.exists());
     version_should_work(&b, which);
     contexts_should_work(&b).await;
+    on_context(&b).await;
+    new_page_input(&b).await;
+    new_page_owns_context(&b).await;
     b
 }
 
@@ -59,3 +63,58 @@ async fn contexts_should_work(b: &Browser) {
     context.close().await.unwrap();
     assert_eq!(b.contexts().unwrap().len(), len);
 }
+
+async fn on_context(b: &Browser) {
+    let (tx, rx) = mpsc::unbounded();
+    b.on_context(move |c| {
+        let tx = tx.clone();
+        async move {
+            tx.unbounded_send(c).unwrap();
+        }
+    })
+    .unwrap();
+    let c1 = b.context_builder().build().await.unwrap();
+    let c2 = b.context_builder().build().await.unwrap();
+    let created: Vec<BrowserContext> = rx.take(2).collect().await;
+    assert!(created.contains(&c1));
+    assert!(created.contains(&c2));
+    c1.close().await.unwrap();
+    c2.close().await.unwrap();
+}
+
+async fn new_page_input(b: &Browser) {
+    let len = b.contexts().unwrap().len();
+    let p = b.new_page().await.unwrap();
+    assert_eq!(b.contexts().unwrap().len(), len + 1);
+    p.set_content_builder("<input>")
+        .set_content()
+        .await
+        .unwrap();
+    p.mouse.click_builder(1., 1.).click().await.unwrap();
+    p.focus("input", None).await.unwrap();
+    p.keyboard.r#type("hello", None).await.unwrap();
+    let value: String = p
+        .eval("() => document.querySelector('input').value")
+        .await
+        .unwrap();
+    assert_eq!(value, "hello");
+    p.close(None).await.unwrap();
+    assert_eq!(b.contexts().unwrap().len(), len);
+}
+
+async fn new_page_owns_context(b: &Browser) {
+    let len = b.contexts().unwrap().len();
+    let p = b.new_page().await.unwrap();
+    let implicit = p.context();
+    assert!(b.contexts().unwrap().contains(&implicit));
+    p.close(None).await.unwrap();
+    assert_eq!(b.contexts().unwrap().len(), len);
+    assert!(!b.contexts().unwrap().contains(&implicit));
+    // Pages of an explicit context leave it open
+    let c = b.context_builder().build().await.unwrap();
+    let p = c.new_page().await.unwrap();
+    p.close(None).await.unwrap();
+    assert_eq!(b.contexts().unwrap().len(), len + 1);
+    assert!(c.pages().unwrap().is_empty());
+    c.close().await.unwrap();
+}
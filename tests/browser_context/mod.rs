@@ -1,36 +1,96 @@
 use super::Which;
+use futures::stream::StreamExt;
 use playwright::api::{
-    browser::RecordVideo, Browser, BrowserContext, BrowserType, Cookie, LocalStorageEntry,
-    OriginState, StorageState
+    browser::{RecordHar, RecordVideo},
+    browser_context, page, Browser, BrowserContext, BrowserType, ColorScheme, Cookie, Geolocation,
+    HttpCredentials, HttpCredentialsSend, LocalStorageEntry, OriginState, ProxySettings,
+    StorageState, Viewport
 };
+use std::sync::{Arc, Mutex};
 
 pub async fn all(
     browser: &Browser,
     persistent: &BrowserContext,
     port: u16,
-    _which: Which
+    which: Which
 ) -> BrowserContext {
     let c = launch(browser).await;
     assert_ne!(persistent, &c);
     assert!(c.browser().unwrap().is_some());
     storage_state(&c, port).await;
     set_offline_should_work(browser, port).await;
+    route_should_abort_images(browser, port).await;
+    route_async_handler(browser, port).await;
+    tracing_sources(browser, port).await;
+    tracing_chunks(browser, port).await;
+    storage_state_round_trip(browser, port).await;
+    grant_permissions_to_origin(browser, port).await;
+    geolocation_option(browser, port).await;
+    extra_http_headers_option(browser, port).await;
+    record_har(browser, port).await;
+    record_video(browser, port).await;
+    set_download_path(browser, port).await;
+    http_credentials(browser).await;
+    locale_timezone_color_scheme(browser).await;
+    pages_stream(browser).await;
+    response_from_cache(browser, port).await;
+    tap_requires_touch(browser).await;
+    expose_function(browser).await;
+    viewport_option(browser).await;
+    if which == Which::Chromium {
+        response_from_service_worker(browser, port).await;
+    }
     set_timeout(&c).await;
     cookies_should_work(&c).await;
+    named_cookies(&c).await;
     add_init_script_should_work(&c).await;
     pages_should_work(&c).await;
     c
 }
 
-pub async fn persistent(t: &BrowserType, _port: u16, which: Which) -> BrowserContext {
+pub async fn persistent(t: &BrowserType, port: u16, which: Which) -> BrowserContext {
+    persistent_cookies(t, port).await;
+    proxy_settings(t, port).await;
     let c = launch_persistent_context(t).await;
     if Which::Firefox != which {
         // XXX: launch with permissions not work on firefox
         check_launched_permissions(&c).await;
     }
+    if Which::Chromium == which {
+        extension_service_worker(t).await;
+    }
     c
 }
 
+async fn proxy_settings(t: &BrowserType, port: u16) {
+    // Nothing listens there
+    let unreachable = format!("http://127.0.0.1:{}", super::free_local_port().unwrap());
+    let browser = t
+        .launcher()
+        .proxy(ProxySettings::new(unreachable))
+        .launch()
+        .await
+        .unwrap();
+    // The test server answers proxied requests by their path
+    let url = "http://example.com/static/empty.html";
+    let c = browser.context_builder().build().await.unwrap();
+    let page = c.new_page().await.unwrap();
+    let err = page.goto_builder(url).goto().await.unwrap_err();
+    assert!(matches!(*err, playwright::Error::Network(_)), "{}", err);
+    c.close().await.unwrap();
+    let c = browser
+        .context_builder()
+        .proxy(ProxySettings::new(super::origin(port)))
+        .build()
+        .await
+        .unwrap();
+    let page = c.new_page().await.unwrap();
+    let response = page.goto_builder(url).goto().await.unwrap().unwrap();
+    assert_eq!(response.status().unwrap(), 200);
+    c.close().await.unwrap();
+    browser.close().await.unwrap();
+}
+
 async fn launch(b: &Browser) -> BrowserContext {
     let c = b
         .context_builder()
@@ -79,6 +139,102 @@ async fn launch_persistent_context(t: &BrowserType) -> BrowserContext {
         .unwrap()
 }
 
+async fn persistent_cookies(t: &BrowserType, port: u16) {
+    let user_data_dir = super::temp_dir().join("persistent-cookies");
+    let _ = std::fs::remove_dir_all(&user_data_dir);
+    std::fs::create_dir_all(&user_data_dir).unwrap();
+    let url = super::origin(port);
+    let expires = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs_f64()
+        + 3600.;
+    let c = t
+        .persistent_context_launcher(&user_data_dir)
+        .launch()
+        .await
+        .unwrap();
+    c.add_cookies(&[Cookie {
+        expires: Some(expires),
+        ..Cookie::with_url("persisted", "yes", url.as_str())
+    }])
+    .await
+    .unwrap();
+    // Closing the context also shuts the browser down, releasing the profile for the next launch
+    c.close().await.unwrap();
+    let c = t
+        .persistent_context_launcher(&user_data_dir)
+        .launch()
+        .await
+        .unwrap();
+    let cookies = c.cookies(&[url]).await.unwrap();
+    assert!(cookies
+        .iter()
+        .any(|x| x.name == "persisted" && x.value == "yes"));
+    c.close().await.unwrap();
+}
+
+async fn extension_service_worker(t: &BrowserType) {
+    use playwright::api::BrowserChannel;
+    let extension = super::temp_dir().join("extension");
+    std::fs::create_dir_all(&extension).unwrap();
+    std::fs::write(
+        extension.join("manifest.json"),
+        r#"{
+            "name": "worker",
+            "version": "1.0",
+            "manifest_version": 3,
+            "background": {"service_worker": "background.js"}
+        }"#
+    )
+    .unwrap();
+    std::fs::write(extension.join("background.js"), "self.ready = true;").unwrap();
+    let user_data_dir = super::temp_dir().join("extension-profile");
+    std::fs::create_dir_all(&user_data_dir).unwrap();
+    let args = vec![
+        format!("--disable-extensions-except={}", extension.display()),
+        format!("--load-extension={}", extension.display()),
+    ];
+    // The headless shell can't load extensions, unlike the new headless mode of the full build
+    let c = t
+        .persistent_context_launcher(&user_data_dir)
+        .channel(BrowserChannel::Chromium)
+        .headless(true)
+        .args(&args)
+        .launch()
+        .await
+        .unwrap();
+    let (tx, mut background_pages) = tokio::sync::mpsc::unbounded_channel();
+    c.on_background_page(move |p| {
+        let tx = tx.clone();
+        async move {
+            tx.send(p).ok();
+        }
+    })
+    .unwrap();
+    let mut events = c.subscribe_event().unwrap();
+    if c.service_workers().unwrap().is_empty() {
+        let started = async {
+            while let Some(Ok(evt)) = events.next().await {
+                if let browser_context::Event::ServiceWorker(_) = evt {
+                    break;
+                }
+            }
+        };
+        tokio::time::timeout(std::time::Duration::from_secs(10), started)
+            .await
+            .expect("the extension's service worker did not start");
+    }
+    let workers = c.service_workers().unwrap();
+    assert_eq!(workers.len(), 1);
+    assert!(workers[0].url().unwrap().starts_with("chrome-extension://"));
+    assert!(workers[0].eval::<bool>("() => self.ready").await.unwrap());
+    // A Manifest V3 extension has no background page
+    assert!(c.background_pages().unwrap().is_empty());
+    assert!(background_pages.try_recv().is_err());
+    c.close().await.unwrap();
+}
+
 async fn pages_should_work(c: &BrowserContext) {
     let len = c.pages().unwrap().len();
     let page = c.new_page().await.unwrap();
@@ -89,8 +245,12 @@ async fn pages_should_work(c: &BrowserContext) {
 }
 
 async fn set_timeout(c: &BrowserContext) {
-    c.set_default_navigation_timeout(10000).await.unwrap();
     c.set_default_timeout(10000).await.unwrap();
+    // Follows the default timeout until it is set
+    assert_eq!(c.default_navigation_timeout().unwrap(), 10000);
+    c.set_default_navigation_timeout(20000).await.unwrap();
+    assert_eq!(c.default_navigation_timeout().unwrap(), 20000);
+    assert_eq!(c.default_timeout().unwrap(), 10000);
 }
 
 async fn cookies_should_work(c: &BrowserContext) {
@@ -112,6 +272,40 @@ async fn cookies_should_work(c: &BrowserContext) {
     assert_eq!(&first.name, "foo");
     assert_eq!(&first.value, "bar");
     ensure_cookies_are_cleared(c).await;
+    let mut cookie = Cookie::with_domain_path("baz", "qux", "example.com", "/");
+    c.add_cookies(&[cookie.clone()]).await.unwrap();
+    let cookies = c.cookies(&["https://example.com/".into()]).await.unwrap();
+    assert_eq!(cookies.len(), 1);
+    assert_eq!(&cookies[0].name, "baz");
+    cookie.path = None;
+    let err = c.add_cookies(&[cookie]).await.unwrap_err();
+    assert!(matches!(*err, playwright::Error::InvalidCookie(_)));
+    ensure_cookies_are_cleared(c).await;
+}
+
+async fn named_cookies(c: &BrowserContext) {
+    ensure_cookies_are_cleared(c).await;
+    let cookies = [
+        Cookie::with_domain_path("session", "abc", "example.com", "/"),
+        Cookie::with_domain_path("theme", "dark", "example.com", "/"),
+        Cookie::with_domain_path("theme", "light", "example.org", "/")
+    ];
+    c.add_cookies(&cookies).await.unwrap();
+    let session = c.cookie("session", None).await.unwrap().unwrap();
+    assert_eq!(session.value, "abc");
+    let theme = c
+        .cookie("theme", Some("https://example.org/"))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(theme.value, "light");
+    assert_eq!(c.cookie("missing", None).await.unwrap(), None);
+    c.delete_cookie("theme").await.unwrap();
+    assert_eq!(c.cookie("theme", None).await.unwrap(), None);
+    let left = c.cookies(&[]).await.unwrap();
+    assert_eq!(left.len(), 1);
+    assert_eq!(left[0].name, "session");
+    ensure_cookies_are_cleared(c).await;
 }
 
 async fn ensure_cookies_are_cleared(c: &BrowserContext) {
@@ -126,6 +320,410 @@ async fn check_launched_permissions(c: &BrowserContext) {
     assert_eq!(get_permission(c, "geolocation").await, "prompt");
 }
 
+async fn grant_permissions_to_origin(b: &Browser, port: u16) {
+    let c = b.context_builder().build().await.unwrap();
+    let p = c.new_page().await.unwrap();
+    p.goto_builder(&super::url_static(port, "/empty.html"))
+        .goto()
+        .await
+        .unwrap();
+    let state = || async {
+        p.eval::<String>(
+            "() => navigator.permissions.query({name: 'geolocation'}).then(r => r.state)"
+        )
+        .await
+        .unwrap()
+    };
+    let geolocation = ["geolocation".to_owned()];
+    c.grant_permissions(&geolocation, Some("https://example.com"))
+        .await
+        .unwrap();
+    assert_eq!(state().await, "prompt");
+    c.grant_permissions(&geolocation, Some(&super::origin(port)))
+        .await
+        .unwrap();
+    assert_eq!(state().await, "granted");
+    c.set_geolocation(Some(&Geolocation::new(59.95, 30.31667)))
+        .await
+        .unwrap();
+    let coords: (f64, f64) = p
+        .eval(
+            "() => new Promise((resolve, reject) => navigator.geolocation.getCurrentPosition(
+                p => resolve([p.coords.latitude, p.coords.longitude]), reject))"
+        )
+        .await
+        .unwrap();
+    assert_eq!(coords, (59.95, 30.31667));
+    c.clear_permissions().await.unwrap();
+    assert_eq!(state().await, "prompt");
+    c.close().await.unwrap();
+}
+
+async fn geolocation_option(b: &Browser, port: u16) {
+    let c = b
+        .context_builder()
+        .geolocation(Geolocation::new(48.858, 2.2945))
+        .permissions(&["geolocation".into()])
+        .build()
+        .await
+        .unwrap();
+    let p = c.new_page().await.unwrap();
+    p.goto_builder(&super::url_static(port, "/empty.html"))
+        .goto()
+        .await
+        .unwrap();
+    let position = || async {
+        p.eval::<Option<(f64, f64)>>(
+            "() => new Promise(resolve => navigator.geolocation.getCurrentPosition(
+                p => resolve([p.coords.latitude, p.coords.longitude]),
+                () => resolve(null),
+                { timeout: 1000 }))"
+        )
+        .await
+        .unwrap()
+    };
+    assert_eq!(position().await, Some((48.858, 2.2945)));
+    c.set_geolocation(Some(&Geolocation::new(-33.8568, 151.2153)))
+        .await
+        .unwrap();
+    assert_eq!(position().await, Some((-33.8568, 151.2153)));
+    c.set_geolocation(None).await.unwrap();
+    assert_eq!(position().await, None);
+    c.close().await.unwrap();
+}
+
+async fn extra_http_headers_option(b: &Browser, port: u16) {
+    let mut headers = std::collections::HashMap::new();
+    headers.insert("X-Test".to_owned(), "context".to_owned());
+    let c = b
+        .context_builder()
+        .extra_http_headers(headers)
+        .build()
+        .await
+        .unwrap();
+    let p = c.new_page().await.unwrap();
+    p.set_extra_http_headers(vec![("X-Page".into(), "page".into())])
+        .await
+        .unwrap();
+    let url = super::url_static(port, "/empty.html");
+    let (maybe_request, _) = tokio::join!(
+        p.expect_event(page::EventType::Request),
+        p.goto_builder(&url).goto()
+    );
+    let req = match maybe_request.unwrap() {
+        page::Event::Request(req) => req,
+        _ => unreachable!()
+    };
+    let headers = req.headers().unwrap();
+    assert_eq!(headers.get("x-test").map(String::as_str), Some("context"));
+    assert_eq!(headers.get("x-page").map(String::as_str), Some("page"));
+    c.close().await.unwrap();
+}
+
+async fn record_har(b: &Browser, port: u16) {
+    let path = super::temp_dir().join("har").join("record.har");
+    let url = super::url_static(port, "/empty.html");
+    let c = b
+        .context_builder()
+        .record_har(RecordHar::new(&path))
+        .build()
+        .await
+        .unwrap();
+    let p = c.new_page().await.unwrap();
+    p.goto_builder(&url).goto().await.unwrap();
+    c.close().await.unwrap();
+    let har: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    let entries = har["log"]["entries"].as_array().unwrap();
+    assert!(entries.iter().any(|e| e["request"]["url"] == url.as_str()));
+}
+
+async fn record_video(b: &Browser, port: u16) {
+    let dir = super::temp_dir().join("record_video");
+    let c = b
+        .context_builder()
+        .record_video(RecordVideo {
+            dir: &dir,
+            size: Some(Viewport {
+                width: 320,
+                height: 240
+            })
+        })
+        .build()
+        .await
+        .unwrap();
+    let p = c.new_page().await.unwrap();
+    p.goto_builder(&super::url_static(port, "/empty.html"))
+        .goto()
+        .await
+        .unwrap();
+    let video = p.video().unwrap().unwrap();
+    let path = video.path().unwrap();
+    c.close().await.unwrap();
+    assert!(std::fs::metadata(&path).unwrap().len() > 0);
+    let copy = super::temp_dir()
+        .join("record_video_copy")
+        .join("video.webm");
+    video.save_as(&copy).await.unwrap();
+    assert!(std::fs::metadata(&copy).unwrap().len() > 0);
+}
+
+async fn set_download_path(b: &Browser, port: u16) {
+    let c = b
+        .context_builder()
+        .accept_downloads(true)
+        .build()
+        .await
+        .unwrap();
+    let p = c.new_page().await.unwrap();
+    p.set_content_builder(&format!(
+        r#"<a href="{}">download</a>"#,
+        super::url_download(port, "/worker.html")
+    ))
+    .set_content()
+    .await
+    .unwrap();
+    let p = &p;
+    let download = move || async move {
+        let (d, _) = tokio::join!(
+            p.expect_event(page::EventType::Download),
+            p.click_builder("a").click()
+        );
+        match d.unwrap() {
+            page::Event::Download(d) => d,
+            _ => unreachable!()
+        }
+    };
+    let saved = move |dir: &std::path::Path, name: &str| {
+        let path = dir.join(name);
+        async move {
+            for _ in 0..50 {
+                if path.is_file() {
+                    return true;
+                }
+                p.wait_for_timeout(100.).await;
+            }
+            false
+        }
+    };
+    let (first, second) = (
+        super::temp_dir().join("downloads1"),
+        super::temp_dir().join("downloads2")
+    );
+    c.set_download_path(Some(first.clone())).unwrap();
+    let d = download().await;
+    assert!(saved(&first, d.suggested_filename()).await);
+    c.set_download_path(Some(second.clone())).unwrap();
+    std::fs::remove_file(first.join(d.suggested_filename())).unwrap();
+    let d = download().await;
+    assert!(saved(&second, d.suggested_filename()).await);
+    assert!(!first.join(d.suggested_filename()).exists());
+    c.close().await.unwrap();
+}
+
+async fn response_from_service_worker(b: &Browser, port: u16) {
+    let c = b.context_builder().build().await.unwrap();
+    let p = c.new_page().await.unwrap();
+    p.goto_builder(&super::url_static(port, "/sw.html"))
+        .goto()
+        .await
+        .unwrap();
+    p.eval::<()>("() => window.controlled").await.unwrap();
+    let (response, text) = tokio::join!(
+        p.wait_for_response_builder("**/from-sw")
+            .wait_for_response(),
+        p.eval::<String>("() => fetch('/static/from-sw').then(r => r.text())")
+    );
+    assert_eq!(text.unwrap(), "from service worker");
+    let response = response.unwrap();
+    assert!(response.from_service_worker().unwrap());
+    assert!(response.request().service_worker().unwrap().is_none());
+    let (response, _) = tokio::join!(
+        p.wait_for_response_builder("**/empty.html")
+            .wait_for_response(),
+        p.eval::<String>("() => fetch('/static/empty.html').then(r => r.text())")
+    );
+    assert!(!response.unwrap().from_service_worker().unwrap());
+    c.close().await.unwrap();
+}
+
+async fn response_from_cache(b: &Browser, port: u16) {
+    // A context of its own, as routes disable the cache
+    let c = b.context_builder().build().await.unwrap();
+    let p = c.new_page().await.unwrap();
+    p.goto_builder(&super::url_static(port, "/empty.html"))
+        .goto()
+        .await
+        .unwrap();
+    let (response, _) = tokio::join!(
+        p.wait_for_response_builder("**/simple.json")
+            .wait_for_response(),
+        p.eval::<String>("() => fetch('/static/simple.json').then(r => r.text())")
+    );
+    assert!(!response.unwrap().from_cache().await.unwrap());
+    let (response, _) = tokio::join!(
+        p.wait_for_response_builder("**/simple.json")
+            .wait_for_response(),
+        p.eval::<String>(
+            "() => fetch('/static/simple.json', { cache: 'force-cache' }).then(r => r.text())"
+        )
+    );
+    assert!(response.unwrap().from_cache().await.unwrap());
+    c.close().await.unwrap();
+}
+
+async fn tap_requires_touch(b: &Browser) {
+    let c = b.context_builder().build().await.unwrap();
+    let p = c.new_page().await.unwrap();
+    p.set_content_builder("<button>tap</button>")
+        .set_content()
+        .await
+        .unwrap();
+    let err = p.touchscreen().tap(10., 10.).await.unwrap_err();
+    assert!(format!("{:?}", err).contains("hasTouch"), "{:?}", err);
+    let err = p.tap_builder("button").tap().await.unwrap_err();
+    assert!(format!("{:?}", err).contains("hasTouch"), "{:?}", err);
+    c.close().await.unwrap();
+}
+
+async fn viewport_option(b: &Browser) {
+    let c = b
+        .context_builder()
+        .viewport(Some(Viewport {
+            width: 800,
+            height: 600
+        }))
+        .device_scale_factor(2.0)
+        .user_agent("playwright-rust")
+        .build()
+        .await
+        .unwrap();
+    let p = c.new_page().await.unwrap();
+    let size = "() => [window.innerWidth, window.innerHeight, window.devicePixelRatio]";
+    let (width, height, ratio): (i32, i32, f64) = p.eval(size).await.unwrap();
+    assert_eq!((width, height, ratio), (800, 600, 2.0));
+    let user_agent: String = p.eval("() => navigator.userAgent").await.unwrap();
+    assert_eq!(user_agent, "playwright-rust");
+    p.set_viewport_size(Viewport {
+        width: 640,
+        height: 480
+    })
+    .await
+    .unwrap();
+    let (width, height, _): (i32, i32, f64) = p.eval(size).await.unwrap();
+    assert_eq!((width, height), (640, 480));
+    c.close().await.unwrap();
+}
+
+async fn expose_function(b: &Browser) {
+    let c = b.context_builder().build().await.unwrap();
+    c.expose_function("add", |(x, y): (i32, i32)| async move { Ok(x + y) })
+        .await
+        .unwrap();
+    let p = c.new_page().await.unwrap();
+    let sum: i32 = p.eval("() => window.add(2, 3)").await.unwrap();
+    assert_eq!(sum, 5);
+    c.remove_exposed_function("add").await.unwrap();
+    assert!(p.eval::<i32>("() => window.add(2, 3)").await.is_err());
+    // Pages opened afterwards still get the stub, whose calls are rejected
+    let p2 = c.new_page().await.unwrap();
+    assert!(p2.eval::<i32>("() => window.add(2, 3)").await.is_err());
+    c.remove_exposed_function("missing").await.unwrap();
+    c.close().await.unwrap();
+}
+
+async fn pages_stream(b: &Browser) {
+    use browser_context::PageLifecycle::{Closed, Created};
+    let c = b.context_builder().build().await.unwrap();
+    let mut lifecycle = c.pages_stream().unwrap();
+    let p1 = c.new_page().await.unwrap();
+    let p2 = c.new_page().await.unwrap();
+    assert_eq!(lifecycle.next().await, Some(Created(p1.clone())));
+    assert_eq!(lifecycle.next().await, Some(Created(p2.clone())));
+    p1.close(None).await.unwrap();
+    // Closed pages are disposed, so they can't be compared anymore
+    assert!(matches!(lifecycle.next().await, Some(Closed(_))));
+    assert_eq!(c.pages().unwrap(), vec![p2]);
+    // Closing the context closes its remaining pages, then ends the stream
+    c.close().await.unwrap();
+    let rest: Vec<_> = lifecycle.collect().await;
+    assert_eq!(rest.len(), 1);
+    assert!(matches!(&rest[0], Closed(_)));
+}
+
+async fn locale_timezone_color_scheme(b: &Browser) {
+    let c = b
+        .context_builder()
+        .locale("fr-FR")
+        .timezone_id("America/New_York")
+        .color_scheme(ColorScheme::Dark)
+        .build()
+        .await
+        .unwrap();
+    let p = c.new_page().await.unwrap();
+    let language: String = p.eval("() => navigator.language").await.unwrap();
+    assert_eq!(language, "fr-FR");
+    let zone: String = p
+        .eval("() => Intl.DateTimeFormat().resolvedOptions().timeZone")
+        .await
+        .unwrap();
+    assert_eq!(zone, "America/New_York");
+    // Standard time, UTC-5
+    let offset: i32 = p
+        .eval("() => new Date(2021, 0, 1).getTimezoneOffset()")
+        .await
+        .unwrap();
+    assert_eq!(offset, 300);
+    let dark: bool = p
+        .eval("() => matchMedia('(prefers-color-scheme: dark)').matches")
+        .await
+        .unwrap();
+    assert!(dark);
+    c.close().await.unwrap();
+}
+
+async fn http_credentials(b: &Browser) {
+    use std::{net::ToSocketAddrs, time::Duration};
+    // Needs the network
+    let reachable = ("httpbin.org", 443)
+        .to_socket_addrs()
+        .ok()
+        .and_then(|mut addrs| addrs.next())
+        .map(|addr| std::net::TcpStream::connect_timeout(&addr, Duration::from_secs(5)).is_ok())
+        .unwrap_or_default();
+    if !reachable {
+        return;
+    }
+    let url = "https://httpbin.org/basic-auth/user/passwd";
+    let status = |credentials: Option<HttpCredentials>| async move {
+        let builder = b.context_builder();
+        let builder = match &credentials {
+            Some(x) => builder.http_credentials(x),
+            None => builder
+        };
+        let c = builder.build().await.unwrap();
+        let page = c.new_page().await.unwrap();
+        let response = page.goto_builder(url).goto().await.unwrap().unwrap();
+        let status = response.status().unwrap();
+        c.close().await.unwrap();
+        status
+    };
+    assert_eq!(status(None).await, 401);
+    assert_eq!(
+        status(Some(HttpCredentials::new("user", "wrong"))).await,
+        401
+    );
+    assert_eq!(
+        status(Some(HttpCredentials::new("user", "passwd"))).await,
+        200
+    );
+    let always = HttpCredentials {
+        send: Some(HttpCredentialsSend::Always),
+        ..HttpCredentials::new("user", "passwd")
+    };
+    assert_eq!(status(Some(always)).await, 200);
+}
+
 async fn get_permission(c: &BrowserContext, name: &str) -> String {
     let p = c.new_page().await.unwrap();
     let res = p
@@ -156,11 +754,158 @@ async fn set_offline_should_work(browser: &Browser, port: u16) {
         .unwrap();
     let page = c.new_page().await.unwrap();
     let url = super::url_static(port, "/empty.html");
-    let err = page.goto_builder(&url).goto().await;
-    assert!(err.is_err());
+    let err = page.goto_builder(&url).goto().await.unwrap_err();
+    assert!(matches!(*err, playwright::Error::Network(_)), "{}", err);
     c.set_offline(false).await.unwrap();
+    c.set_offline(true).await.unwrap();
+    let err = page
+        .goto_builder("https://example.com/")
+        .goto()
+        .await
+        .unwrap_err();
+    assert!(matches!(*err, playwright::Error::Network(_)), "{}", err);
+    c.set_offline(false).await.unwrap();
+    let response = page.goto_builder(&url).goto().await.unwrap();
+    assert_eq!(response.unwrap().status().unwrap(), 200);
+    c.close().await.unwrap();
+}
+
+async fn route_should_abort_images(browser: &Browser, port: u16) {
+    let c = browser.context_builder().build().await.unwrap();
+    let aborted = Arc::new(Mutex::new(Vec::new()));
+    let a = aborted.clone();
+    c.route("**/*.png", move |route, request| {
+        let a = a.clone();
+        async move {
+            a.lock().unwrap().push(request.url().unwrap());
+            route.abort(None).await.unwrap();
+        }
+    })
+    .await
+    .unwrap();
+    let page = c.new_page().await.unwrap();
+    let url = super::url_static(port, "/image.html");
     let response = page.goto_builder(&url).goto().await.unwrap();
     assert_eq!(response.unwrap().status().unwrap(), 200);
+    assert_eq!(
+        page.text_content("p", None).await.unwrap().as_deref(),
+        Some("loaded")
+    );
+    assert!(page
+        .eval::<bool>("() => !!window.imageFailed")
+        .await
+        .unwrap());
+    assert_eq!(
+        *aborted.lock().unwrap(),
+        vec![super::url_static(port, "/logo.png")]
+    );
+    c.unroute("**/*.png").await.unwrap();
+    c.close().await.unwrap();
+}
+
+async fn route_async_handler(browser: &Browser, port: u16) {
+    let c = browser.context_builder().build().await.unwrap();
+    let upstream = c.new_page().await.unwrap();
+    let upstream_url = super::url_static(port, "/simple.json");
+    c.route("**/*", move |route, request| {
+        let upstream = upstream.clone();
+        let upstream_url = upstream_url.clone();
+        async move {
+            if !request.url().unwrap().ends_with("/transformed") {
+                route.continue_builder().r#continue().await.unwrap();
+                return;
+            }
+            // The upstream request is routed through this same handler while this call is pending
+            let response = upstream.goto_builder(&upstream_url).goto().await.unwrap();
+            let body = response.unwrap().text().await.unwrap().to_uppercase();
+            route.fulfill_builder().body(&body).fulfill().await.unwrap();
+        }
+    })
+    .await
+    .unwrap();
+    let page = c.new_page().await.unwrap();
+    let url = super::url_static(port, "/empty.html");
+    page.goto_builder(&url).goto().await.unwrap();
+    let text: String = page
+        .eval("async () => (await fetch('/transformed')).text()")
+        .await
+        .unwrap();
+    assert_eq!(text, r#"{"FOO": "BAR"}"#);
+    c.close().await.unwrap();
+}
+
+async fn tracing_sources(browser: &Browser, port: u16) {
+    let c = browser.context_builder().build().await.unwrap();
+    let tracing = c.tracing();
+    tracing.start_builder().sources(true).start().await.unwrap();
+    let page = c.new_page().await.unwrap();
+    let url = super::url_static(port, "/empty.html");
+    page.goto_builder(&url).goto().await.unwrap();
+    let path = super::temp_dir().join("trace-sources.zip");
+    tracing.stop(Some(&path)).await.unwrap();
+    let zip = zip::ZipArchive::new(std::fs::File::open(&path).unwrap()).unwrap();
+    let names: Vec<&str> = zip.file_names().collect();
+    assert!(names.iter().any(|n| n.ends_with(".trace")));
+    assert!(names.contains(&"sources/tests/browser_context/mod.rs"));
+    c.close().await.unwrap();
+}
+
+async fn tracing_chunks(browser: &Browser, port: u16) {
+    let c = browser.context_builder().build().await.unwrap();
+    let tracing = c.tracing();
+    tracing
+        .start_builder()
+        .name("chunks".into())
+        .screenshots(true)
+        .snapshots(true)
+        .start()
+        .await
+        .unwrap();
+    let page = c.new_page().await.unwrap();
+    page.goto_builder(&super::url_static(port, "/empty.html"))
+        .goto()
+        .await
+        .unwrap();
+    let first = super::temp_dir().join("trace-chunk1.zip");
+    tracing.stop_chunk(Some(&first)).await.unwrap();
+    tracing.start_chunk(None).await.unwrap();
+    page.goto_builder(&super::url_static(port, "/empty2.html"))
+        .goto()
+        .await
+        .unwrap();
+    let second = super::temp_dir().join("trace-chunk2.zip");
+    tracing.stop(Some(&second)).await.unwrap();
+    for path in &[first, second] {
+        let zip = zip::ZipArchive::new(std::fs::File::open(path).unwrap()).unwrap();
+        assert!(zip.file_names().any(|n| n == "trace.trace"), "{:?}", path);
+    }
+    c.close().await.unwrap();
+}
+
+async fn storage_state_round_trip(b: &Browser, port: u16) {
+    let url = super::url_static(port, "/empty.html");
+    let path = super::temp_dir().join("state").join("storage_state.json");
+    let c = b.context_builder().build().await.unwrap();
+    let page = c.new_page().await.unwrap();
+    page.goto_builder(&url).goto().await.unwrap();
+    page.eval::<()>("() => { localStorage['token'] = 'secret'; }")
+        .await
+        .unwrap();
+    let saved = c.storage_state(Some(&path)).await.unwrap();
+    c.close().await.unwrap();
+    let json = std::fs::read_to_string(&path).unwrap();
+    let read: StorageState = serde_json::from_str(&json).unwrap();
+    assert_eq!(read.origins, saved.origins);
+    let c = b
+        .context_builder()
+        .storage_state_path(path)
+        .build()
+        .await
+        .unwrap();
+    let page = c.new_page().await.unwrap();
+    page.goto_builder(&url).goto().await.unwrap();
+    let token: String = page.eval("() => localStorage['token']").await.unwrap();
+    assert_eq!(token, "secret");
     c.close().await.unwrap();
 }
 
@@ -171,7 +916,7 @@ async fn storage_state(c: &BrowserContext, port: u16) {
     page.eval::<()>("() => { localStorage['name2'] = 'value2'; }")
         .await
         .unwrap();
-    let storage = c.storage_state().await.unwrap();
+    let storage = c.storage_state(None).await.unwrap();
     assert!(storage
         .cookies
         .unwrap()
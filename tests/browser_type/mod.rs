@@ -11,6 +11,7 @@ pub async fn all(playwright: &Playwright, which: Which) -> BrowserType {
     executable_should_exist(&t);
     should_handle_timeout(&t).await;
     should_fire_close(&t).await;
+    launch_server_should_work(&t).await;
     t
 }
 
@@ -51,3 +52,25 @@ async fn should_fire_close(t: &BrowserType) {
     close.unwrap();
     assert_eq!(wait.unwrap(), Event::Close);
 }
+
+async fn launch_server_should_work(t: &BrowserType) {
+    use std::net::TcpStream;
+    let server = t.launch_server_builder().launch_server().await.unwrap();
+    let endpoint = server.ws_endpoint().to_owned();
+    assert!(endpoint.starts_with("ws://"), "{}", endpoint);
+    let addr = endpoint["ws://".len()..]
+        .split('/')
+        .next()
+        .unwrap()
+        .to_owned();
+    assert!(TcpStream::connect(&addr).is_ok());
+    let browser = t.connect_builder(&endpoint).connect().await.unwrap();
+    let page = browser.new_page().await.unwrap();
+    page.set_content_builder("<title>served</title>")
+        .set_content()
+        .await
+        .unwrap();
+    assert_eq!(page.title().await.unwrap(), "served");
+    server.close().await.unwrap();
+    assert!(TcpStream::connect(&addr).is_err());
+}
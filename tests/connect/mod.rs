@@ -1,5 +1,8 @@
 use super::{free_local_port, install_browser, playwright_with_driver, Which};
-use playwright::api::{page, Browser, BrowserType};
+use playwright::{
+    api::{page, Browser, BrowserType},
+    Error
+};
 use serde::Deserialize;
 
 pub(super) async fn connect_over_cdp(which: Which) {
@@ -12,6 +15,7 @@ pub(super) async fn connect_over_cdp(which: Which) {
 
     http(&browser_type).await;
     ws(&browser_type).await;
+    unreachable_endpoint(&browser_type).await;
 }
 
 async fn http(browser_type: &BrowserType) {
@@ -110,3 +114,52 @@ async fn fetch_ws_endpoint(browser_type: &BrowserType, port: u16) -> String {
     } = serde_json::from_str(&text).unwrap();
     web_socket_debugger_url.into()
 }
+
+async fn unreachable_endpoint(browser_type: &BrowserType) {
+    let port = free_local_port().unwrap();
+    let endpoint_url = format!("http://localhost:{}", port);
+    let err = browser_type
+        .connect_over_cdp_builder(&endpoint_url)
+        .timeout(1000.0)
+        .connect_over_cdp()
+        .await
+        .unwrap_err();
+    match &*err {
+        Error::ConnectOverCdp { endpoint, .. } => assert_eq!(endpoint, &endpoint_url),
+        e => panic!("unexpected error {:?}", e)
+    }
+}
+
+pub(super) async fn connect(which: Which) {
+    let playwright = playwright_with_driver().await;
+    install_browser(&playwright, which);
+    let browser_type = match which {
+        Which::Webkit => playwright.webkit(),
+        Which::Firefox => playwright.firefox(),
+        Which::Chromium => playwright.chromium()
+    };
+    let server = browser_type
+        .launch_server_builder()
+        .launch_server()
+        .await
+        .unwrap();
+    for _ in 0..2 {
+        let browser = browser_type
+            .connect_builder(server.ws_endpoint())
+            .connect()
+            .await
+            .unwrap();
+        assert!(!browser.version().unwrap().is_empty());
+        let context = browser.context_builder().build().await.unwrap();
+        let page = context.new_page().await.unwrap();
+        page.set_content_builder("<title>remote</title>")
+            .set_content()
+            .await
+            .unwrap();
+        assert_eq!(page.title().await.unwrap(), "remote");
+        assert_eq!(browser.contexts().unwrap().len(), 1);
+        // Disconnects, the server keeps running for the next client
+        browser.close().await.unwrap();
+    }
+    server.close().await.unwrap();
+}
@@ -1,13 +1,12 @@
 use super::Which;
 use playwright::{
-    api::{page, Page, Viewport},
+    api::{page, Browser, Page, Viewport},
     Playwright
 };
 
 pub async fn all(playwright: &Playwright, port: u16, _which: Which) {
     let _devices = playwright.devices();
     let device = playwright.device("iPhone 11 Pro").unwrap();
-    dbg!(&device);
     let chromium = playwright.chromium();
     let browser = chromium.launcher().launch().await.unwrap();
     let ctx = browser
@@ -17,19 +16,46 @@ pub async fn all(playwright: &Playwright, port: u16, _which: Which) {
         .await
         .unwrap();
     let page = ctx.new_page().await.unwrap();
-    check_user_agent(&page, port).await;
+    check_user_agent(&page, port, &device.user_agent).await;
     check_size(&page).await;
     assert!((device_pixel_ratio(&page).await - 3.0).abs() < f64::EPSILON);
     assert!(has_touch(&page).await);
     // TODO: is_mobile
     let tmp_dir = tempdir::TempDir::new("playwright-rust").unwrap();
-    dbg!(&tmp_dir);
     chromium
         .persistent_context_launcher(tmp_dir.path())
         .set_device(&device)
         .launch()
         .await
         .unwrap();
+    overrides(playwright, &browser).await;
+}
+
+// Setters called after set_device take precedence over the descriptor
+async fn overrides(playwright: &Playwright, browser: &Browser) {
+    let device = playwright.device("iPhone 12").unwrap();
+    assert!(
+        device.user_agent.contains("iPhone"),
+        "{}",
+        device.user_agent
+    );
+    let ctx = browser
+        .context_builder()
+        .set_device(&device)
+        .user_agent("custom")
+        .viewport(Some(Viewport {
+            width: 500,
+            height: 400
+        }))
+        .build()
+        .await
+        .unwrap();
+    let page = ctx.new_page().await.unwrap();
+    let user_agent: String = page.eval("() => navigator.userAgent").await.unwrap();
+    assert_eq!(user_agent, "custom");
+    assert_eq!(page.viewport_size().unwrap().unwrap().width, 500);
+    assert!((device_pixel_ratio(&page).await - device.device_scale_factor).abs() < f64::EPSILON);
+    ctx.close().await.unwrap();
 }
 
 async fn check_size(page: &Page) {
@@ -71,8 +97,8 @@ async fn has_touch(page: &Page) -> bool {
     page.eval("() => 'ontouchstart' in window").await.unwrap()
 }
 
-async fn check_user_agent(page: &Page, port: u16) {
-    let user_agent = "Mozilla/5.0 (iPhone; CPU iPhone OS 12_2 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/13.0 Mobile/15E148 Safari/604.1";
+async fn check_user_agent(page: &Page, port: u16, user_agent: &str) {
+    assert!(user_agent.contains("iPhone"), "{}", user_agent);
     assert_eq!(
         page.eval::<String>("() => navigator.userAgent")
             .await
@@ -88,6 +114,5 @@ async fn check_user_agent(page: &Page, port: u16) {
         page::Event::Request(request) => request,
         _ => unreachable!()
     };
-    dbg!(&request.headers().unwrap());
     assert_eq!(request.headers().unwrap()["user-agent"], user_agent);
 }
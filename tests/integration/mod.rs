@@ -12,6 +12,7 @@ pub async fn all(which: Which) {
     browser_context_test(which).await;
     dblclick_and_hover_test(which).await;
     element_visibility_test(which).await;
+    with_element_disposes_handles(which).await;
 }
 
 async fn query_selector_basic_example(which: Which) {
@@ -424,3 +425,50 @@ async fn element_visibility_test(which: Which) {
 
     browser.close().await.unwrap();
 }
+
+async fn with_element_disposes_handles(which: Which) {
+    let playwright = crate::playwright_with_driver().await;
+    crate::install_browser(&playwright, which);
+
+    let t = match which {
+        Which::Webkit => playwright.webkit(),
+        Which::Firefox => playwright.firefox(),
+        Which::Chromium => playwright.chromium()
+    };
+
+    let browser = t.launcher().launch().await.unwrap();
+    let bc = browser.context_builder().build().await.unwrap();
+    let page = bc.new_page().await.unwrap();
+    page.set_content_builder("<button>ok</button>")
+        .set_content()
+        .await
+        .unwrap();
+
+    let before = playwright.object_count();
+    for _ in 0..50 {
+        let text = page
+            .with_element("button", |e| async move { e.text_content().await })
+            .await
+            .unwrap();
+        assert_eq!(text.unwrap().unwrap(), Some("ok".to_owned()));
+        // Handles are disposed even when the closure fails
+        let failed = page
+            .with_element("button", |e| async move {
+                let attribute = e.get_attribute("missing").await.unwrap();
+                attribute.ok_or("no attribute")
+            })
+            .await
+            .unwrap();
+        assert_eq!(failed, Some(Err("no attribute")));
+    }
+    assert_eq!(playwright.object_count(), before);
+
+    // No element, nothing to run
+    let missing = page
+        .with_element("#missing", |_| async { panic!("no element should match") })
+        .await
+        .unwrap();
+    assert!(missing.is_none());
+
+    browser.close().await.unwrap();
+}
@@ -1,6 +1,9 @@
 use super::Which;
 use futures::stream::StreamExt;
-use playwright::api::{page, BrowserContext, Geolocation, Page, Viewport};
+use playwright::api::{
+    page, AbortErrorCode, BrowserContext, FilePayload, Frame, Geolocation, GetByRoleOptions, Page,
+    Position, UrlMatcher, Viewport
+};
 
 macro_rules! concurrent {
     ($which:expr, $($e:expr),*) => {
@@ -28,6 +31,7 @@ pub async fn all(c: &BrowserContext, port: u16, which: Which) {
         set_extra_http_headers(c, port),
         focus_should_work(c),
         reload_should_worker(c),
+        hard_reload(c, port),
         screenshot_should_work(&page),
         title_should_work(&page),
         check_should_work(c),
@@ -37,12 +41,78 @@ pub async fn all(c: &BrowserContext, port: u16, which: Which) {
         workers_should_work(c, port, which),
         accessibility(c),
         query_selector_and_eval(c),
-        input(c)
+        input(c),
+        expose_binding_handle(c),
+        expose_function(c),
+        remove_exposed_function(c),
+        wait_for_actionable_selector(c),
+        wait_for_push_state(c, port),
+        add_init_script_path(c, port),
+        add_init_script_frames(c, port),
+        response_body_cached(c, port),
+        shadow_dom_piercing(c),
+        aria_snapshot(c),
+        frame_text_readers(c),
+        screenshot_timeout(c),
+        wait_for_delayed_selector(c),
+        locator_re_resolves(c),
+        get_by_locators(c),
+        get_by_role_normalized_name(c),
+        locator_text_whitespace(c),
+        locator_all_texts(c),
+        route_fulfill_and_continue(c, port),
+        route_abort_error_code(c, port),
+        dialogs(c),
+        console_args(c),
+        main_frame_persists(c, port),
+        on_download_data_url(c),
+        eval_invalid_expression(c),
+        set_input_files_payload(c),
+        file_chooser(c),
+        expect_file_chooser(c),
+        screenshot_viewport(c),
+        expect_request_finished(c, port),
+        locator_screenshot(c),
+        goto_about_blank(c, port),
+        wait_for_response(c, port),
+        on_response_prefetch_body(c, port),
+        frames_matching(c, port),
+        scroll_position(c),
+        mouse_click_count_and_button(c),
+        set_content_title(c),
+        wait_for_function_polling(c),
+        wait_for_load_state(c, port),
+        expect_navigation(c, port),
+        select_option(c),
+        referrer_policy(c, port),
+        type_and_press(c),
+        keyboard_event_fidelity(c),
+        keyboard_shortcuts(c),
+        mouse_drawing(c),
+        touchscreen_tap(c),
+        evaluate_arg_not_interpolated(c),
+        drag_and_drop(c),
+        text_and_attribute_accessors(c),
+        goto_retries(c, port),
+        scroll_into_view(c),
+        contenteditable_fill_and_type(c),
+        element_screenshot(c),
+        video_segment(c)
     );
-    // TODO
-    // file_chooser(c, port).await;
-    if which != Which::Firefox {
+    if which == Which::Chromium {
         pdf_should_work(&page).await;
+    } else {
+        pdf_not_supported(&page).await;
+    }
+    if which == Which::Chromium {
+        route_abort_net_error(c, port).await;
+        vision_deficiency(c).await;
+        window_bounds(c).await;
+        evaluate_isolated_world(c).await;
+    } else {
+        vision_deficiency_not_supported(c).await;
+        window_bounds_not_supported(c).await;
+        evaluate_isolated_world_not_supported(c).await;
     }
     video(&page).await;
     emulate_media(&page).await;
@@ -54,6 +124,91 @@ macro_rules! done {
     };
 }
 
+async fn evaluate_arg_not_interpolated(c: &BrowserContext) {
+    let p = new(c).await;
+    for arg in [
+        "\"; window.injected = true; //",
+        "'; window.injected = true; //",
+        "`${window.injected = true}`",
+        "</script><script>window.injected = true</script>"
+    ] {
+        let len: usize = done!(p.evaluate("s => s.length", arg));
+        assert_eq!(len, arg.encode_utf16().count());
+        let same: String = done!(p.evaluate("s => s", arg));
+        assert_eq!(same, arg);
+    }
+    let injected: bool = done!(p.eval("() => 'injected' in window"));
+    assert!(!injected);
+    close(&p).await;
+}
+
+async fn drag_and_drop(c: &BrowserContext) {
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(
+            r#"<div id="item" draggable="true" style="width:50px;height:50px">item</div>
+            <div id="zone" style="width:200px;height:200px;margin-top:50px;border:1px solid"></div>
+            <script>
+            const zone = document.querySelector('#zone');
+            window.events = [];
+            document.querySelector('#item').addEventListener('dragstart', e => {
+                events.push('dragstart');
+                e.dataTransfer.setData('text/plain', e.target.id);
+            });
+            zone.addEventListener('dragover', e => e.preventDefault());
+            zone.addEventListener('drop', e => {
+                events.push('drop');
+                e.preventDefault();
+                zone.appendChild(document.getElementById(e.dataTransfer.getData('text/plain')));
+            });
+            </script>"#
+        )
+        .set_content());
+    done!(p
+        .drag_and_drop_builder("#item", "#zone")
+        .trial(true)
+        .drag_and_drop());
+    let events: Vec<String> = done!(p.eval("() => events"));
+    assert!(events.is_empty());
+    done!(p
+        .drag_and_drop_builder("#item", "#zone")
+        .target_position(Position { x: 10., y: 10. })
+        .drag_and_drop());
+    let events: Vec<String> = done!(p.eval("() => events"));
+    assert_eq!(events, vec!["dragstart", "drop"]);
+    let parent: String = done!(p.eval("() => document.querySelector('#item').parentElement.id"));
+    assert_eq!(parent, "zone");
+    close(&p).await;
+}
+
+async fn text_and_attribute_accessors(c: &BrowserContext) {
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(r#"<a data-id="7">Link <b>here</b><span hidden>!</span></a>"#)
+        .set_content());
+    assert_eq!(
+        done!(p.get_attribute("a", "data-id", None)).as_deref(),
+        Some("7")
+    );
+    assert_eq!(done!(p.get_attribute("a", "href", None)), None);
+    assert_eq!(
+        done!(p.text_content("a", None)).as_deref(),
+        Some("Link here!")
+    );
+    assert_eq!(done!(p.inner_text("a", None)), "Link here");
+    assert_eq!(
+        done!(p.inner_html("a", None)),
+        "Link <b>here</b><span hidden=\"\">!</span>"
+    );
+    let a = done!(p.query_selector("a")).unwrap();
+    assert_eq!(done!(a.get_attribute("data-id")).as_deref(), Some("7"));
+    assert_eq!(done!(a.get_attribute("href")), None);
+    assert_eq!(done!(a.text_content()).as_deref(), Some("Link here!"));
+    assert_eq!(done!(a.inner_text()), "Link here");
+    assert_eq!(done!(a.inner_html()), done!(p.inner_html("a", None)));
+    close(&p).await;
+}
+
 async fn eq_context_close(c: &BrowserContext, p1: &Page) {
     let p2 = new(c).await;
     assert_ne!(p1, &p2);
@@ -134,7 +289,30 @@ async fn reload_should_worker(c: &BrowserContext) {
     close(&page).await;
 }
 
+async fn hard_reload(c: &BrowserContext, port: u16) {
+    let page = new(c).await;
+    let url = super::url_static(port, "/empty.html");
+    page.goto_builder(&url).goto().await.unwrap();
+    // The document is fetched again in full rather than revalidated or taken from the cache
+    let response = done!(page.reload_builder().bypass_cache(true).reload()).unwrap();
+    assert_eq!(response.url().unwrap(), url);
+    assert_eq!(response.status().unwrap(), 200);
+    assert!(!done!(response.from_cache()));
+    // Requests made after the reload are left alone
+    let (maybe_request, _) = tokio::join!(
+        page.expect_event(page::EventType::Request),
+        page.eval::<()>("() => { fetch(location.href) }")
+    );
+    let req = match maybe_request.unwrap() {
+        page::Event::Request(req) => req,
+        _ => unreachable!()
+    };
+    assert_eq!(req.headers().unwrap().get("pragma"), None);
+    close(&page).await;
+}
+
 async fn navigations(page: &Page, port: u16) {
+    use playwright::api::DocumentLoadState;
     assert_eq!(page.go_back_builder().go_back().await.unwrap(), None);
     let url1 = super::url_static(port, "/empty.html");
     let url2 = super::url_static(port, "/empty2.html");
@@ -144,6 +322,7 @@ async fn navigations(page: &Page, port: u16) {
         let response = page.go_back_builder().go_back().await.unwrap().unwrap();
         assert!(response.ok().unwrap());
         assert_eq!(response.url().unwrap(), url1);
+        assert_eq!(page.url().unwrap(), url1);
     }
     {
         let response = page
@@ -157,6 +336,16 @@ async fn navigations(page: &Page, port: u16) {
     }
     let maybe_response = page.go_forward_builder().go_forward().await.unwrap();
     assert_eq!(maybe_response, None);
+    let response = page
+        .reload_builder()
+        .wait_until(DocumentLoadState::DomContentLoaded)
+        .timeout(10000.)
+        .reload()
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(response.url().unwrap(), url2);
+    assert_eq!(page.url().unwrap(), url2);
 }
 
 async fn set_timeout(page: &Page) {
@@ -205,10 +394,12 @@ async fn ensure_timeout(page: &Page) {
 
 async fn permissions(c: &BrowserContext, page: &Page, port: u16, which: Which) {
     const PERMISSION_DENIED: i32 = 1;
+    const POSITION_UNAVAILABLE: i32 = 2;
+    const TIMEOUT: i32 = 3;
     let snippet = "async () => {
         let getCurrentPositionAsync =
             () => new Promise((resolve, reject) =>
-                navigator.geolocation.getCurrentPosition(resolve, reject));
+                navigator.geolocation.getCurrentPosition(resolve, reject, { timeout: 1000 }));
         let err;
         const result = await getCurrentPositionAsync().catch(e => { err = e; });
         return [result?.coords.latitude, err?.code];
@@ -242,7 +433,11 @@ async fn permissions(c: &BrowserContext, page: &Page, port: u16, which: Which) {
     .unwrap();
     let result = geo().await;
     dbg!(&result);
-    assert_eq!(result.0, Some(59.95))
+    assert_eq!(result.0, Some(59.95));
+    c.set_geolocation(None).await.unwrap();
+    let (latitude, err) = geo().await;
+    assert_eq!(latitude, None);
+    assert!(matches!(err, Some(POSITION_UNAVAILABLE) | Some(TIMEOUT)));
 }
 
 async fn get_permission(p: &Page, name: &str) -> String {
@@ -266,6 +461,270 @@ async fn viewport(c: &BrowserContext) {
     close(&p).await;
 }
 
+async fn screenshot_viewport(c: &BrowserContext) {
+    let p = new(c).await;
+    let v = Viewport {
+        width: 800,
+        height: 600
+    };
+    p.set_viewport_size(v.clone()).await.unwrap();
+    let png = done!(p.screenshot_builder().viewport(1200, 600).screenshot());
+    // Width is the first field of the IHDR chunk
+    let width = u32::from_be_bytes([png[16], png[17], png[18], png[19]]);
+    assert_eq!(width, 1200);
+    let inner_width: i32 = done!(p.eval("() => window.innerWidth"));
+    assert_eq!(inner_width, 800);
+    assert_eq!(p.viewport_size().unwrap(), Some(v));
+    close(&p).await;
+    // A page without a viewport could not get back to having none
+    let b = c.browser().unwrap().unwrap();
+    let c = done!(b.context_builder().no_viewport(true).build());
+    let p = done!(c.new_page());
+    let err = p
+        .screenshot_builder()
+        .viewport(1200, 600)
+        .screenshot()
+        .await
+        .unwrap_err();
+    assert!(matches!(*err, playwright::Error::NoViewport), "{}", err);
+    assert_eq!(p.viewport_size().unwrap(), None);
+    done!(c.close());
+}
+
+async fn expect_request_finished(c: &BrowserContext, port: u16) {
+    let p = new(c).await;
+    done!(p
+        .goto_builder(&super::url_static(port, "/empty.html"))
+        .goto());
+    done!(p.route("**/upload", |route, _| async move {
+        route.fulfill_builder().body("ok").fulfill().await.unwrap();
+    }));
+    done!(p
+        .set_content_builder("<div id=status>idle</div>")
+        .set_content());
+    let (finished, _) = tokio::join!(
+        p.expect_request_finished_builder(|r| r.url().unwrap().ends_with("/upload"))
+            .timeout(5000.)
+            .expect_request_finished(),
+        p.eval::<()>(
+            r#"() => {
+                const body = new FormData();
+                body.append('file', new Blob(['a\n'], {type: 'text/plain'}), 'a.txt');
+                fetch('/upload', {method: 'POST', body})
+                    .then(() => document.getElementById('status').textContent = 'uploaded');
+            }"#
+        )
+    );
+    let request = finished.unwrap();
+    assert_eq!(request.method().unwrap(), "POST");
+    let uploaded = "() => document.getElementById('status').textContent == 'uploaded'";
+    done!(p.wait_for_function_builder(uploaded).wait_for_function());
+    close(&p).await;
+}
+
+async fn locator_screenshot(c: &BrowserContext) {
+    use playwright::api::ScreenshotAnimations;
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(
+            "<div class=box style='width:40px;height:30px;background:red'></div>
+             <div class=box style='width:40px;height:30px;background:blue'></div>"
+        )
+        .set_content());
+    let png = done!(p
+        .locator(".box")
+        .nth(0)
+        .screenshot_builder()
+        .animations(ScreenshotAnimations::Disabled)
+        .mask(vec![p.locator(".box").nth(1)])
+        .screenshot());
+    assert!(png.starts_with(b"\x89PNG"));
+    let width = u32::from_be_bytes([png[16], png[17], png[18], png[19]]);
+    assert_eq!(width, 40);
+    let err = p
+        .locator(".box")
+        .screenshot_builder()
+        .timeout(1000.)
+        .screenshot()
+        .await
+        .unwrap_err();
+    assert!(
+        format!("{:?}", err).contains("strict mode violation"),
+        "{:?}",
+        err
+    );
+    close(&p).await;
+}
+
+async fn goto_about_blank(c: &BrowserContext, port: u16) {
+    let p = new(c).await;
+    done!(p
+        .goto_builder(&super::url_static(port, "/empty.html"))
+        .goto());
+    let response = done!(p.goto_builder("about:blank").goto());
+    assert!(response.is_none());
+    assert_eq!(p.url().unwrap(), "about:blank");
+    done!(p.set_content_builder("<p>hello</p>").set_content());
+    let text: String = done!(p.eval("() => document.querySelector('p').textContent"));
+    assert_eq!(text, "hello");
+    done!(p
+        .goto_builder(&super::url_static(port, "/empty.html"))
+        .goto());
+    let response = done!(p.goto_builder("").goto());
+    assert!(response.is_none());
+    assert_eq!(p.url().unwrap(), "about:blank");
+    close(&p).await;
+}
+
+async fn wait_for_response(c: &BrowserContext, port: u16) {
+    use futures::{channel::mpsc, StreamExt};
+    let p = new(c).await;
+    let (tx, rx) = mpsc::unbounded();
+    p.on_response(move |r| {
+        let tx = tx.clone();
+        async move {
+            tx.unbounded_send(r.status().unwrap()).ok();
+        }
+    })
+    .unwrap();
+    let url = super::url_static(port, "/empty.html");
+    let (response, _) = tokio::join!(
+        p.wait_for_response_builder("**/empty.html")
+            .wait_for_response(),
+        p.goto_builder(&url).goto()
+    );
+    let response = response.unwrap();
+    assert_eq!(response.status().unwrap(), 200);
+    assert!(response.ok().unwrap());
+    assert_eq!(response.request().url().unwrap(), url);
+    let headers = done!(response.headers());
+    assert!(headers.iter().any(|h| h.name == "content-type"));
+    let statuses: Vec<i32> = rx.take(1).collect().await;
+    assert_eq!(statuses, vec![200]);
+    let err = p
+        .wait_for_response_builder("**/never")
+        .timeout(100.0)
+        .wait_for_response()
+        .await
+        .err()
+        .unwrap();
+    assert!(matches!(err, playwright::Error::Timeout));
+    close(&p).await;
+}
+
+async fn on_response_prefetch_body(c: &BrowserContext, port: u16) {
+    use futures::{
+        channel::{mpsc, oneshot},
+        FutureExt, StreamExt
+    };
+    let p = new(c).await;
+    done!(p
+        .goto_builder(&super::url_static(port, "/empty.html"))
+        .goto());
+    done!(p.route("**/api/*", |route, _| async move {
+        route
+            .fulfill_builder()
+            .body("prefetched")
+            .fulfill()
+            .await
+            .unwrap();
+    }));
+    let (go, wait) = oneshot::channel::<()>();
+    let wait = wait.shared();
+    let (tx, rx) = mpsc::unbounded();
+    for prefetch in [true, false] {
+        let (wait, tx) = (wait.clone(), tx.clone());
+        let builder = p.on_response_builder(move |r| {
+            let (wait, tx) = (wait.clone(), tx.clone());
+            async move {
+                if !r.url().unwrap().contains("/api/") {
+                    return;
+                }
+                // Only read the body once the page and its responses are gone
+                wait.await.ok();
+                tx.unbounded_send((prefetch, r.text().await.ok())).ok();
+            }
+        });
+        let builder = if prefetch {
+            builder.prefetch_body("**/api/*")
+        } else {
+            builder
+        };
+        builder.on_response().unwrap();
+    }
+    done!(p.eval::<String>("() => fetch('/api/data').then(r => r.text())"));
+    close(&p).await;
+    go.send(()).unwrap();
+    let mut bodies: Vec<(bool, Option<String>)> = rx.take(2).collect().await;
+    bodies.sort();
+    assert_eq!(
+        bodies,
+        vec![(false, None), (true, Some("prefetched".to_owned()))]
+    );
+}
+
+async fn frames_matching(c: &BrowserContext, port: u16) {
+    let p = new(c).await;
+    let empty = super::url_static(port, "/empty.html");
+    let empty2 = super::url_static(port, "/empty2.html");
+    done!(p
+        .set_content_builder(&format!(
+            r#"<iframe name="a" src="{}"></iframe>
+               <iframe name="b" src="{}"></iframe>
+               <iframe name="c" src="{}"></iframe>"#,
+            empty, empty2, empty2
+        ))
+        .set_content());
+    let url_contains = |s: &'static str| move |f: &Frame| f.url().unwrap().contains(s);
+    assert_eq!(p.frames().unwrap().len(), 4);
+    assert_eq!(p.frames_matching(url_contains("empty2")).unwrap().len(), 2);
+    assert_eq!(p.frames_matching(url_contains("empty")).unwrap().len(), 3);
+    let named = p.frames_matching(|f| f.name().unwrap() == "a").unwrap();
+    assert_eq!(named.len(), 1);
+    assert_eq!(named[0].url().unwrap(), empty);
+    close(&p).await;
+}
+
+async fn scroll_position(c: &BrowserContext) {
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(r#"<div style="width: 5000px; height: 5000px"></div>"#)
+        .set_content());
+    assert_eq!(done!(p.scroll_position()), (0.0, 0.0));
+    done!(p.scroll_to(100.0, 250.0));
+    assert_eq!(done!(p.scroll_position()), (100.0, 250.0));
+    done!(p.scroll_to(0.0, 1_000_000.0));
+    let (x, y) = done!(p.scroll_position());
+    assert_eq!(x, 0.0);
+    assert!(y > 250.0 && y < 5000.0);
+    close(&p).await;
+}
+
+async fn mouse_click_count_and_button(c: &BrowserContext) {
+    use playwright::api::MouseButton;
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(
+            r#"<p id="p" style="margin: 0; font-size: 20px">Hello mouse world.</p>
+            <script>
+              window.menus = 0;
+              document.addEventListener('contextmenu', e => { e.preventDefault(); window.menus++; });
+            </script>"#
+        )
+        .set_content());
+    done!(p.mouse.click_builder(10.0, 10.0).click_count(3).click());
+    let selected: String = done!(p.eval("() => window.getSelection().toString()"));
+    assert_eq!(selected.trim(), "Hello mouse world.");
+    done!(p
+        .mouse
+        .click_builder(10.0, 10.0)
+        .button(MouseButton::Right)
+        .click());
+    let menus: i32 = done!(p.eval("() => window.menus"));
+    assert_eq!(menus, 1);
+    close(&p).await;
+}
+
 async fn download(c: &BrowserContext, port: u16) {
     let p = new(c).await;
     p.set_content_builder(&format!(
@@ -298,12 +757,35 @@ async fn download(c: &BrowserContext, port: u16) {
 
 async fn video(p: &Page) {
     let video = p.video().unwrap().unwrap();
-    dbg!(video.path().unwrap());
-    // TODO
-    // let path = super::temp_dir().join("video.webm");
-    // video.save_as(&path).await.unwrap();
-    // assert!(path.is_file());
-    // video.delete().await.unwrap();
+    let path = video.path().unwrap();
+    assert!(path.starts_with(super::temp_dir().join("video")));
+    assert_eq!(path.extension().unwrap(), "webm");
+}
+
+async fn video_segment(c: &BrowserContext) {
+    use playwright::api::browser::RecordVideo;
+    let p = new(c).await;
+    done!(p.set_content_builder("<button>0</button>").set_content());
+    // Any page that isn't recording a segment is refused
+    let err = p.stop_video().await.err().unwrap();
+    assert!(matches!(*err, playwright::Error::NotRecording), "{}", err);
+    let dir = super::temp_dir().join("video-segment");
+    let recording = done!(p.start_video(RecordVideo {
+        dir: &dir,
+        size: None
+    }));
+    done!(recording
+        .set_content_builder("<button onclick='this.textContent++'>0</button>")
+        .set_content());
+    for _ in 0..5 {
+        done!(recording.click_builder("button").click());
+    }
+    let path = done!(recording.stop_video());
+    assert!(path.starts_with(&dir));
+    assert!(std::fs::metadata(&path).unwrap().len() > 0);
+    // The original page keeps going
+    assert_eq!(done!(p.inner_text("button", None)), "0");
+    close(&p).await;
 }
 
 async fn accessibility(c: &BrowserContext) {
@@ -372,7 +854,7 @@ async fn accessibility(c: &BrowserContext) {
 }
 
 async fn screenshot_should_work(p: &Page) {
-    use playwright::api::ScreenshotType;
+    use playwright::api::{FloatRect, ScreenshotType};
     let path = super::temp_dir().join("screenshot.jpg");
     p.screenshot_builder()
         .r#type(ScreenshotType::Jpeg)
@@ -382,16 +864,164 @@ async fn screenshot_should_work(p: &Page) {
         .await
         .unwrap();
     assert!(path.is_file());
+    done!(p
+        .set_content_builder("<div style='width:50px;height:50px;background:red'></div>")
+        .set_content());
+    let png = done!(p
+        .screenshot_builder()
+        .r#type(ScreenshotType::Png)
+        .clip(FloatRect {
+            x: 0.,
+            y: 0.,
+            width: 50.,
+            height: 50.
+        })
+        .screenshot());
+    assert!(png.starts_with(b"\x89PNG"));
 }
 
 async fn pdf_should_work(p: &Page) {
     let path = super::temp_dir().join("pdf.pdf");
     p.pdf_builder().path(path.clone()).pdf().await.unwrap();
     assert!(path.is_file());
+    done!(p.goto_builder("data:text/html,<h1>hi</h1>").goto());
+    let pdf = done!(p.pdf_builder().format("A4").landscape(true).pdf());
+    assert!(pdf.starts_with(b"%PDF"));
+}
+
+async fn pdf_not_supported(p: &Page) {
+    let err = p.pdf_builder().pdf().await.unwrap_err();
+    assert!(matches!(*err, playwright::Error::PdfNotSupported(_)));
+}
+
+async fn vision_deficiency(c: &BrowserContext) {
+    use playwright::api::VisionDeficiency;
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(
+            r#"<div style="display:flex;height:100vh">
+                <div style="flex:1;background:red"></div>
+                <div style="flex:1;background:green"></div>
+            </div>"#
+        )
+        .set_content());
+    let unfiltered = done!(p.screenshot_builder().screenshot());
+    p.set_vision_deficiency(VisionDeficiency::Deuteranopia)
+        .await
+        .unwrap();
+    let filtered = done!(p.screenshot_builder().screenshot());
+    assert_ne!(filtered, unfiltered);
+    p.set_vision_deficiency(VisionDeficiency::None)
+        .await
+        .unwrap();
+    assert_eq!(done!(p.screenshot_builder().screenshot()), unfiltered);
+    close(&p).await;
+}
+
+async fn vision_deficiency_not_supported(c: &BrowserContext) {
+    use playwright::api::VisionDeficiency;
+    let p = new(c).await;
+    let err = p
+        .set_vision_deficiency(VisionDeficiency::Deuteranopia)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(*err, playwright::Error::CdpNotSupported(_)),
+        "{}",
+        err
+    );
+    close(&p).await;
+}
+
+async fn evaluate_isolated_world(c: &BrowserContext) {
+    let p = new(c).await;
+    done!(p.set_content_builder("<title>shared</title>").set_content());
+    done!(p.eval::<()>("() => { window.shared = 'page'; }"));
+    let set = "x => { window.marker = x; return [document.title, typeof window.shared]; }";
+    let seen: (String, String) = done!(p
+        .evaluate_builder(set)
+        .arg(&42)
+        .isolated_world(true)
+        .evaluate());
+    assert_eq!(seen, ("shared".to_owned(), "undefined".to_owned()));
+    let marker: i32 = done!(p
+        .evaluate_builder("() => window.marker")
+        .isolated_world(true)
+        .evaluate());
+    assert_eq!(marker, 42);
+    let in_main: String = done!(p.eval("() => typeof window.marker"));
+    assert_eq!(in_main, "undefined");
+    let err = p
+        .evaluate_builder("() => { throw new Error('boom'); }")
+        .isolated_world(true)
+        .evaluate::<()>()
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&*err, playwright::Error::Evaluation(m) if m.contains("boom")),
+        "{}",
+        err
+    );
+    // A new document gets a new world
+    done!(p.goto_builder("about:blank").goto());
+    let marker: Option<i32> = done!(p
+        .evaluate_builder("() => window.marker")
+        .isolated_world(true)
+        .evaluate());
+    assert_eq!(marker, None);
+    let not_isolated: i32 = done!(p.evaluate_builder("x => x + 1").arg(&1).evaluate());
+    assert_eq!(not_isolated, 2);
+    close(&p).await;
+}
+
+async fn evaluate_isolated_world_not_supported(c: &BrowserContext) {
+    let p = new(c).await;
+    let err = p
+        .evaluate_builder("() => 1")
+        .isolated_world(true)
+        .evaluate::<i32>()
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(*err, playwright::Error::CdpNotSupported(_)),
+        "{}",
+        err
+    );
+    close(&p).await;
+}
+
+async fn window_bounds(c: &BrowserContext) {
+    use playwright::api::{WindowBounds, WindowState};
+    let p = new(c).await;
+    done!(p.set_fullscreen(true));
+    let bounds = done!(p.window_bounds());
+    assert_eq!(bounds.window_state, Some(WindowState::Fullscreen));
+    done!(p.set_fullscreen(false));
+    let bounds = done!(p.window_bounds());
+    assert_eq!(bounds.window_state, Some(WindowState::Normal));
+    done!(p.set_window_bounds(WindowBounds {
+        width: Some(640),
+        height: Some(480),
+        ..WindowBounds::default()
+    }));
+    let bounds = done!(p.window_bounds());
+    assert_eq!((bounds.width, bounds.height), (Some(640), Some(480)));
+    close(&p).await;
+}
+
+async fn window_bounds_not_supported(c: &BrowserContext) {
+    let p = new(c).await;
+    let err = p.set_fullscreen(true).await.unwrap_err();
+    assert!(
+        matches!(*err, playwright::Error::CdpNotSupported(_)),
+        "{}",
+        err
+    );
+    close(&p).await;
 }
 
 async fn emulate_media(p: &Page) {
-    use playwright::api::page::Media;
+    use playwright::api::{page::Media, ColorScheme};
     let screen = || async {
         p.eval::<bool>("() => matchMedia('screen').matches")
             .await
@@ -421,6 +1051,23 @@ async fn emulate_media(p: &Page) {
         .unwrap();
     assert!(screen().await);
     assert!(!print().await);
+    let dark = || async {
+        p.eval::<bool>("() => matchMedia('(prefers-color-scheme: dark)').matches")
+            .await
+            .unwrap()
+    };
+    p.emulate_media_builder()
+        .color_scheme(ColorScheme::Dark)
+        .emulate_media()
+        .await
+        .unwrap();
+    assert!(dark().await);
+    p.emulate_media_builder()
+        .color_scheme(ColorScheme::Light)
+        .emulate_media()
+        .await
+        .unwrap();
+    assert!(!dark().await);
 }
 
 async fn check_should_work(c: &BrowserContext) {
@@ -435,70 +1082,368 @@ async fn check_should_work(c: &BrowserContext) {
     p.uncheck_builder("input").uncheck().await.unwrap();
     let checked = p.is_checked("input", None).await.unwrap();
     assert!(!checked);
+    // Already unchecked, nothing to do
+    p.uncheck_builder("input").uncheck().await.unwrap();
+    let checked: bool = done!(p.eval("() => document.querySelector('input').checked"));
+    assert!(!checked);
+    // A trial only runs the actionability checks
+    done!(p
+        .set_checked_builder("input", true)
+        .trial(true)
+        .set_checked());
+    assert!(!done!(p.is_checked("input", None)));
+    done!(p.set_checked_builder("input", true).set_checked());
+    let checked: bool = done!(p.eval("() => document.querySelector('input').checked"));
+    assert!(checked);
+    done!(p.set_checked_builder("input", true).set_checked());
+    assert!(done!(p.is_checked("input", None)));
+    done!(p
+        .set_checked_builder("input", false)
+        .force(true)
+        .set_checked());
+    assert!(!done!(p.is_checked("input", None)));
     close(&p).await;
 }
 
-async fn title_should_work(p: &Page) {
-    p.eval::<String>(r#"() => document.title = "foo""#)
-        .await
-        .unwrap();
-    assert_eq!(p.title().await.unwrap(), "foo");
+async fn type_and_press(c: &BrowserContext) {
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(
+            r#"<input id="a"><input id="b"><script>
+            window.keys = [];
+            document.querySelector('#b').addEventListener('keydown', e => keys.push(e.key));
+            </script>"#
+        )
+        .set_content());
+    let start = std::time::Instant::now();
+    done!(p.type_builder("#b", "hello").delay(20.).r#type());
+    assert!(start.elapsed() >= std::time::Duration::from_millis(80));
+    // Typing focused the input first
+    let focused: String = done!(p.eval("() => document.activeElement.id"));
+    assert_eq!(focused, "b");
+    let keys: Vec<String> = done!(p.eval("() => window.keys"));
+    assert_eq!(keys, vec!["h", "e", "l", "l", "o"]);
+    done!(p.press_builder("#b", "Backspace").press());
+    let value: String = done!(p.eval("() => document.querySelector('#b').value"));
+    assert_eq!(value, "hell");
+    done!(p.press_builder("#b", "ControlOrMeta+A").press());
+    done!(p.press_builder("#b", "Delete").press());
+    let value: String = done!(p.eval("() => document.querySelector('#b').value"));
+    assert_eq!(value, "");
+    close(&p).await;
 }
 
-async fn pointer(c: &BrowserContext) {
+async fn keyboard_event_fidelity(c: &BrowserContext) {
     let p = new(c).await;
-    p.set_content_builder(r#"<input type="checkbox" />"#)
-        .set_content()
-        .await
-        .unwrap();
-    let checked = || async {
-        p.eval::<bool>("() => document.querySelector('input').checked")
-            .await
-            .unwrap()
-    };
-    p.tap_builder("input").tap().await.unwrap();
-    assert!(checked().await);
-    p.dblclick_builder("input").dblclick().await.unwrap();
-    assert!(checked().await);
-    p.click_builder("input").click().await.unwrap();
-    assert!(!checked().await);
+    done!(p
+        .set_content_builder(
+            r#"<input><script>
+            window.counts = { keydown: 0, keyup: 0, input: 0 };
+            for (const type of Object.keys(counts)) {
+                document.querySelector('input').addEventListener(type, () => counts[type]++);
+            }
+            </script>"#
+        )
+        .set_content());
+    done!(p.focus("input", None));
+    // Direct insertion only fires `input`
+    done!(p.keyboard.insert_text("嗨"));
+    assert_eq!(key_event_counts(&p).await, vec![0, 0, 1]);
+    done!(p.eval::<()>("() => { counts = { keydown: 0, keyup: 0, input: 0 }; }"));
+    done!(p.keyboard.r#type("ab", None));
+    assert_eq!(key_event_counts(&p).await, vec![2, 2, 2]);
+    done!(p.keyboard.press("Backspace", None));
+    assert_eq!(key_event_counts(&p).await, vec![3, 3, 3]);
+    let value: String = done!(p.eval("() => document.querySelector('input').value"));
+    assert_eq!(value, "嗨a");
     close(&p).await;
 }
 
-async fn new(c: &BrowserContext) -> Page {
-    let page = c.new_page().await.unwrap();
-    set_timeout(&page).await;
-    page
+async fn keyboard_shortcuts(c: &BrowserContext) {
+    let p = new(c).await;
+    done!(p.set_content_builder("<input>").set_content());
+    done!(p.focus("input", None));
+    let keyboard = p.keyboard();
+    done!(keyboard.r#type("hello", None));
+    done!(keyboard.press("Shift+KeyA", None));
+    let value: String = done!(p.eval("() => document.querySelector('input').value"));
+    assert_eq!(value, "helloA");
+    done!(keyboard.press("ControlOrMeta+A", None));
+    done!(keyboard.press("Delete", None));
+    let value: String = done!(p.eval("() => document.querySelector('input').value"));
+    assert_eq!(value, "");
+    done!(keyboard.down("Shift"));
+    done!(keyboard.press("KeyB", None));
+    done!(keyboard.up("Shift"));
+    let value: String = done!(p.eval("() => document.querySelector('input').value"));
+    assert_eq!(value, "B");
+    close(&p).await;
 }
 
-async fn close(p: &Page) { p.close(None).await.unwrap() }
-
-async fn input(c: &BrowserContext) {
+async fn mouse_drawing(c: &BrowserContext) {
     let p = new(c).await;
     done!(p
-        .set_content_builder(r#"<input type="text" value="" />"#)
+        .set_content_builder(
+            r#"<body style="margin:0;height:3000px">
+            <canvas width="200" height="200"></canvas><script>
+            window.moves = 0;
+            const ctx = document.querySelector('canvas').getContext('2d');
+            ctx.lineWidth = 4;
+            window.alpha = (x, y) => ctx.getImageData(x, y, 1, 1).data[3];
+            document.addEventListener('mousemove', e => {
+                moves++;
+                if (e.buttons !== 1) return;
+                ctx.lineTo(e.clientX, e.clientY);
+                ctx.stroke();
+            });
+            document.addEventListener('mousedown', e => {
+                ctx.beginPath();
+                ctx.moveTo(e.clientX, e.clientY);
+            });
+            </script></body>"#
+        )
         .set_content());
-    assert_eq!(
-        done!(p.get_attribute("input", "type", None)).as_deref(),
-        Some("text")
-    );
-    done!(p.fill_builder("input", "foo").fill());
-    // assert_eq!(
-    //    done!(p.get_attribute("input", "value", None)).as_deref(),
-    //    Some("foo")
-    //);
-    // TODO
+    let mouse = p.mouse();
+    done!(mouse.r#move(20., 100., None));
+    done!(mouse.down(None, None));
+    done!(mouse.r#move(180., 100., Some(10)));
+    done!(mouse.up(None, None));
+    // One event for the first move, then one per step
+    let moves: u32 = done!(p.eval("() => window.moves"));
+    assert_eq!(moves, 11);
+    let drawn: u8 = done!(p.eval("() => alpha(100, 100)"));
+    assert_eq!(drawn, 255);
+    let blank: u8 = done!(p.eval("() => alpha(100, 20)"));
+    assert_eq!(blank, 0);
+    done!(mouse.wheel(0., 500.));
+    done!(p
+        .wait_for_function_builder("() => window.scrollY === 500")
+        .wait_for_function());
     close(&p).await;
 }
 
-async fn set_extra_http_headers(c: &BrowserContext, port: u16) {
+async fn touchscreen_tap(c: &BrowserContext) {
     let p = new(c).await;
-    p.set_extra_http_headers(vec![("hoge".into(), "hoge".into())])
+    done!(p
+        .set_content_builder(
+            r#"<button style="position:absolute;left:0;top:0;width:100px;height:100px">tap</button>
+            <script>
+            window.touches = 0;
+            document.querySelector('button').addEventListener('touchend', () => touches++);
+            </script>"#
+        )
+        .set_content());
+    done!(p.touchscreen().tap(50., 50.));
+    done!(p.tap_builder("button").tap());
+    let touches: u32 = done!(p.eval("() => window.touches"));
+    assert_eq!(touches, 2);
+    close(&p).await;
+}
+
+// keydown, keyup and input events
+async fn key_event_counts(p: &Page) -> Vec<u32> {
+    done!(p.eval("() => [counts.keydown, counts.keyup, counts.input]"))
+}
+
+async fn title_should_work(p: &Page) {
+    p.eval::<String>(r#"() => document.title = "foo""#)
         .await
         .unwrap();
-    let url = super::url_static(port, "/empty.html");
-    let (maybe_request, _) = tokio::join!(
-        p.expect_event(page::EventType::Request),
+    assert_eq!(p.title().await.unwrap(), "foo");
+}
+
+async fn set_content_title(c: &BrowserContext) {
+    let p = new(c).await;
+    p.set_content_builder("<title>My Page</title><p>hello</p>")
+        .set_content()
+        .await
+        .unwrap();
+    assert_eq!(p.title().await.unwrap(), "My Page");
+    p.set_content_builder("<p>untitled</p>")
+        .set_content()
+        .await
+        .unwrap();
+    assert_eq!(p.title().await.unwrap(), "");
+    p.set_content_builder("<title>Another Page</title>")
+        .set_content()
+        .await
+        .unwrap();
+    assert_eq!(p.title().await.unwrap(), "Another Page");
+    close(&p).await;
+}
+
+async fn wait_for_load_state(c: &BrowserContext, port: u16) {
+    use playwright::api::DocumentLoadState;
+    let p = new(c).await;
+    let url = super::url_static(port, "/empty.html");
+    done!(p.goto_builder(&url).goto());
+    done!(p.eval::<()>("() => { history.pushState({}, '', '#pushed') }"));
+    done!(p.wait_for_load_state(DocumentLoadState::NetworkIdle, Some(5000.)));
+    done!(p.wait_for_load_state(DocumentLoadState::DomContentLoaded, None));
+    done!(p.wait_for_load_state(DocumentLoadState::Load, None));
+    close(&p).await;
+}
+
+async fn expect_navigation(c: &BrowserContext, port: u16) {
+    let p = new(c).await;
+    let url1 = super::url_static(port, "/empty.html");
+    let url2 = super::url_static(port, "/empty2.html");
+    done!(p.goto_builder(&url1).goto());
+    done!(p.eval::<()>("() => { document.body.innerHTML = '<a href=\"empty2.html\">next</a>' }"));
+    let (response, _) = tokio::join!(
+        p.expect_navigation_builder()
+            .url("**/empty2.html")
+            .expect_navigation(),
+        p.click_builder("a").click()
+    );
+    let response = response.unwrap().unwrap();
+    assert_eq!(response.url().unwrap(), url2);
+    assert!(response.ok().unwrap());
+    let (response, _) = tokio::join!(
+        p.expect_navigation_builder().expect_navigation(),
+        p.eval::<()>("() => { history.pushState({}, '', '#pushed') }")
+    );
+    assert_eq!(response.unwrap(), None);
+    let err = p
+        .expect_navigation_builder()
+        .url("**/never.html")
+        .timeout(100.)
+        .expect_navigation()
+        .await
+        .unwrap_err();
+    assert!(matches!(*err, playwright::Error::Timeout), "{}", err);
+    close(&p).await;
+}
+
+async fn referrer_policy(c: &BrowserContext, port: u16) {
+    let p = new(c).await;
+    let url1 = super::url_static(port, "/empty.html");
+    let url2 = super::url_static(port, "/empty2.html");
+    // Same origin navigations send the full url by default
+    done!(p.goto_builder(&url1).goto());
+    assert_eq!(follow_link(&p).await, Some(url1.clone()));
+    // A document policy restricting the referrer to the origin
+    done!(p.goto_builder(&url1).goto());
+    done!(p.eval::<()>(
+        "() => { const m = document.createElement('meta'); m.name = 'referrer'; \
+         m.content = 'origin'; document.head.appendChild(m); }"
+    ));
+    assert_eq!(
+        follow_link(&p).await,
+        Some(format!("{}/", super::origin(port)))
+    );
+    done!(p.goto_builder(&url1).goto());
+    done!(p.eval::<()>(
+        "() => { const m = document.createElement('meta'); m.name = 'referrer'; \
+         m.content = 'no-referrer'; document.head.appendChild(m); }"
+    ));
+    assert_eq!(follow_link(&p).await, None);
+    // An explicit referer is sent as is
+    let response = done!(p.goto_builder(&url2).referer(&url1).goto()).unwrap();
+    let headers = response.request().headers().unwrap();
+    assert_eq!(headers.get("referer"), Some(&url1));
+    close(&p).await;
+}
+
+async fn follow_link(p: &Page) -> Option<String> {
+    done!(p.eval::<()>("() => { document.body.innerHTML = '<a href=\"empty2.html\">next</a>' }"));
+    let (response, _) = tokio::join!(
+        p.expect_navigation_builder()
+            .url("**/empty2.html")
+            .expect_navigation(),
+        p.click_builder("a").click()
+    );
+    let headers = response.unwrap().unwrap().request().headers().unwrap();
+    headers.get("referer").cloned()
+}
+
+async fn wait_for_function_polling(c: &BrowserContext) {
+    use playwright::api::frame::Polling;
+    let p = new(c).await;
+    p.eval::<()>("() => { setTimeout(() => window.ready = true, 100) }")
+        .await
+        .unwrap();
+    let mut handle = p
+        .wait_for_function_builder("() => window.ready === true")
+        .polling(Polling::Millis(20))
+        .timeout(5000.)
+        .wait_for_function()
+        .await
+        .unwrap();
+    assert!(handle.json_value::<bool>().await.unwrap());
+    p.eval::<()>("() => { requestAnimationFrame(() => window.count = 3) }")
+        .await
+        .unwrap();
+    done!(p
+        .wait_for_function_builder("n => window.count === n")
+        .arg(&3)
+        .polling(Polling::RequestAnimationFrame)
+        .wait_for_function());
+    let err = p
+        .wait_for_function_builder("() => window.never")
+        .timeout(100.)
+        .wait_for_function()
+        .await
+        .err()
+        .unwrap();
+    assert!(matches!(*err, playwright::Error::Timeout), "{}", err);
+    close(&p).await;
+}
+
+async fn pointer(c: &BrowserContext) {
+    let p = new(c).await;
+    p.set_content_builder(r#"<input type="checkbox" />"#)
+        .set_content()
+        .await
+        .unwrap();
+    let checked = || async {
+        p.eval::<bool>("() => document.querySelector('input').checked")
+            .await
+            .unwrap()
+    };
+    p.tap_builder("input").tap().await.unwrap();
+    assert!(checked().await);
+    p.dblclick_builder("input").dblclick().await.unwrap();
+    assert!(checked().await);
+    p.click_builder("input").click().await.unwrap();
+    assert!(!checked().await);
+    close(&p).await;
+}
+
+async fn new(c: &BrowserContext) -> Page {
+    let page = c.new_page().await.unwrap();
+    set_timeout(&page).await;
+    page
+}
+
+async fn close(p: &Page) { p.close(None).await.unwrap() }
+
+async fn input(c: &BrowserContext) {
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(r#"<input type="text" value="" />"#)
+        .set_content());
+    assert_eq!(
+        done!(p.get_attribute("input", "type", None)).as_deref(),
+        Some("text")
+    );
+    done!(p.fill_builder("input", "foo").fill());
+    // assert_eq!(
+    //    done!(p.get_attribute("input", "value", None)).as_deref(),
+    //    Some("foo")
+    //);
+    // TODO
+    close(&p).await;
+}
+
+async fn set_extra_http_headers(c: &BrowserContext, port: u16) {
+    let p = new(c).await;
+    p.set_extra_http_headers(vec![("hoge".into(), "hoge".into())])
+        .await
+        .unwrap();
+    let url = super::url_static(port, "/empty.html");
+    let (maybe_request, _) = tokio::join!(
+        p.expect_event(page::EventType::Request),
         p.goto_builder(&url).goto()
     );
     let req = match maybe_request.unwrap() {
@@ -568,38 +1513,885 @@ async fn query_selector_and_eval(c: &BrowserContext) {
     close(&p).await;
 }
 
-// async fn file_chooser(c: &BrowserContext, port: u16) {
-//    let p = new(c).await;
-//    let url = super::url_static(port, "/form.html");
-//    p.goto_builder(&url).goto().await.unwrap();
-//    let (maybe_file_chooser, _) = tokio::join!(
-//        p.expect_event(page::EventType::FileChooser),
-//        p.click_builder("input[type=file]").click()
-//    );
-//    let file_chooser = match maybe_file_chooser.unwrap() {
-//        page::Event::FileChooser(file_chooser) => file_chooser,
-//        _ => unreachable!()
-//    };
-//    assert_eq!(file_chooser.page(), p);
-//    assert!(file_chooser.is_multiple());
-//    assert_eq!(
-//        file_chooser.element(),
-//        p.query_selector("input[type=file]").await.unwrap().unwrap()
-//    );
-//    file_chooser
-//        .set_input_files_builder(File {
-//            name: "a".into(),
-//            mime: "text/plain".into(),
-//            buffer: "a\n".into()
-//        })
-//        .add_file(File {
-//            name: "b".into(),
-//            mime: "text/plain".into(),
-//            buffer: "b\n".into()
-//        })
-//        .set_input_files()
-//        .await
-//        .unwrap();
-//    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
-//    close(&p).await;
-//}
+async fn expose_binding_handle(c: &BrowserContext) {
+    use playwright::api::BindingCall;
+    let p = new(c).await;
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    p.expose_binding_builder("clicked", move |call: BindingCall| {
+        let tx = tx.clone();
+        async move {
+            let element = call
+                .element_handle()
+                .map_err(|e| e.to_string())?
+                .ok_or("no handle")?;
+            let tag: String = call
+                .frame()
+                .evaluate("e => e.tagName", element)
+                .await
+                .map_err(|e| e.to_string())?;
+            let _ = tx.send(tag.clone());
+            Ok(tag)
+        }
+    })
+    .handle(true)
+    .expose_binding()
+    .await
+    .unwrap();
+    done!(p
+        .set_content_builder(r#"<button onclick="clicked(this)">Click me</button>"#)
+        .set_content());
+    done!(p.click_builder("button").click());
+    assert_eq!(rx.recv().await.as_deref(), Some("BUTTON"));
+    close(&p).await;
+}
+
+async fn expose_function(c: &BrowserContext) {
+    use std::sync::Arc;
+    let p = new(c).await;
+    let second_served = Arc::new(tokio::sync::Notify::new());
+    p.expose_function("sha", move |(text,): (String,)| {
+        let second_served = second_served.clone();
+        async move {
+            // The first call can only resolve once the second one has been served
+            if text == "first" {
+                second_served.notified().await;
+            } else {
+                second_served.notify_one();
+            }
+            Ok(sha(&text))
+        }
+    })
+    .await
+    .unwrap();
+    let digests: Vec<String> = p
+        .eval(r#"() => Promise.all([window.sha("first"), window.sha("second")])"#)
+        .await
+        .unwrap();
+    assert_eq!(digests, vec![sha("first"), sha("second")]);
+    assert!(p.eval::<String>("() => window.sha(42)").await.is_err());
+    close(&p).await;
+}
+
+async fn remove_exposed_function(c: &BrowserContext) {
+    let p = new(c).await;
+    p.expose_function("sha", |(text,): (String,)| async move { Ok(sha(&text)) })
+        .await
+        .unwrap();
+    let digest: String = p.eval(r#"() => window.sha("a")"#).await.unwrap();
+    assert_eq!(digest, sha("a"));
+    p.remove_exposed_function("sha").await.unwrap();
+    assert!(p.eval::<String>(r#"() => window.sha("a")"#).await.is_err());
+    // The function can't be uninstalled, only its calls are rejected
+    let kind: String = p.eval("() => typeof window.sha").await.unwrap();
+    assert_eq!(kind, "function");
+    // Removing an unknown or already removed name does nothing
+    p.remove_exposed_function("sha").await.unwrap();
+    p.remove_exposed_function("missing").await.unwrap();
+    // The name can be exposed again
+    p.expose_function("sha", |(text,): (String,)| async move {
+        Ok(text.to_uppercase())
+    })
+    .await
+    .unwrap();
+    let upper: String = p.eval(r#"() => window.sha("a")"#).await.unwrap();
+    assert_eq!(upper, "A");
+    close(&p).await;
+}
+
+// FNV-1a, standing in for a digest the page can't compute by itself
+fn sha(text: &str) -> String {
+    let hash = text.bytes().fold(0x811c9dc5u32, |h, b| {
+        (h ^ b as u32).wrapping_mul(0x01000193)
+    });
+    format!("{:08x}", hash)
+}
+
+async fn wait_for_actionable_selector(c: &BrowserContext) {
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(
+            r#"<button>go</button><div id="overlay" style="position:fixed;inset:0"></div>"#
+        )
+        .set_content());
+    done!(p.eval::<()>(
+        "() => { setTimeout(() => document.getElementById('overlay').remove(), 500); }"
+    ));
+    let button = done!(p
+        .wait_for_selector_builder("button")
+        .check_actionability(true)
+        .wait_for_selector())
+    .unwrap();
+    assert!(!done!(
+        p.eval::<bool>("() => !!document.getElementById('overlay')")
+    ));
+    assert_eq!(done!(button.inner_text()), "go");
+    close(&p).await;
+}
+
+async fn wait_for_push_state(c: &BrowserContext, port: u16) {
+    let p = new(c).await;
+    p.goto_builder(&super::url_static(port, "/empty.html"))
+        .goto()
+        .await
+        .unwrap();
+    done!(p.eval::<()>(
+        r#"() => {
+            document.body.innerHTML = `<button onclick="history.pushState({}, '', '/spa')">go</button>`;
+        }"#
+    ));
+    let url = format!("{}/spa", super::origin(port));
+    let (waited, clicked) = tokio::join!(
+        p.wait_for_url(&url, Some(5000.)),
+        p.click_builder("button").click()
+    );
+    clicked.unwrap();
+    waited.unwrap();
+    assert_eq!(p.url().unwrap(), url);
+    // Without a timeout
+    let re = regex::Regex::new(r"/spa\?step=2$").unwrap();
+    let (waited, replaced) = tokio::join!(
+        p.wait_for_url(re, Some(0.)),
+        p.eval::<()>("() => history.replaceState({}, '', '/spa?step=2')")
+    );
+    replaced.unwrap();
+    waited.unwrap();
+    // Already there
+    let step = UrlMatcher::predicate(|url| url.ends_with("step=2"));
+    done!(p.wait_for_url(step, None));
+    close(&p).await;
+}
+
+async fn set_input_files_payload(c: &BrowserContext) {
+    let p = new(c).await;
+    p.set_content_builder("<input type=file>")
+        .set_content()
+        .await
+        .unwrap();
+    let file = FilePayload::new("a.txt".into(), "text/plain".into(), b"a\n".to_vec());
+    p.set_input_files_builder("input")
+        .add_file(file)
+        .set_input_files()
+        .await
+        .unwrap();
+    let name: String = done!(p.eval("() => document.querySelector('input').files[0].name"));
+    assert_eq!(name, "a.txt");
+    close(&p).await;
+}
+
+async fn select_option(c: &BrowserContext) {
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(
+            r#"<select id="one"><option value="r">Red</option><option value="g">Green</option>
+            <option value="b">Blue</option></select>
+            <select id="many" multiple><option value="r">Red</option><option value="g">Green</option>
+            <option value="b">Blue</option></select>"#
+        )
+        .set_content());
+    let selected = done!(p
+        .select_option_builder("#one")
+        .add_label("Green".into())
+        .select_option());
+    assert_eq!(selected, vec!["g".to_owned()]);
+    let value: String = done!(p.eval("() => document.querySelector('#one').value"));
+    assert_eq!(value, "g");
+    let many = done!(p.query_selector("#many")).unwrap();
+    let selected = done!(many
+        .select_option_builder()
+        .add_value("r".into())
+        .add_index(2)
+        .select_option());
+    assert_eq!(selected, vec!["r".to_owned(), "b".to_owned()]);
+    let blue = done!(p.query_selector("#one option[value=b]")).unwrap();
+    let selected = done!(p
+        .select_option_builder("#one")
+        .add_element(&blue)
+        .select_option());
+    assert_eq!(selected, vec!["b".to_owned()]);
+    close(&p).await;
+}
+
+async fn file_chooser(c: &BrowserContext) {
+    let p = new(c).await;
+    p.set_content_builder("<input type=file multiple>")
+        .set_content()
+        .await
+        .unwrap();
+    p.on_file_chooser(|chooser| async move {
+        assert!(chooser.is_multiple());
+        let file = FilePayload::new("b.txt".into(), "text/plain".into(), b"b\n".to_vec());
+        chooser
+            .set_input_files_builder()
+            .add_file(file)
+            .set_input_files()
+            .await
+            .unwrap();
+    })
+    .await
+    .unwrap();
+    p.click_builder("input").click().await.unwrap();
+    p.wait_for_function_builder("() => document.querySelector('input').files.length == 1")
+        .wait_for_function()
+        .await
+        .unwrap();
+    let name: String = done!(p.eval("() => document.querySelector('input').files[0].name"));
+    assert_eq!(name, "b.txt");
+    close(&p).await;
+}
+
+async fn expect_file_chooser(c: &BrowserContext) {
+    let p = new(c).await;
+    done!(p.set_content_builder("<input type=file>").set_content());
+    let (chooser, clicked) = tokio::join!(
+        p.expect_file_chooser_builder().expect_file_chooser(),
+        p.click_builder("input").click()
+    );
+    clicked.unwrap();
+    let chooser = chooser.unwrap();
+    assert!(!chooser.is_multiple());
+    let file = FilePayload::new("c.txt".into(), "text/plain".into(), b"c\n".to_vec());
+    done!(chooser
+        .set_input_files_builder()
+        .add_file(file)
+        .set_input_files());
+    let name: String = done!(p.eval("() => document.querySelector('input').files[0].name"));
+    assert_eq!(name, "c.txt");
+    done!(p.set_content_builder("<p>none</p>").set_content());
+    let timed_out = p
+        .expect_file_chooser_builder()
+        .timeout(100.0)
+        .expect_file_chooser()
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(*timed_out, playwright::Error::Timeout),
+        "{}",
+        timed_out
+    );
+    close(&p).await;
+}
+
+async fn add_init_script_path(c: &BrowserContext, port: u16) {
+    let p = new(c).await;
+    let missing = super::temp_dir().join("missing-init-script.js");
+    assert!(p.add_init_script_path(&missing).await.is_err());
+    let path = super::temp_dir().join("init-script.js");
+    std::fs::write(&path, "window.injected = 42;").unwrap();
+    done!(p.add_init_script_path(&path));
+    let url = super::url_static(port, "/empty.html");
+    done!(p.goto_builder(&url).goto());
+    assert_eq!(done!(p.eval::<i32>("() => window.injected")), 42);
+    close(&p).await;
+}
+
+async fn add_init_script_frames(c: &BrowserContext, port: u16) {
+    let p = new(c).await;
+    done!(p.add_init_script("window.__injected = 42;"));
+    done!(p
+        .goto_builder(&super::url_static(port, "/empty.html"))
+        .goto());
+    assert_eq!(done!(p.eval::<i32>("() => window.__injected")), 42);
+    let attach = format!(
+        "() => new Promise(resolve => {{
+            const frame = document.createElement('iframe');
+            frame.src = '{}';
+            frame.onload = resolve;
+            document.body.appendChild(frame);
+        }})",
+        super::url_static(port, "/empty2.html")
+    );
+    done!(p.eval::<()>(&attach));
+    let frames = p.frames().unwrap();
+    assert_eq!(frames.len(), 2);
+    for f in frames {
+        assert_eq!(done!(f.eval::<i32>("() => window.__injected")), 42);
+    }
+    close(&p).await;
+}
+
+async fn response_body_cached(c: &BrowserContext, port: u16) {
+    let p = new(c).await;
+    let url = super::url_static(port, "/simple.json");
+    let response = done!(p.goto_builder(&url).goto()).unwrap();
+    let text = done!(response.text());
+    let json: serde_json::Value = done!(response.json());
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&text).unwrap(),
+        json
+    );
+    assert_eq!(json["foo"], "bar");
+    assert_eq!(done!(response.body()), text.into_bytes());
+    close(&p).await;
+}
+
+async fn shadow_dom_piercing(c: &BrowserContext) {
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(
+            r#"<my-widget></my-widget>
+            <script>
+                customElements.define('my-widget', class extends HTMLElement {
+                    constructor() {
+                        super();
+                        const root = this.attachShadow({ mode: 'open' });
+                        root.innerHTML = '<button id="inner" onclick="window.clicked = true">inside</button>';
+                    }
+                });
+            </script>"#
+        )
+        .set_content());
+    let button = done!(p.query_selector("#inner")).unwrap();
+    done!(button.click_builder().click());
+    assert!(done!(p.eval::<bool>("() => window.clicked")));
+    done!(p.click_builder("my-widget button").click());
+    assert!(done!(p.query_selector("xpath=//button")).is_none());
+    close(&p).await;
+}
+
+async fn aria_snapshot(c: &BrowserContext) {
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(r#"<nav><a href="/">Home</a><a href="/about">About</a></nav>"#)
+        .set_content());
+    let snapshot = done!(p.locator("nav").aria_snapshot(None));
+    assert!(snapshot.contains("- navigation"));
+    assert!(snapshot.contains(r#"- link "Home""#));
+    assert!(snapshot.contains(r#"- link "About""#));
+    close(&p).await;
+}
+
+async fn frame_text_readers(c: &BrowserContext) {
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(
+            r#"<iframe srcdoc="<p id='t' title='greeting'>Hello <b>frame</b></p>"></iframe>"#
+        )
+        .set_content());
+    let frame = p.main_frame().child_frames().unwrap().remove(0);
+    assert_eq!(done!(frame.inner_text("#t", None)), "Hello frame");
+    assert_eq!(
+        done!(frame.text_content("#t", None)).as_deref(),
+        Some("Hello frame")
+    );
+    assert_eq!(done!(frame.inner_html("#t", None)), "Hello <b>frame</b>");
+    assert_eq!(
+        done!(frame.get_attribute("#t", "title", None)).as_deref(),
+        Some("greeting")
+    );
+    close(&p).await;
+}
+
+async fn screenshot_timeout(c: &BrowserContext) {
+    // A context of its own keeps the busy renderer away from the pages of the other tests
+    let c = done!(c.browser().unwrap().unwrap().context_builder().build());
+    let p = new(&c).await;
+    // Keeps the renderer busy for longer than the timeout so that no frame can be captured meanwhile
+    done!(p.eval::<()>(
+        "() => { setTimeout(() => { const end = Date.now() + 3000; while (Date.now() < end) {} }, 0); }"
+    ));
+    let err = p
+        .screenshot_builder()
+        .timeout(500.)
+        .screenshot()
+        .await
+        .unwrap_err();
+    assert!(matches!(*err, playwright::Error::Timeout));
+    done!(c.close());
+}
+
+async fn wait_for_delayed_selector(c: &BrowserContext) {
+    use playwright::api::frame::FrameState;
+    let p = new(c).await;
+    done!(p.eval::<()>(
+        "() => { setTimeout(() => document.body.innerHTML = '<span id=late>here</span>', 300); }"
+    ));
+    let e = done!(p
+        .wait_for_selector_builder("#late")
+        .state(FrameState::Attached)
+        .strict(true)
+        .wait_for_selector());
+    assert_eq!(done!(e.unwrap().inner_text()), "here");
+    let err = p
+        .main_frame()
+        .wait_for_selector_builder("#never")
+        .timeout(200.)
+        .wait_for_selector()
+        .await
+        .unwrap_err();
+    assert!(matches!(*err, playwright::Error::Timeout));
+    close(&p).await;
+}
+
+async fn locator_re_resolves(c: &BrowserContext) {
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(
+            r#"<div id="root"><button onclick="window.clicks = (window.clicks || 0) + 1">go</button></div>
+            <input id="name"><ul><li>a</li><li>b</li></ul>"#
+        )
+        .set_content());
+    let button = p.locator("#root button");
+    done!(button.click(None));
+    // Replace the button with a fresh node; the locator must find the new one
+    done!(p.eval::<()>(
+        "() => { document.getElementById('root').innerHTML = '<button onclick=\"window.clicks = (window.clicks || 0) + 10\">go</button>'; }"
+    ));
+    done!(button.click(None));
+    assert_eq!(done!(p.eval::<i32>("() => window.clicks")), 11);
+    assert!(done!(button.is_visible()));
+    assert_eq!(done!(button.inner_text(None)), "go");
+    let items = p.locator("li");
+    assert_eq!(done!(items.count()), 2);
+    assert_eq!(done!(items.nth(1).text_content(None)).as_deref(), Some("b"));
+    assert!(items.inner_text(Some(1000.)).await.is_err());
+    let input = p.locator("#name");
+    done!(input.fill("hello", None));
+    assert_eq!(
+        done!(p.eval::<String>("() => document.getElementById('name').value")),
+        "hello"
+    );
+    close(&p).await;
+}
+
+async fn get_by_locators(c: &BrowserContext) {
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(
+            r#"<button>OK</button><button>Not OK</button><button disabled>Cancel</button>
+            <h2>Title</h2><label>Name <input id="name"></label>
+            <input placeholder="Search here"><span data-testid="status">ready</span>"#
+        )
+        .set_content());
+    let ok = p.get_by_role(
+        "button",
+        GetByRoleOptions {
+            name: Some("OK".into()),
+            exact: true,
+            ..Default::default()
+        }
+    );
+    assert_eq!(done!(ok.count()), 1);
+    assert_eq!(done!(ok.inner_text(None)), "OK");
+    let loose = p.get_by_role(
+        "button",
+        GetByRoleOptions {
+            name: Some("ok".into()),
+            ..Default::default()
+        }
+    );
+    assert_eq!(done!(loose.count()), 2);
+    let disabled = p.get_by_role(
+        "button",
+        GetByRoleOptions {
+            disabled: Some(true),
+            ..Default::default()
+        }
+    );
+    assert_eq!(done!(disabled.inner_text(None)), "Cancel");
+    let heading = p.get_by_role(
+        "heading",
+        GetByRoleOptions {
+            level: Some(2),
+            ..Default::default()
+        }
+    );
+    assert_eq!(done!(heading.inner_text(None)), "Title");
+    assert_eq!(done!(p.get_by_text("not ok", false).count()), 1);
+    assert_eq!(done!(p.get_by_text("ok", true).count()), 0);
+    done!(p.get_by_label("Name", false).fill("Alice", None));
+    assert_eq!(
+        done!(p.eval::<String>("() => document.getElementById('name').value")),
+        "Alice"
+    );
+    assert_eq!(done!(p.get_by_placeholder("search", false).count()), 1);
+    assert_eq!(done!(p.get_by_placeholder("search", true).count()), 0);
+    assert_eq!(
+        done!(p.get_by_test_id("status").text_content(None)).as_deref(),
+        Some("ready")
+    );
+    close(&p).await;
+}
+
+async fn get_by_role_normalized_name(c: &BrowserContext) {
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(
+            r#"<button>
+              Submit
+              order
+            </button><button aria-label="  Cancel  ">x</button>
+            <label for="c"> Remember   me </label><input id="c" type="checkbox">"#
+        )
+        .set_content());
+    let by_name = |role: &str, name: &str| {
+        p.get_by_role(
+            role,
+            GetByRoleOptions {
+                name: Some(name.into()),
+                exact: true,
+                ..Default::default()
+            }
+        )
+    };
+    assert_eq!(done!(by_name("button", "Submit order").count()), 1);
+    assert_eq!(done!(by_name("button", "  Submit  order ").count()), 1);
+    assert_eq!(done!(by_name("button", "Cancel").count()), 1);
+    assert_eq!(done!(by_name("button", "Submit").count()), 0);
+    assert_eq!(done!(by_name("checkbox", "Remember me").count()), 1);
+    close(&p).await;
+}
+
+async fn locator_text_whitespace(c: &BrowserContext) {
+    let p = new(c).await;
+    done!(p
+        .set_content_builder("<div id=\"a\">\n    Hello\n      <b>big</b>\n    world\n</div>")
+        .set_content());
+    let l = p.locator("#a");
+    assert_eq!(
+        done!(l.text_content(None)).as_deref(),
+        Some("\n    Hello\n      big\n    world\n")
+    );
+    assert_eq!(done!(l.inner_text(None)), "Hello big world");
+    close(&p).await;
+}
+
+async fn locator_all_texts(c: &BrowserContext) {
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(
+            r#"<ul><li>One</li><li>  Two  </li><li>Three<span hidden> hidden</span></li></ul>"#
+        )
+        .set_content());
+    let items = p.locator("li");
+    assert_eq!(done!(items.all_inner_texts()), vec!["One", "Two", "Three"]);
+    assert_eq!(
+        done!(items.all_text_contents()),
+        vec!["One", "  Two  ", "Three hidden"]
+    );
+    assert!(done!(p.locator("ol > li").all_inner_texts()).is_empty());
+    close(&p).await;
+}
+
+async fn route_fulfill_and_continue(c: &BrowserContext, port: u16) {
+    let p = new(c).await;
+    done!(p
+        .goto_builder(&super::url_static(port, "/empty.html"))
+        .goto());
+    done!(p.route("**/mocked.json", |route, _| async move {
+        route
+            .fulfill_builder()
+            .status(201)
+            .content_type("application/json")
+            .body(r#"{"mocked": true}"#)
+            .fulfill()
+            .await
+            .unwrap();
+    }));
+    let json = super::url_static(port, "/simple.json");
+    let re = regex::Regex::new(r"/rewritten$").unwrap();
+    done!(p.route(re, move |route, request| {
+        let json = json.clone();
+        async move {
+            assert_eq!(request.method().unwrap(), "POST");
+            route
+                .continue_builder()
+                .url(&json)
+                .method("GET")
+                .r#continue()
+                .await
+                .unwrap();
+        }
+    }));
+    let mocked: serde_json::Value = done!(p.eval(
+        "async () => { const r = await fetch('mocked.json'); return { status: r.status, type: r.headers.get('content-type'), body: await r.json() }; }"
+    ));
+    assert_eq!(
+        mocked,
+        serde_json::json!({"status": 201, "type": "application/json", "body": {"mocked": true}})
+    );
+    let rewritten: serde_json::Value =
+        done!(p.eval("async () => (await fetch('rewritten', { method: 'POST' })).json()"));
+    assert_eq!(rewritten["foo"], "bar");
+    done!(p.unroute("**/mocked.json"));
+    let status: i32 = done!(p.eval("async () => (await fetch('mocked.json')).status"));
+    assert_eq!(status, 404);
+    close(&p).await;
+}
+
+async fn goto_retries(c: &BrowserContext, port: u16) {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc
+    };
+    let p = new(c).await;
+    let attempts = Arc::new(AtomicUsize::new(0));
+    let counter = attempts.clone();
+    // Resets the connection on the first attempt only
+    done!(p.route("**/flaky.html", move |route, _| {
+        let counter = counter.clone();
+        async move {
+            if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                route
+                    .abort(Some(AbortErrorCode::ConnectionReset))
+                    .await
+                    .unwrap();
+            } else {
+                route
+                    .fulfill_builder()
+                    .body("<p>ok</p>")
+                    .fulfill()
+                    .await
+                    .unwrap();
+            }
+        }
+    }));
+    let url = super::url_static(port, "/flaky.html");
+    let response = done!(p.goto_builder(&url).retries(2).goto()).unwrap();
+    assert!(response.ok().unwrap());
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    // Failures other than connection ones are not retried
+    let denied = Arc::new(AtomicUsize::new(0));
+    let counter = denied.clone();
+    done!(p.route("**/denied.html", move |route, _| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        async move {
+            route
+                .abort(Some(AbortErrorCode::AccessDenied))
+                .await
+                .unwrap();
+        }
+    }));
+    let url = super::url_static(port, "/denied.html");
+    assert!(p.goto_builder(&url).retries(2).goto().await.is_err());
+    assert_eq!(denied.load(Ordering::SeqCst), 1);
+    // Nor are error statuses, which aren't failures
+    let url = super::url_static(port, "/missing.html");
+    let response = done!(p.goto_builder(&url).retries(2).goto()).unwrap();
+    assert_eq!(response.status().unwrap(), 404);
+    close(&p).await;
+}
+
+async fn scroll_into_view(c: &BrowserContext) {
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(
+            r#"<div style="height:5000px"></div><button>far</button><span hidden>hidden</span>"#
+        )
+        .set_content());
+    let viewport_height: f64 = done!(p.eval("() => window.innerHeight"));
+    let button = done!(p.query_selector("button")).unwrap();
+    let before = done!(button.bounding_box()).unwrap();
+    assert!(before.y > viewport_height);
+    done!(button.scroll_into_view_if_needed(None));
+    let after = done!(button.bounding_box()).unwrap();
+    assert!(
+        after.y >= 0. && after.y + after.height <= viewport_height,
+        "{:?}",
+        after
+    );
+    assert_eq!((after.width, after.height), (before.width, before.height));
+    let hidden = done!(p.query_selector("span")).unwrap();
+    assert_eq!(done!(hidden.bounding_box()), None);
+    close(&p).await;
+}
+
+async fn route_abort_error_code(c: &BrowserContext, port: u16) {
+    let p = new(c).await;
+    done!(p
+        .goto_builder(&super::url_static(port, "/empty.html"))
+        .goto());
+    done!(p.route("**/simple.json", |route, _| async move {
+        route
+            .abort(Some(AbortErrorCode::ConnectionFailed))
+            .await
+            .unwrap();
+    }));
+    let mut events = p.subscribe_event().unwrap();
+    let reason: String = done!(p.eval(
+        "async () => { try { await fetch('simple.json'); return 'resolved'; } catch (e) { return e.name; } }"
+    ));
+    // A failed connection surfaces as a network error rather than an HTTP response
+    assert_eq!(reason, "TypeError");
+    let mut failure = None;
+    while let Some(Ok(e)) = events.next().await {
+        if let page::Event::RequestFailed(r) = e {
+            failure = r.failure().unwrap();
+            break;
+        }
+    }
+    assert!(failure.is_some());
+    close(&p).await;
+}
+
+// The code is only visible in Chromium's net errors; any other would fail as the default `failed`
+async fn route_abort_net_error(c: &BrowserContext, port: u16) {
+    let p = new(c).await;
+    done!(p
+        .goto_builder(&super::url_static(port, "/empty.html"))
+        .goto());
+    done!(p.route("**/simple.json", |route, _| async move {
+        route
+            .abort(Some(AbortErrorCode::AccessDenied))
+            .await
+            .unwrap();
+    }));
+    let (failed, _) = tokio::join!(
+        p.expect_event(page::EventType::RequestFailed),
+        p.eval::<()>("() => { fetch('simple.json').catch(() => {}) }")
+    );
+    let failure = match failed.unwrap() {
+        page::Event::RequestFailed(r) => r.failure().unwrap(),
+        _ => unreachable!()
+    };
+    assert_eq!(failure.as_deref(), Some("net::ERR_ACCESS_DENIED"));
+    close(&p).await;
+}
+
+async fn dialogs(c: &BrowserContext) {
+    let p = new(c).await;
+    // Without a handler dialogs are dismissed instead of blocking the page
+    assert!(!done!(p.eval::<bool>("() => confirm('dismissed?')")));
+    done!(p.on_dialog(|dialog| async move {
+        assert_eq!(dialog.message().unwrap(), "ok?");
+        assert_eq!(dialog.r#type().unwrap(), "confirm");
+        assert_eq!(dialog.default_value().unwrap(), "");
+        dialog.accept(None).await.unwrap();
+    }));
+    assert!(done!(p.eval::<bool>("() => confirm('ok?')")));
+    done!(p.on_dialog(|dialog| async move {
+        assert_eq!(dialog.default_value().unwrap(), "default");
+        dialog.accept(Some("answer")).await.unwrap();
+    }));
+    let answer: String = done!(p.eval("() => prompt('name?', 'default')"));
+    assert_eq!(answer, "answer");
+    close(&p).await;
+}
+
+async fn console_args(c: &BrowserContext) {
+    let p = new(c).await;
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    p.on_console(move |msg| {
+        let tx = tx.clone();
+        async move {
+            let mut args = msg.args().unwrap();
+            let mut a = args[0].get_property("a").await.unwrap();
+            let a: i32 = a.json_value().await.unwrap();
+            let _ = tx.send((msg.r#type().unwrap(), args.len(), a, args.remove(0)));
+        }
+    })
+    .unwrap();
+    done!(p.eval::<()>("() => console.log({a: 1})"));
+    let (r#type, len, a, mut arg) = rx.recv().await.unwrap();
+    assert_eq!((r#type.as_str(), len, a), ("log", 1, 1));
+    // The handles were only valid while the handler ran
+    let mut disposed = false;
+    for _ in 0..50 {
+        if arg.json_value::<serde_json::Value>().await.is_err() {
+            disposed = true;
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    assert!(disposed);
+    close(&p).await;
+}
+
+async fn main_frame_persists(c: &BrowserContext, port: u16) {
+    let p = new(c).await;
+    let frame = p.main_frame();
+    let url1 = super::url_static(port, "/empty.html");
+    let url2 = super::url_static(port, "/form.html");
+    done!(p.goto_builder(&url1).goto());
+    assert_eq!(frame.url().unwrap(), url1);
+    done!(p.goto_builder(&url2).goto());
+    // The handle taken before navigating still refers to the main frame
+    assert_eq!(frame.url().unwrap(), url2);
+    assert!(frame == p.main_frame());
+    assert_eq!(done!(frame.title()), done!(p.title()));
+    done!(frame.eval::<()>("() => { document.body.dataset.marker = 'x'; }"));
+    assert_eq!(
+        done!(p.eval::<String>("() => document.body.dataset.marker")),
+        "x"
+    );
+    close(&p).await;
+}
+
+async fn on_download_data_url(c: &BrowserContext) {
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(r#"<a href="data:text/plain,hello" download="hello.txt">download</a>"#)
+        .set_content());
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    p.on_download(move |download| {
+        let tx = tx.clone();
+        async move {
+            let path = download.path().await.unwrap().unwrap();
+            let _ = tx.send((download.suggested_filename().to_owned(), path));
+        }
+    })
+    .unwrap();
+    done!(p.click_builder("a").click());
+    let (name, path) = rx.recv().await.unwrap();
+    assert_eq!(name, "hello.txt");
+    assert_eq!(std::fs::read_to_string(path).unwrap(), "hello");
+    close(&p).await;
+}
+
+async fn eval_invalid_expression(c: &BrowserContext) {
+    let p = new(c).await;
+    let start = std::time::Instant::now();
+    let err = p.eval::<i32>("() => { return (1 + 2; }").await.unwrap_err();
+    assert!(matches!(*err, playwright::Error::InvalidExpression(_)));
+    assert!(start.elapsed() < std::time::Duration::from_secs(1));
+    assert_eq!(done!(p.eval::<i32>("() => [1, 2].map(x => x * 2)[1]")), 4);
+    close(&p).await;
+}
+
+async fn contenteditable_fill_and_type(c: &BrowserContext) {
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(
+            r#"<div contenteditable id="editor">old</div><script>
+            window.keys = 0;
+            document.querySelector('#editor').addEventListener('keydown', () => keys++);
+            </script>"#
+        )
+        .set_content());
+    let editor = p.locator("#editor");
+    // Filling replaces the existing text
+    done!(editor.fill("hello", None));
+    assert_eq!(done!(editor.text_content(None)).as_deref(), Some("hello"));
+    // Put the caret at the end so typing appends
+    done!(p.eval::<()>(
+        "() => getSelection().setPosition(document.querySelector('#editor').firstChild, 5)"
+    ));
+    done!(editor.press_sequentially(" world", None));
+    assert_eq!(
+        done!(editor.text_content(None)).as_deref(),
+        Some("hello world")
+    );
+    let keys: i32 = done!(p.eval("() => window.keys"));
+    assert_eq!(keys, 6);
+    close(&p).await;
+}
+
+async fn element_screenshot(c: &BrowserContext) {
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(
+            "<div style='margin-top:2000px;width:57px;height:23px;border:2px solid;background:green'></div>"
+        )
+        .set_content());
+    let div = done!(p.query_selector("div")).unwrap();
+    let png = done!(div.screenshot_builder().await.screenshot());
+    assert!(png.starts_with(b"\x89PNG"));
+    // Width and height are the first fields of the IHDR chunk
+    let width = u32::from_be_bytes([png[16], png[17], png[18], png[19]]);
+    let height = u32::from_be_bytes([png[20], png[21], png[22], png[23]]);
+    // The element was scrolled into view and only its border box was captured
+    let b = done!(div.bounding_box()).unwrap();
+    assert!(b.y >= 0. && b.y < 2000., "{:?}", b);
+    assert_eq!((width, height), (b.width as u32, b.height as u32));
+    assert_eq!((width, height), (61, 27));
+    close(&p).await;
+}
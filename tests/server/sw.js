@@ -0,0 +1,6 @@
+self.addEventListener('activate', event => event.waitUntil(self.clients.claim()));
+self.addEventListener('fetch', event => {
+  if (event.request.url.endsWith('/from-sw')) {
+    event.respondWith(new Response('from service worker'));
+  }
+});
@@ -25,12 +25,18 @@ pub enum Which {
     Chromium,
 }
 
-// NOTE: chromium_page and firefox_page tests are disabled because they depend on
-// complex integration setup with a test server and persistent browser contexts
-// These are full integration tests, not unit tests
-// Uncomment to run with: cargo test --test test
-// playwright::runtime_test!(chromium_page, page(Which::Chromium).await);
-// playwright::runtime_test!(firefox_page, page(Which::Firefox).await);
+// Runs the browser_type, browser, browser_context and page suites against a
+// test server and a persistent context, so it only runs with `--ignored`
+playwright::runtime_test!(
+    #[ignore]
+    chromium_page,
+    page(Which::Chromium).await
+);
+playwright::runtime_test!(
+    #[ignore]
+    firefox_page,
+    page(Which::Firefox).await
+);
 // playwright::runtime_test!(webkit_page, page(Which::Webkit).await);
 
 playwright::runtime_test!(chromium_selectors, selectors(Which::Chromium).await);
@@ -41,17 +47,29 @@ playwright::runtime_test!(chromium_integration, integration(Which::Chromium).awa
 playwright::runtime_test!(firefox_integration, integration(Which::Firefox).await);
 // playwright::runtime_test!(webkit_integration, integration(Which::Webkit).await);
 
-// NOTE: chromium_devices, firefox_devices, and connect_over_cdp tests are disabled
-// because they depend on playwright.device() which is not initialized in this version
-// These are integration tests that require specific setup and are not critical for publishing
-// playwright::runtime_test!(chromium_devices, devices(Which::Chromium).await);
+playwright::runtime_test!(chromium_devices, devices(Which::Chromium).await);
 // playwright::runtime_test!(firefox_devices, devices(Which::Chromium).await);
 // playwright::runtime_test!(webkit_devices, devices(Which::Webkit).await);
 
-// playwright::runtime_test!(
-//     connect_over_cdp,
-//     connect::connect_over_cdp(Which::Chromium).await
-// );
+// Launches a Chromium with a remote debugging port, so it only runs with
+// `--ignored`
+playwright::runtime_test!(
+    #[ignore]
+    connect_over_cdp,
+    connect::connect_over_cdp(Which::Chromium).await
+);
+// Starts a browser server with the driver's CLI, so it only runs with
+// `--ignored`
+playwright::runtime_test!(
+    #[ignore]
+    chromium_connect,
+    connect::connect(Which::Chromium).await
+);
+playwright::runtime_test!(
+    #[ignore]
+    firefox_connect,
+    connect::connect(Which::Firefox).await
+);
 
 async fn page(which: Which) {
     std::fs::create_dir_all(temp_dir()).unwrap();